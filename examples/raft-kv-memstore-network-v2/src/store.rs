@@ -103,11 +103,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<StateMachineStore> {
             format!("--{}", snapshot_idx)
         };
 
-        let meta = SnapshotMeta {
-            last_log_id: last_applied_log,
-            last_membership,
-            snapshot_id,
-        };
+        let meta = SnapshotMeta::new(last_applied_log, last_membership, snapshot_id);
 
         let snapshot = StoredSnapshot {
             meta: meta.clone(),
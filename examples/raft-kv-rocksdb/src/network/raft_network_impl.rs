@@ -2,6 +2,7 @@ use std::any::Any;
 use std::fmt::Display;
 
 use openraft::error::InstallSnapshotError;
+use openraft::error::Malformed;
 use openraft::error::NetworkError;
 use openraft::error::RPCError;
 use openraft::error::RaftError;
@@ -73,7 +74,7 @@ impl std::error::Error for ErrWrap {}
 fn to_error<E: std::error::Error + 'static + Clone>(e: toy_rpc::Error, target: NodeId) -> RPCError<TypeConfig, E> {
     match e {
         toy_rpc::Error::IoError(e) => RPCError::Network(NetworkError::new(&e)),
-        toy_rpc::Error::ParseError(e) => RPCError::Network(NetworkError::new(&ErrWrap(e))),
+        toy_rpc::Error::ParseError(e) => RPCError::Malformed(Malformed::new(&ErrWrap(e))),
         toy_rpc::Error::Internal(e) => {
             let any: &dyn Any = &e;
             let error: &E = any.downcast_ref().unwrap();
@@ -36,6 +36,13 @@ pub trait AsyncRuntime: Debug + Default + PartialEq + Eq + OptionalSend + Option
     type Sleep: Future<Output = ()> + OptionalSend + OptionalSync;
 
     /// A measurement of a monotonically non-decreasing clock.
+    ///
+    /// Openraft reads the current time only with [`Instant::now()`] of this type, and waits only
+    /// with [`Self::sleep()`], [`Self::sleep_until()`], [`Self::timeout()`] and
+    /// [`Self::timeout_at()`]. Thus this runtime is the clock of Openraft: a runtime with a
+    /// controllable clock makes election timeouts, leader leases and other time based behaviors
+    /// deterministic. For example, with [`TokioRuntime`], a test can freeze the time with
+    /// `tokio::time::pause()` and move it forward with `tokio::time::advance()`.
     type Instant: Instant;
 
     /// The timeout error type.
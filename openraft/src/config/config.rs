@@ -10,9 +10,11 @@ use rand::Rng;
 
 use crate::config::error::ConfigError;
 use crate::raft_state::LogStateReader;
+use crate::storage_error::Violation;
 use crate::AsyncRuntime;
 use crate::LogIdOptionExt;
 use crate::NodeId;
+use crate::StorageError;
 
 /// Log compaction and snapshot policy.
 ///
@@ -47,6 +49,37 @@ impl SnapshotPolicy {
     }
 }
 
+/// How a node flushes the log entries it appends to [`RaftLogStorage`].
+///
+/// [`RaftLogStorage`]: crate::storage::RaftLogStorage
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LogFlushMode {
+    /// Wait for every append to be flushed before proceeding.
+    PerEntry,
+
+    /// Submit appends without waiting and wait for the flushes of all of them in a batch.
+    ///
+    /// A batch is waited for when `max_entries` entries are pending, when `max_delay` has
+    /// elapsed since the first pending append, or when another operation depends on the pending
+    /// entries being persisted. An entry is never acknowledged to the leader or committed before
+    /// its flush completes.
+    ///
+    /// Only a leader batches the appends of client writes. A follower or learner waits for the
+    /// flush of every AppendEntries request before responding to it, because the leader does not
+    /// send the next request before receiving the response, and holding the flush back would only
+    /// delay it. Under load, the leader sends more entries in every request instead, thus a
+    /// follower flushes less often than once per entry in either mode.
+    Batched {
+        /// The maximum time an appended entry stays un-waited-for.
+        max_delay: Duration,
+
+        /// The maximum number of entries in a batch.
+        max_entries: u64,
+    },
+}
+
 /// Parse number with unit such as 5.3 KB
 fn parse_bytes_with_unit(src: &str) -> Result<u64, ConfigError> {
     let res = byte_unit::Byte::from_str(src).map_err(|e| ConfigError::InvalidNumber {
@@ -84,6 +117,44 @@ fn parse_snapshot_policy(src: &str) -> Result<SnapshotPolicy, ConfigError> {
     Ok(SnapshotPolicy::LogsSinceLast(n_logs))
 }
 
+fn parse_log_flush_mode(src: &str) -> Result<LogFlushMode, ConfigError> {
+    let syntax = "per_entry|batched:<max_delay_ms>:<max_entries>";
+
+    if src == "per_entry" {
+        return Ok(LogFlushMode::PerEntry);
+    }
+
+    let elts = src.split(':').collect::<Vec<_>>();
+    if elts.len() != 3 || elts[0] != "batched" {
+        return Err(ConfigError::InvalidLogFlushMode {
+            syntax: syntax.to_string(),
+            invalid: src.to_string(),
+        });
+    }
+
+    let parse_u64 = |s: &str| {
+        s.parse::<u64>().map_err(|e| ConfigError::InvalidNumber {
+            invalid: src.to_string(),
+            reason: e.to_string(),
+        })
+    };
+
+    let max_delay_ms = parse_u64(elts[1])?;
+    let max_entries = parse_u64(elts[2])?;
+
+    if max_entries == 0 {
+        return Err(ConfigError::InvalidLogFlushMode {
+            syntax: syntax.to_string(),
+            invalid: src.to_string(),
+        });
+    }
+
+    Ok(LogFlushMode::Batched {
+        max_delay: Duration::from_millis(max_delay_ms),
+        max_entries,
+    })
+}
+
 /// The runtime configuration for a Raft node.
 ///
 /// The default values used by this type should generally work well for Raft clusters which will
@@ -173,16 +244,366 @@ pub struct Config {
     #[clap(long, default_value = "3MiB", value_parser=parse_bytes_with_unit)]
     pub snapshot_max_chunk_size: u64,
 
+    /// The maximum size in bytes of the application data of a single log entry.
+    ///
+    /// A client write whose data is larger than this is rejected by the leader with
+    /// [`EntryTooLarge`] before it is appended to the log, so that an entry that can never be
+    /// replicated does not get stuck in the log. The size is obtained with
+    /// [`RaftPayload::app_data_size()`]; entries that do not report a size are not checked.
+    ///
+    /// It is disabled by default, by setting it to `0`.
+    ///
+    /// [`EntryTooLarge`]: crate::error::EntryTooLarge
+    /// [`RaftPayload::app_data_size()`]: crate::entry::RaftPayload::app_data_size
+    #[clap(long, default_value = "0", value_parser=parse_bytes_with_unit)]
+    pub max_entry_size_bytes: u64,
+
+    /// How appended log entries are flushed: `per_entry` or `batched:<max_delay_ms>:<max_entries>`.
+    ///
+    /// With `per_entry`, every append waits for its flush before Raft proceeds. With `batched`,
+    /// appends are submitted to the storage without waiting, and their flushes are waited for
+    /// together, which reduces the number of `fsync` a storage has to do under load. Either way,
+    /// an entry is acknowledged or committed only after it is flushed. Only a leader batches
+    /// flushes.
+    ///
+    /// See [`LogFlushMode`].
+    #[clap(long, default_value = "per_entry", value_parser=parse_log_flush_mode)]
+    pub log_flush_mode: LogFlushMode,
+
     /// The maximum number of logs to keep that are already included in **snapshot**.
     ///
     /// Logs that are not in snapshot will never be purged.
+    /// Thus setting it to `N` guarantees that at least the most recent `N` logs are kept after
+    /// purging, e.g., for a downstream consumer to tail them, even if they are all included in the
+    /// snapshot.
+    ///
+    /// A follower that needs logs older than the kept ones is replicated with a snapshot instead.
     #[clap(long, default_value = "1000")]
     pub max_in_snapshot_log_to_keep: u64,
 
+    /// Whether to purge logs that are included in a snapshot after the snapshot is built.
+    ///
+    /// When disabled, logs are kept after building a snapshot, e.g., to be inspected for debugging
+    /// or retention, and are only purged when requested with [`Trigger::purge_log()`]. Logs are
+    /// still replaced when a snapshot is installed from the leader.
+    ///
+    /// A leader replicates logs to a lagging follower as long as they are not purged, and sends a
+    /// snapshot only when the logs are purged.
+    ///
+    /// By default it is `true`.
+    ///
+    /// [`Trigger::purge_log()`]: crate::raft::trigger::Trigger::purge_log
+    #[clap(long,
+           default_value_t = true,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub purge_after_snapshot: bool,
+
     /// The minimal number of applied logs to purge in a batch.
     #[clap(long, default_value = "1")]
     pub purge_batch_size: u64,
 
+    /// Whether the leader tells a node that is just removed from membership that it is no longer a
+    /// member.
+    ///
+    /// When enabled, the leader sends the membership log that removes a node to it, in a single
+    /// best-effort AppendEntries request. If the removed node accepts it, it becomes a learner and
+    /// stops starting elections. The request is sent again once the log is committed, so that
+    /// [`Raft::removed_from_cluster()`] returns on the removed node. Delivery is not retried and
+    /// never blocks the membership change, so an unreachable removed node has to be shut down by
+    /// the application.
+    ///
+    /// [`Raft::removed_from_cluster()`]: crate::Raft::removed_from_cluster
+    #[clap(long)]
+    pub notify_removed_nodes: bool,
+
+    /// Whether a leader rejects client writes until a quorum has acknowledged it in its term.
+    ///
+    /// A newly elected leader has not yet heard from a quorum by replication. When enabled, it
+    /// rejects [`Raft::client_write()`] with [`NotReady`] until a quorum has responded to its
+    /// replication, e.g., when the blank log it proposes upon election is accepted. This narrows
+    /// the window in which a write is accepted by a leader that can not commit it. Membership
+    /// changes are not rejected.
+    ///
+    /// [`Raft::client_write()`]: crate::Raft::client_write
+    /// [`NotReady`]: crate::error::NotReady
+    #[clap(long)]
+    pub require_quorum_before_writes: bool,
+
+    /// Whether a leader rejects client writes while a membership change is in progress.
+    ///
+    /// When enabled, from the time a membership config is proposed until it is committed,
+    /// including the joint config and the following uniform config of a
+    /// [`Raft::change_membership()`], the leader rejects [`Raft::client_write()`] with
+    /// [`MembershipChanging`]. Membership entries are not affected. By default writes continue
+    /// during a membership change.
+    ///
+    /// [`Raft::change_membership()`]: crate::Raft::change_membership
+    /// [`Raft::client_write()`]: crate::Raft::client_write
+    /// [`MembershipChanging`]: crate::error::MembershipChanging
+    #[clap(long)]
+    pub reject_writes_during_membership_change: bool,
+
+    /// Whether [`Raft::change_membership()`] rejects a change that makes a lagging node a voter.
+    ///
+    /// When enabled, every node that becomes a voter by a change has to be a line-rate learner,
+    /// i.e., its replication lag is within [`replication_lag_threshold`], otherwise the change
+    /// fails with [`LearnerNotReady`]. Wait for a learner with [`Raft::await_line_rate()`] before
+    /// promoting it. Since a node added with [`ChangeMembers::AddVoters`] is not yet replicated
+    /// to, it has to be added as a learner first.
+    ///
+    /// By default a lagging node is counted in the quorum as soon as it becomes a voter, which
+    /// may stall commits until it catches up.
+    ///
+    /// [`Raft::change_membership()`]: crate::Raft::change_membership
+    /// [`replication_lag_threshold`]: Self::replication_lag_threshold
+    /// [`LearnerNotReady`]: crate::error::LearnerNotReady
+    /// [`Raft::await_line_rate()`]: crate::Raft::await_line_rate
+    /// [`ChangeMembers::AddVoters`]: crate::ChangeMembers::AddVoters
+    #[clap(long)]
+    pub reject_lagging_new_voters: bool,
+
+    /// The number of times [`Raft::change_membership()`] retries a change that is rejected for a
+    /// transient reason.
+    ///
+    /// A change is retried if it fails with [`InProgress`], e.g., the previous membership config
+    /// can not be committed during a brief loss of quorum, or with [`LearnerNotReady`] when
+    /// [`reject_lagging_new_voters`](Self::reject_lagging_new_voters) is enabled. It is
+    /// not retried if the requested membership is invalid. The first retry waits for
+    /// `heartbeat_interval`, and the wait doubles for every following retry, up to
+    /// `election_timeout_max`.
+    ///
+    /// It is disabled by default, by setting it to `0`.
+    ///
+    /// [`Raft::change_membership()`]: crate::Raft::change_membership
+    /// [`InProgress`]: crate::error::InProgress
+    /// [`LearnerNotReady`]: crate::error::LearnerNotReady
+    #[clap(long, default_value = "0")]
+    pub change_membership_retries: u64,
+
+    /// The period in milliseconds after a leader steps down, during which it uses a shorter
+    /// election timeout, i.e., half of `election_timeout_min`.
+    ///
+    /// A node that was just leader before a brief disruption, such as a short network partition or
+    /// a rolling restart of other nodes, then likely starts an election before other nodes and
+    /// takes back the leadership, which reduces leadership churn. It only affects when an
+    /// election starts: the node still needs votes from a quorum and the leader lease is still
+    /// respected.
+    ///
+    /// It is disabled by default, by setting it to `0`.
+    #[clap(long, default_value = "0")]
+    pub leader_affinity_window: u64,
+
+    /// The read lease in milliseconds, used by [`Raft::client_read_lease()`].
+    ///
+    /// A leader serves lease reads only if a quorum has acknowledged it within this period. Once
+    /// the quorum contact is lost, lease reads are rejected at once, before the leader steps down.
+    ///
+    /// It must not be greater than `election_timeout_max`, during which a follower refuses to vote
+    /// for another candidate after hearing from the leader. `0` means to use
+    /// `election_timeout_min`.
+    ///
+    /// [`Raft::client_read_lease()`]: crate::Raft::client_read_lease
+    #[clap(long, default_value = "0")]
+    pub read_lease: u64,
+
+    /// The time in milliseconds a new leader waits before serving lease reads.
+    ///
+    /// A new leader never serves lease reads before it commits the blank log of its term, i.e.,
+    /// before it knows its log contains every committed entry. This adds an extra warm-up period,
+    /// counted from when it becomes leader, during which [`Raft::client_read_lease()`] is rejected
+    /// with a `LeaderNotReady` error as well.
+    ///
+    /// It is disabled by default, by setting it to `0`.
+    ///
+    /// [`Raft::client_read_lease()`]: crate::Raft::client_read_lease
+    #[clap(long, default_value = "0")]
+    pub read_lease_warmup: u64,
+
+    /// Whether a leader skips a heartbeat to a follower that has received an AppendEntries from it
+    /// within the last `heartbeat_interval`.
+    ///
+    /// Any AppendEntries, with or without log entries, resets the election timer of a follower
+    /// and serves as a heartbeat. Skipping the redundant heartbeats saves RPCs on a busy cluster
+    /// with many followers. A follower still hears from the leader at least once every two
+    /// heartbeat intervals, thus `election_timeout_min` must be greater than twice
+    /// `heartbeat_interval` when it is enabled.
+    #[clap(long)]
+    pub heartbeat_suppression: bool,
+
+    /// The maximum number of followers or learners a leader sends snapshot to at the same time.
+    ///
+    /// Sending snapshots to many nodes at once, e.g., when many nodes rejoin after a failure, may
+    /// saturate the disk or network of the leader. Once the limit is reached, other nodes that
+    /// need a snapshot keep receiving heartbeats, and are sent a snapshot when a transfer finishes.
+    ///
+    /// It is disabled by default, by setting it to `0`.
+    #[clap(long, default_value = "0")]
+    pub max_concurrent_snapshot_transfers: u64,
+
+    /// The maximum number of client writes per second a leader accepts.
+    ///
+    /// A write exceeding the rate is rejected with [`RateLimited`] instead of being queued, to
+    /// protect the cluster from a runaway client. At most one second worth of writes can be
+    /// accepted in a burst. The rate is tracked by the leader and is reset when a new leader is
+    /// established.
+    ///
+    /// It is disabled by default, by setting it to `0`.
+    ///
+    /// [`RateLimited`]: crate::error::RateLimited
+    #[clap(long, default_value = "0")]
+    pub max_write_rate: u64,
+
+    /// The maximum number of entries in the log, i.e., entries not yet purged, before the leader
+    /// refuses client writes.
+    ///
+    /// It is a last resort to protect the disk from unbounded log growth, e.g., when purging is
+    /// held back by a lagging follower. A write that would exceed the limit is rejected with
+    /// [`LogFull`], and a snapshot is triggered so that the log can be purged.
+    ///
+    /// It is disabled by default, by setting it to `0`.
+    ///
+    /// [`LogFull`]: crate::error::LogFull
+    #[clap(long, default_value = "0")]
+    pub max_log_entries: u64,
+
+    /// The extra election timeout in milliseconds added for every voter other than this node.
+    ///
+    /// In a large cluster a vote round-trip takes longer, and a fixed election timeout may cause
+    /// spurious elections. With this option the effective election timeout becomes
+    /// `election_timeout + election_timeout_per_voter * (voters - 1)`, where `voters` is the
+    /// number of voters in the current effective membership. It is re-evaluated whenever the
+    /// membership changes.
+    ///
+    /// It is disabled by default, by setting it to `0`.
+    #[clap(long, default_value = "0")]
+    pub election_timeout_per_voter: u64,
+
+    /// The maximum number of terms a node may advance by its own elections, beyond the greatest
+    /// term it has seen from other nodes.
+    ///
+    /// A node that can not reach others, e.g., during a long partition, keeps starting elections
+    /// and increasing its term. When it comes back, its greater term forces the others to give up
+    /// their term, disrupting the cluster. With this option, once a node's term reaches the
+    /// greatest term seen from another node plus `max_term_jump`, it does not increase its term
+    /// any more: it re-sends vote requests for its own vote in the current term, or skips the
+    /// election if it has voted for another node in this term. Receiving any message from another
+    /// node with a greater term lifts the limit accordingly.
+    ///
+    /// It is disabled by default, by setting it to `0`.
+    #[clap(long, default_value = "0")]
+    pub max_term_jump: u64,
+
+    /// The capacity of the broadcast channel of [`RaftEvent`]s returned by [`Raft::events()`].
+    ///
+    /// A subscriber that falls behind by more than this number of events misses the oldest ones,
+    /// and is informed of how many events are missed.
+    ///
+    /// [`RaftEvent`]: crate::metrics::RaftEvent
+    /// [`Raft::events()`]: crate::Raft::events
+    #[clap(long, default_value = "1024")]
+    pub event_channel_capacity: usize,
+
+    /// The seed of the random number generator for the randomized election timeout.
+    ///
+    /// By default the election timeout is picked with the thread local RNG of the async runtime.
+    /// If a seed is set, a node picks its election timeouts with an RNG seeded with it and the
+    /// node id, thus the sequence of election timeouts of a node is reproducible, e.g., a test can
+    /// force a specific election order by choosing the seed. A new election timeout is picked
+    /// every time the node starts an election, and nodes sharing the same seed get different
+    /// election timeouts.
+    #[clap(long)]
+    pub election_timeout_seed: Option<u64>,
+
+    /// The grace period in milliseconds before a node without a known leader reports
+    /// [`LeadershipStatus::Leaderless`] in metrics.
+    ///
+    /// Within this period after losing the leader, it reports
+    /// [`LeadershipStatus::ElectingNewLeader`], so that a normal failover does not look like a
+    /// leaderless cluster. The status is re-evaluated when metrics are updated, e.g., on every
+    /// tick.
+    ///
+    /// By default it is `0`, i.e., a node without a known leader is reported as leaderless at
+    /// once.
+    ///
+    /// [`LeadershipStatus::Leaderless`]: crate::metrics::LeadershipStatus::Leaderless
+    /// [`LeadershipStatus::ElectingNewLeader`]: crate::metrics::LeadershipStatus::ElectingNewLeader
+    #[clap(long, default_value = "0")]
+    pub leaderless_grace: u64,
+
+    /// The interval in milliseconds after which a leader with an idle log appends a blank entry.
+    ///
+    /// A leader commits entries of previous terms only after an entry of its own term is
+    /// committed. A new leader always appends a blank entry, but if that entry can not be
+    /// committed at once, e.g., a quorum is not reachable for a while, and no client writes
+    /// follow, the commit index does not advance. With this option, if no entry is appended to the
+    /// log of a leader within this interval, the leader appends a blank entry, which, once
+    /// committed, commits every entry before it.
+    ///
+    /// The log is checked on every tick, thus the actual interval is rounded up to the tick
+    /// interval.
+    ///
+    /// It is disabled by default, by setting it to `0`.
+    #[clap(long, default_value = "0")]
+    pub idle_noop_interval: u64,
+
+    /// Whether to degrade to read-only mode instead of shutting down on a storage IO error.
+    ///
+    /// When enabled, a [`StorageError::IO`] returned by the log store or state machine does not
+    /// stop `RaftCore`. Instead a leader steps down and the node enters a degraded mode reported
+    /// by [`RaftMetrics::storage_degraded`], in which it:
+    /// - does not start elections;
+    /// - does not write to the storage, e.g., it does not build snapshots or purge logs;
+    /// - rejects client writes with [`ForwardToLeader`];
+    /// - fails replication, vote and snapshot requests with [`Fatal::StorageError`], so that the
+    ///   rest of the cluster treats it as unreachable;
+    /// - still serves reads from its state machine, e.g., [`Raft::with_state_machine()`].
+    ///
+    /// Committing logs that the log store has not reported durable always enters this mode,
+    /// even if this option is disabled: the logs are not applied and a leader steps down. Other
+    /// [`StorageError::Defensive`] errors still shut down `RaftCore`, because they indicate
+    /// corrupted data. The node has to be restarted after the storage recovers, so that the
+    /// in-memory raft state is reloaded from the storage.
+    ///
+    /// By default it is `false`.
+    ///
+    /// [`StorageError::IO`]: crate::StorageError::IO
+    /// [`StorageError::Defensive`]: crate::StorageError::Defensive
+    /// [`RaftMetrics::storage_degraded`]: crate::RaftMetrics::storage_degraded
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    /// [`Fatal::StorageError`]: crate::error::Fatal::StorageError
+    /// [`Raft::with_state_machine()`]: crate::Raft::with_state_machine
+    #[clap(long)]
+    pub degrade_on_storage_error: bool,
+
+    /// Whether to save the committed log id with [`RaftLogStorage::save_committed()`] every time
+    /// it is updated.
+    ///
+    /// The saved committed log id is restored on startup with
+    /// [`RaftLogStorage::read_committed()`], and the state machine is recovered by re-applying
+    /// logs up to it. It is advisory: it only speeds up recovery and avoids the applied state
+    /// falling back upon restart. Safety still derives from the quorum, not from the saved value.
+    /// A saved committed log id greater than the last log id in the log store is ignored on
+    /// startup.
+    ///
+    /// Disable it to save one storage write per commit, if the state machine persists its state
+    /// in `apply()` or the application tolerates the applied state falling back to the last
+    /// snapshot upon restart.
+    ///
+    /// By default it is `true`.
+    ///
+    /// [`RaftLogStorage::save_committed()`]: crate::storage::RaftLogStorage::save_committed
+    /// [`RaftLogStorage::read_committed()`]: crate::storage::RaftLogStorage::read_committed
+    #[clap(long,
+           default_value_t = true,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub persist_commit_index: bool,
+
     /// Enable or disable tick.
     ///
     /// If ticking is disabled, timeout based events are all disabled:
@@ -256,6 +677,17 @@ impl Config {
         RT::thread_rng().gen_range(self.election_timeout_min..self.election_timeout_max)
     }
 
+    /// Whether `RaftCore` enters read-only degraded mode instead of shutting down on `error`.
+    ///
+    /// Committing logs that are not durable always steps down, an IO error does only if it is
+    /// enabled, see [`degrade_on_storage_error`](Self::degrade_on_storage_error).
+    pub(crate) fn degrades_on<NID: NodeId>(&self, error: &StorageError<NID>) -> bool {
+        match error {
+            StorageError::IO { .. } => self.degrade_on_storage_error,
+            StorageError::Defensive { source } => matches!(source.violation, Violation::ApplyNonDurable { .. }),
+        }
+    }
+
     /// Get the timeout for sending and installing the last snapshot segment.
     pub fn install_snapshot_timeout(&self) -> Duration {
         Duration::from_millis(self.install_snapshot_timeout)
@@ -275,6 +707,15 @@ impl Config {
         }
     }
 
+    /// Get the read lease for serving lease reads.
+    pub fn read_lease(&self) -> Duration {
+        if self.read_lease > 0 {
+            Duration::from_millis(self.read_lease)
+        } else {
+            Duration::from_millis(self.election_timeout_min)
+        }
+    }
+
     /// Build a `Config` instance from a series of command line arguments.
     ///
     /// The first element in `args` must be the application name.
@@ -302,10 +743,28 @@ impl Config {
             });
         }
 
+        if self.heartbeat_suppression && self.election_timeout_min <= self.heartbeat_interval * 2 {
+            return Err(ConfigError::ElectionTimeoutLTSuppressedHeartBeat {
+                election_timeout_min: self.election_timeout_min,
+                heartbeat_interval: self.heartbeat_interval,
+            });
+        }
+
         if self.max_payload_entries == 0 {
             return Err(ConfigError::MaxPayloadIs0);
         }
 
+        if self.event_channel_capacity == 0 {
+            return Err(ConfigError::EventChannelCapacityIs0);
+        }
+
+        if self.read_lease > self.election_timeout_max {
+            return Err(ConfigError::ReadLeaseGTElectionTimeout {
+                read_lease: self.read_lease,
+                election_timeout_max: self.election_timeout_max,
+            });
+        }
+
         Ok(self)
     }
 }
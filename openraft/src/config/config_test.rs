@@ -1,8 +1,17 @@
 use core::time::Duration;
 
+use anyerror::AnyError;
+
 use crate::config::error::ConfigError;
+use crate::storage_error::DefensiveError;
+use crate::storage_error::ErrorSubject;
+use crate::storage_error::Violation;
+use crate::testing::log_id;
 use crate::Config;
+use crate::LogFlushMode;
 use crate::SnapshotPolicy;
+use crate::StorageError;
+use crate::StorageIOError;
 
 #[test]
 fn test_config_defaults() {
@@ -17,6 +26,21 @@ fn test_config_defaults() {
 
     assert_eq!(3 * 1024 * 1024, cfg.snapshot_max_chunk_size);
     assert_eq!(SnapshotPolicy::LogsSinceLast(5000), cfg.snapshot_policy);
+    assert_eq!(0, cfg.max_entry_size_bytes);
+    assert!(!cfg.notify_removed_nodes);
+    assert!(!cfg.require_quorum_before_writes);
+    assert!(!cfg.reject_writes_during_membership_change);
+    assert!(!cfg.reject_lagging_new_voters);
+    assert_eq!(0, cfg.change_membership_retries);
+    assert_eq!(0, cfg.leader_affinity_window);
+    assert_eq!(LogFlushMode::PerEntry, cfg.log_flush_mode);
+    assert_eq!(None, cfg.election_timeout_seed);
+    assert!(!cfg.degrade_on_storage_error);
+    assert!(cfg.persist_commit_index);
+    assert!(cfg.purge_after_snapshot);
+    assert_eq!(0, cfg.max_term_jump);
+    assert_eq!(1024, cfg.event_channel_capacity);
+    assert_eq!(0, cfg.idle_noop_interval);
 }
 
 #[test]
@@ -44,6 +68,35 @@ fn test_invalid_election_timeout_config_produces_expected_error() {
         election_timeout_min: 1000,
         heartbeat_interval: 1500
     });
+
+    let config = Config {
+        election_timeout_min: 1000,
+        election_timeout_max: 2000,
+        read_lease: 2001,
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    let err = res.unwrap_err();
+    assert_eq!(err, ConfigError::ReadLeaseGTElectionTimeout {
+        read_lease: 2001,
+        election_timeout_max: 2000
+    });
+
+    let config = Config {
+        election_timeout_min: 1000,
+        election_timeout_max: 2000,
+        heartbeat_interval: 500,
+        heartbeat_suppression: true,
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    let err = res.unwrap_err();
+    assert_eq!(err, ConfigError::ElectionTimeoutLTSuppressedHeartBeat {
+        election_timeout_min: 1000,
+        heartbeat_interval: 500
+    });
 }
 
 #[test]
@@ -61,7 +114,21 @@ fn test_build() -> anyhow::Result<()> {
         "--replication-lag-threshold=203",
         "--snapshot-max-chunk-size=204",
         "--max-in-snapshot-log-to-keep=205",
+        "--max-entry-size-bytes=206",
         "--purge-batch-size=207",
+        "--leader-affinity-window=208",
+        "--max-concurrent-snapshot-transfers=209",
+        "--max-write-rate=210",
+        "--election-timeout-per-voter=211",
+        "--election-timeout-seed=212",
+        "--leaderless-grace=213",
+        "--max-log-entries=214",
+        "--change-membership-retries=215",
+        "--notify-removed-nodes",
+        "--require-quorum-before-writes",
+        "--reject-writes-during-membership-change",
+        "--reject-lagging-new-voters",
+        "--degrade-on-storage-error",
     ])?;
 
     assert_eq!("bar", config.cluster_name);
@@ -79,7 +146,21 @@ fn test_build() -> anyhow::Result<()> {
     assert_eq!(203, config.replication_lag_threshold);
     assert_eq!(204, config.snapshot_max_chunk_size);
     assert_eq!(205, config.max_in_snapshot_log_to_keep);
+    assert_eq!(206, config.max_entry_size_bytes);
     assert_eq!(207, config.purge_batch_size);
+    assert_eq!(208, config.leader_affinity_window);
+    assert_eq!(209, config.max_concurrent_snapshot_transfers);
+    assert_eq!(210, config.max_write_rate);
+    assert_eq!(211, config.election_timeout_per_voter);
+    assert_eq!(Some(212), config.election_timeout_seed);
+    assert_eq!(213, config.leaderless_grace);
+    assert_eq!(214, config.max_log_entries);
+    assert_eq!(215, config.change_membership_retries);
+    assert!(config.notify_removed_nodes);
+    assert!(config.require_quorum_before_writes);
+    assert!(config.reject_writes_during_membership_change);
+    assert!(config.reject_lagging_new_voters);
+    assert!(config.degrade_on_storage_error);
 
     // Test config methods
     #[allow(deprecated)]
@@ -98,6 +179,68 @@ fn test_build() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_config_degrades_on() -> anyhow::Result<()> {
+    let io_err: StorageError<u64> = StorageIOError::write_logs(AnyError::error("disk")).into();
+    let non_durable: StorageError<u64> =
+        DefensiveError::new(ErrorSubject::Apply(log_id(1, 1, 5)), Violation::ApplyNonDurable {
+            durable: Some(log_id(1, 1, 3)),
+            upto: log_id(1, 1, 5),
+        })
+        .into();
+    let corrupted: StorageError<u64> = DefensiveError::new(ErrorSubject::Logs, Violation::LogsNonConsecutive {
+        prev: Some(log_id(1, 1, 3)),
+        next: log_id(1, 1, 5),
+    })
+    .into();
+
+    // Disabled: only committing non-durable logs does not shut down RaftCore.
+    let config = Config::default();
+    assert!(!config.degrades_on(&io_err));
+    assert!(config.degrades_on(&non_durable));
+    assert!(!config.degrades_on(&corrupted));
+
+    let config = Config::build(&["foo", "--degrade-on-storage-error"])?;
+    assert!(config.degrades_on(&io_err));
+    assert!(config.degrades_on(&non_durable));
+    assert!(!config.degrades_on(&corrupted));
+
+    Ok(())
+}
+
+#[test]
+fn test_config_read_lease() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--election-timeout-min=100", "--election-timeout-max=200"])?;
+    assert_eq!(0, config.read_lease);
+    assert_eq!(
+        Duration::from_millis(100),
+        config.read_lease(),
+        "by default read_lease is election_timeout_min"
+    );
+
+    let config = Config::build(&[
+        "foo",
+        "--election-timeout-min=100",
+        "--election-timeout-max=200",
+        "--read-lease=150",
+    ])?;
+    assert_eq!(Duration::from_millis(150), config.read_lease());
+
+    assert_eq!(0, config.read_lease_warmup);
+    let config = Config::build(&["foo", "--read-lease-warmup=500"])?;
+    assert_eq!(500, config.read_lease_warmup);
+
+    let res = Config::build(&[
+        "foo",
+        "--election-timeout-min=100",
+        "--election-timeout-max=200",
+        "--read-lease=201",
+    ]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
 #[test]
 fn test_config_snapshot_policy() -> anyhow::Result<()> {
     let config = Config::build(&["foo", "--snapshot-policy=never"])?;
@@ -112,6 +255,32 @@ fn test_config_snapshot_policy() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_config_log_flush_mode() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--log-flush-mode=per_entry"])?;
+    assert_eq!(LogFlushMode::PerEntry, config.log_flush_mode);
+
+    let config = Config::build(&["foo", "--log-flush-mode=batched:5:64"])?;
+    assert_eq!(
+        LogFlushMode::Batched {
+            max_delay: Duration::from_millis(5),
+            max_entries: 64
+        },
+        config.log_flush_mode
+    );
+
+    let res = Config::build(&["foo", "--log-flush-mode=batched:5:0"]);
+    assert!(res.is_err());
+
+    let res = Config::build(&["foo", "--log-flush-mode=batched:5"]);
+    assert!(res.is_err());
+
+    let res = Config::build(&["foo", "--log-flush-mode=bar:5:64"]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
 #[test]
 fn test_config_enable_tick() -> anyhow::Result<()> {
     let config = Config::build(&["foo", "--enable-tick=false"])?;
@@ -146,6 +315,55 @@ fn test_config_enable_heartbeat() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_config_persist_commit_index() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--persist-commit-index=false"])?;
+    assert!(!config.persist_commit_index);
+
+    let config = Config::build(&["foo", "--persist-commit-index"])?;
+    assert!(config.persist_commit_index);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_purge_after_snapshot() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--purge-after-snapshot=false"])?;
+    assert!(!config.purge_after_snapshot);
+
+    let config = Config::build(&["foo", "--purge-after-snapshot"])?;
+    assert!(config.purge_after_snapshot);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_max_term_jump() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--max-term-jump=3"])?;
+    assert_eq!(3, config.max_term_jump);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_event_channel_capacity() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--event-channel-capacity=16"])?;
+    assert_eq!(16, config.event_channel_capacity);
+
+    let res = Config::build(&["foo", "--event-channel-capacity=0"]);
+    assert_eq!(Err(ConfigError::EventChannelCapacityIs0), res);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_idle_noop_interval() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--idle-noop-interval=500"])?;
+    assert_eq!(500, config.idle_noop_interval);
+
+    Ok(())
+}
+
 #[test]
 fn test_config_enable_elect() -> anyhow::Result<()> {
     let config = Config::build(&["foo", "--enable-elect=false"])?;
@@ -14,15 +14,30 @@ pub enum ConfigError {
     #[error("max_payload_entries must be > 0")]
     MaxPayloadIs0,
 
+    #[error("event_channel_capacity must be > 0")]
+    EventChannelCapacityIs0,
+
     #[error("election_timeout_min({election_timeout_min}) must be > heartbeat_interval({heartbeat_interval})")]
     ElectionTimeoutLTHeartBeat {
         election_timeout_min: u64,
         heartbeat_interval: u64,
     },
 
+    #[error("election_timeout_min({election_timeout_min}) must be > 2 * heartbeat_interval({heartbeat_interval}) when heartbeat_suppression is enabled")]
+    ElectionTimeoutLTSuppressedHeartBeat {
+        election_timeout_min: u64,
+        heartbeat_interval: u64,
+    },
+
+    #[error("read_lease({read_lease}) must be <= election_timeout_max({election_timeout_max})")]
+    ReadLeaseGTElectionTimeout { read_lease: u64, election_timeout_max: u64 },
+
     #[error("snapshot policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
     InvalidSnapshotPolicy { invalid: String, syntax: String },
 
+    #[error("log flush mode string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidLogFlushMode { invalid: String, syntax: String },
+
     #[error("{reason} when parsing {invalid:?}")]
     InvalidNumber { invalid: String, reason: String },
 }
@@ -4,6 +4,7 @@ mod error;
 #[cfg(test)] mod config_test;
 
 pub use config::Config;
+pub use config::LogFlushMode;
 pub(crate) use config::RuntimeConfig;
 pub use config::SnapshotPolicy;
 pub use error::ConfigError;
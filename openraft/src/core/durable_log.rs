@@ -0,0 +1,111 @@
+//! Track the last log id that is durably stored, to guard applying logs that are not.
+
+use crate::storage_error::DefensiveError;
+use crate::storage_error::ErrorSubject;
+use crate::storage_error::Violation;
+use crate::LogId;
+use crate::LogIdOptionExt;
+use crate::NodeId;
+
+/// The last log id that the log storage has reported to be durably stored.
+///
+/// A log entry must not be applied before it is durable: otherwise, after a crash, the state
+/// machine may contain an entry that is lost from the log, and may differ from other nodes.
+/// Openraft waits for log flushes before committing, and this is an additional check of that
+/// invariant in the commit-to-apply path.
+pub(crate) struct DurableLog<NID>
+where NID: NodeId
+{
+    durable: Option<LogId<NID>>,
+}
+
+impl<NID> DurableLog<NID>
+where NID: NodeId
+{
+    /// Create with the last log id loaded from storage at startup, which is already durable.
+    pub(crate) fn new(durable: Option<LogId<NID>>) -> Self {
+        Self { durable }
+    }
+
+    pub(crate) fn durable(&self) -> Option<&LogId<NID>> {
+        self.durable.as_ref()
+    }
+
+    /// Update when the log storage reports that logs up to `log_id` are flushed, or when logs up
+    /// to `log_id` are replaced by an installed snapshot.
+    pub(crate) fn update(&mut self, log_id: LogId<NID>) {
+        if Some(&log_id) > self.durable.as_ref() {
+            self.durable = Some(log_id);
+        }
+    }
+
+    /// Update when logs since index `since` are deleted from the storage.
+    ///
+    /// `prev` is the log id right before `since`, which becomes the last durable log id.
+    pub(crate) fn truncate(&mut self, since: u64, prev: Option<LogId<NID>>) {
+        if self.durable.next_index() > since {
+            self.durable = prev;
+        }
+    }
+
+    /// Check that logs up to `upto` are durable and can be applied to the state machine.
+    pub(crate) fn check_apply(&self, upto: &LogId<NID>) -> Result<(), DefensiveError<NID>> {
+        if self.durable.index() >= Some(upto.index) {
+            return Ok(());
+        }
+
+        Err(DefensiveError::new(
+            ErrorSubject::Apply(*upto),
+            Violation::ApplyNonDurable {
+                durable: self.durable,
+                upto: *upto,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::durable_log::DurableLog;
+    use crate::storage_error::Violation;
+    use crate::testing::log_id;
+
+    #[test]
+    fn test_durable_log() -> anyhow::Result<()> {
+        let mut d = DurableLog::<u64>::new(Some(log_id(1, 1, 3)));
+        assert!(d.check_apply(&log_id(1, 1, 3)).is_ok());
+
+        // Entries 4..=5 are submitted but the flush is not yet reported: commit races ahead of
+        // durability.
+        let err = d.check_apply(&log_id(1, 1, 5)).unwrap_err();
+        assert_eq!(
+            Violation::ApplyNonDurable {
+                durable: Some(log_id(1, 1, 3)),
+                upto: log_id(1, 1, 5),
+            },
+            err.violation
+        );
+
+        // Flush reported
+        d.update(log_id(1, 1, 5));
+        assert!(d.check_apply(&log_id(1, 1, 5)).is_ok());
+
+        // Does not go backward
+        d.update(log_id(1, 1, 4));
+        assert_eq!(Some(&log_id(1, 1, 5)), d.durable());
+
+        // Truncate after the durable log id has no effect
+        d.truncate(6, Some(log_id(1, 1, 5)));
+        assert_eq!(Some(&log_id(1, 1, 5)), d.durable());
+
+        // Truncate conflicting logs, until the replacing logs are flushed
+        d.truncate(4, Some(log_id(1, 1, 3)));
+        assert_eq!(Some(&log_id(1, 1, 3)), d.durable());
+        assert!(d.check_apply(&log_id(2, 1, 4)).is_err());
+
+        d.update(log_id(2, 1, 4));
+        assert!(d.check_apply(&log_id(2, 1, 4)).is_ok());
+
+        Ok(())
+    }
+}
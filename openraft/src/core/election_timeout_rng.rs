@@ -0,0 +1,105 @@
+//! Pick randomized election timeouts for a node.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::ops::Range;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+use crate::AsyncRuntime;
+use crate::Config;
+
+/// The random number generator a node picks its election timeouts with.
+///
+/// Without [`Config::election_timeout_seed`], every election timeout is picked with the thread
+/// local RNG of the async runtime. With a seed, it is picked with an RNG seeded with the seed and
+/// the node id, so that the sequence of election timeouts of a node is reproducible, and nodes
+/// sharing the same seed still get different election timeouts.
+pub(crate) struct ElectionTimeoutRng {
+    /// The range of the election timeout in milliseconds.
+    range: Range<u64>,
+
+    seeded: Option<StdRng>,
+}
+
+impl ElectionTimeoutRng {
+    pub(crate) fn new<NID: Hash>(id: &NID, config: &Config) -> Self {
+        let seeded = config.election_timeout_seed.map(|seed| {
+            let mut hasher = DefaultHasher::new();
+            seed.hash(&mut hasher);
+            id.hash(&mut hasher);
+            StdRng::seed_from_u64(hasher.finish())
+        });
+
+        Self {
+            range: config.election_timeout_min..config.election_timeout_max,
+            seeded,
+        }
+    }
+
+    /// Pick the next election timeout.
+    pub(crate) fn next_timeout<RT: AsyncRuntime>(&mut self) -> Duration {
+        let millis = match &mut self.seeded {
+            Some(rng) => rng.gen_range(self.range.clone()),
+            None => RT::thread_rng().gen_range(self.range.clone()),
+        };
+        Duration::from_millis(millis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::core::election_timeout_rng::ElectionTimeoutRng;
+    use crate::Config;
+    use crate::TokioRuntime;
+
+    fn timeouts(rng: &mut ElectionTimeoutRng) -> Vec<Duration> {
+        (0..10).map(|_| rng.next_timeout::<TokioRuntime>()).collect()
+    }
+
+    #[test]
+    fn test_election_timeout_rng_seeded() -> anyhow::Result<()> {
+        let config = Config::build(&["foo", "--election-timeout-seed=5"])?;
+        let range =
+            Duration::from_millis(config.election_timeout_min)..Duration::from_millis(config.election_timeout_max);
+
+        let ts1 = timeouts(&mut ElectionTimeoutRng::new(&1u64, &config));
+        assert!(ts1.iter().all(|t| range.contains(t)));
+
+        // Consecutive election timeouts differ.
+        assert!(ts1.iter().any(|t| t != &ts1[0]), "consecutive timeouts: {:?}", ts1);
+
+        // Reproducible with the same seed and node id.
+        let ts1_again = timeouts(&mut ElectionTimeoutRng::new(&1u64, &config));
+        assert_eq!(ts1, ts1_again);
+
+        // Nodes sharing the same seed get different election timeouts.
+        let ts2 = timeouts(&mut ElectionTimeoutRng::new(&2u64, &config));
+        assert_ne!(ts1, ts2);
+
+        // A different seed gives a different sequence.
+        let config6 = Config::build(&["foo", "--election-timeout-seed=6"])?;
+        let ts1_seed6 = timeouts(&mut ElectionTimeoutRng::new(&1u64, &config6));
+        assert_ne!(ts1, ts1_seed6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_election_timeout_rng_unseeded() -> anyhow::Result<()> {
+        let config = Config::default();
+        let range =
+            Duration::from_millis(config.election_timeout_min)..Duration::from_millis(config.election_timeout_max);
+
+        let ts = timeouts(&mut ElectionTimeoutRng::new(&1u64, &config));
+        assert!(ts.iter().all(|t| range.contains(t)));
+
+        Ok(())
+    }
+}
@@ -6,9 +6,12 @@
 
 pub(crate) mod balancer;
 pub(crate) mod command_state;
+pub(crate) mod durable_log;
+pub(crate) mod election_timeout_rng;
 pub(crate) mod notify;
 mod raft_core;
 pub(crate) mod raft_msg;
+pub(crate) mod read_freshness;
 mod replication_state;
 mod server_state;
 pub(crate) mod sm;
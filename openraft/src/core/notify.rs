@@ -3,7 +3,9 @@ use std::fmt;
 use crate::core::sm;
 use crate::raft::VoteResponse;
 use crate::replication;
+use crate::LogId;
 use crate::RaftTypeConfig;
+use crate::StorageError;
 use crate::Vote;
 
 /// A message coming from the internal components.
@@ -43,6 +45,15 @@ where C: RaftTypeConfig
     /// Result of executing a command sent from state machine worker.
     StateMachine { command_result: sm::CommandResult<C> },
 
+    /// The state machine failed to apply a log entry.
+    ///
+    /// It is sent before the error is returned by the state machine worker, so that it is
+    /// observed before `RaftCore` decides how to handle the error.
+    ApplyFailed {
+        log_id: LogId<C::NodeId>,
+        error: StorageError<C::NodeId>,
+    },
+
     /// A tick event to wake up RaftCore to check timeout etc.
     Tick {
         /// ith tick
@@ -87,6 +98,9 @@ where C: RaftTypeConfig
             Self::StateMachine { command_result } => {
                 write!(f, "StateMachine command done: {:?}", command_result)
             }
+            Self::ApplyFailed { log_id, error } => {
+                write!(f, "ApplyFailed: log_id: {}, error: {}", log_id, error)
+            }
             Self::Tick { i } => {
                 write!(f, "Tick {}", i)
             }
@@ -1,8 +1,10 @@
 use std::borrow::Borrow;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::io;
 use std::marker::PhantomData;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -14,6 +16,7 @@ use futures::StreamExt;
 use futures::TryFutureExt;
 use maplit::btreeset;
 use tokio::select;
+use tokio::sync::broadcast;
 use tokio::sync::mpsc;
 use tokio::sync::watch;
 use tracing::Instrument;
@@ -22,9 +25,12 @@ use tracing::Span;
 
 use crate::async_runtime::AsyncOneshotSendExt;
 use crate::config::Config;
+use crate::config::LogFlushMode;
 use crate::config::RuntimeConfig;
 use crate::core::balancer::Balancer;
 use crate::core::command_state::CommandState;
+use crate::core::durable_log::DurableLog;
+use crate::core::election_timeout_rng::ElectionTimeoutRng;
 use crate::core::notify::Notify;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::core::raft_msg::AppendEntriesTx;
@@ -32,6 +38,8 @@ use crate::core::raft_msg::ClientReadTx;
 use crate::core::raft_msg::RaftMsg;
 use crate::core::raft_msg::ResultSender;
 use crate::core::raft_msg::VoteTx;
+use crate::core::read_freshness::ReadFreshness;
+use crate::core::replication_lag;
 use crate::core::sm;
 use crate::core::sm::handle;
 use crate::core::sm::CommandSeq;
@@ -43,22 +51,43 @@ use crate::engine::Command;
 use crate::engine::Condition;
 use crate::engine::Engine;
 use crate::engine::Respond;
+use crate::entry::EntryPayloadKind;
 use crate::entry::FromAppData;
+use crate::entry::LogEntrySummary;
 use crate::entry::RaftEntry;
 use crate::error::ClientWriteError;
+use crate::error::EntryTooLarge;
 use crate::error::Fatal;
+use crate::error::FollowerReadError;
 use crate::error::ForwardToLeader;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::error::LeaderNotReady;
+use crate::error::LearnerNotReady;
+use crate::error::LogFull;
+use crate::error::LogPurged;
+use crate::error::MembershipChanging;
+use crate::error::NotReady;
 use crate::error::QuorumNotEnough;
 use crate::error::RPCError;
+use crate::error::RateLimited;
+use crate::error::ReplayError;
 use crate::error::Timeout;
+use crate::error::UncommittedEntriesError;
+use crate::internal_server_state::LeaderQuorumSet;
+use crate::leader::Leading;
 use crate::log_id::LogIdOptionExt;
 use crate::log_id::RaftLogId;
+use crate::metrics::LeadershipStatus;
 use crate::metrics::RaftDataMetrics;
+use crate::metrics::RaftEvent;
 use crate::metrics::RaftMetrics;
 use crate::metrics::RaftServerMetrics;
 use crate::metrics::ReplicationMetrics;
+use crate::metrics::ReplicationRejection;
+use crate::metrics::SnapshotInstallProgress;
+use crate::metrics::StorageMetrics;
+use crate::metrics::StorageOp;
 use crate::network::v2::RaftNetworkV2;
 use crate::network::RPCOption;
 use crate::network::RPCTypes;
@@ -67,10 +96,15 @@ use crate::progress::entry::ProgressEntry;
 use crate::progress::Inflight;
 use crate::progress::Progress;
 use crate::quorum::QuorumSet;
+use crate::raft::liveness::LivenessState;
+use crate::raft::observer::ObserverEvent;
+use crate::raft::observer::ObserverHandle;
+use crate::raft::quorum_policy::QuorumPolicyRef;
 use crate::raft::responder::Responder;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
 use crate::raft::ClientWriteResponse;
+use crate::raft::ReadIndexRequest;
 use crate::raft::VoteRequest;
 use crate::raft_state::LogStateReader;
 use crate::replication;
@@ -91,13 +125,21 @@ use crate::type_config::alias::OneshotReceiverOf;
 use crate::type_config::alias::ResponderOf;
 use crate::AsyncRuntime;
 use crate::ChangeMembers;
+use crate::ClusterCommitView;
 use crate::Instant;
+use crate::LeadershipDiag;
 use crate::LogId;
 use crate::Membership;
+use crate::NodeCommitView;
+use crate::NodeReplication;
+use crate::NodeRole;
+use crate::NodeTopology;
 use crate::OptionalSend;
+use crate::RaftState;
 use crate::RaftTypeConfig;
 use crate::StorageError;
 use crate::StorageIOError;
+use crate::Topology;
 use crate::Vote;
 
 /// A temp struct to hold the data for a node that is being applied.
@@ -143,6 +185,14 @@ pub(crate) struct LeaderData<C: RaftTypeConfig> {
 
     /// The time to send next heartbeat.
     pub(crate) next_heartbeat: InstantOf<C>,
+
+    /// The last log id of the leader when it was last found changed, and when.
+    ///
+    /// It is used to detect an idle log, see [`Config::idle_noop_interval`].
+    pub(crate) last_log_seen: (Option<LogId<C::NodeId>>, InstantOf<C>),
+
+    /// When this node became leader, for [`Config::read_lease_warmup`].
+    pub(crate) became_leader_at: InstantOf<C>,
 }
 
 impl<C: RaftTypeConfig> LeaderData<C> {
@@ -150,10 +200,28 @@ impl<C: RaftTypeConfig> LeaderData<C> {
         Self {
             replications: BTreeMap::new(),
             next_heartbeat: InstantOf::<C>::now(),
+            last_log_seen: (None, InstantOf::<C>::now()),
+            became_leader_at: InstantOf::<C>::now(),
         }
     }
 }
 
+/// Log appends that are submitted to [`RaftLogStorage`] but whose flushes are not yet waited for,
+/// when [`LogFlushMode::Batched`] is used.
+pub(crate) struct PendingFlush<C: RaftTypeConfig> {
+    /// When the first pending append is submitted.
+    pub(crate) since: InstantOf<C>,
+
+    /// The number of entries that are pending.
+    pub(crate) n_entries: u64,
+
+    /// The last log id of the pending appends.
+    pub(crate) last_log_id: LogId<C::NodeId>,
+
+    /// Receives the flush result of every pending append.
+    pub(crate) callbacks: Vec<OneshotReceiverOf<C, Result<Option<LogId<C::NodeId>>, io::Error>>>,
+}
+
 // TODO: remove SM
 /// The core type implementing the Raft protocol.
 pub struct RaftCore<C, N, LS, SM>
@@ -171,6 +239,11 @@ where
 
     pub(crate) runtime_config: Arc<RuntimeConfig>,
 
+    /// The values returned by [`Raft::liveness()`], updated when reporting metrics.
+    ///
+    /// [`Raft::liveness()`]: crate::Raft::liveness
+    pub(crate) liveness: Arc<LivenessState<C>>,
+
     /// The `RaftNetworkFactory` implementation.
     pub(crate) network: N,
 
@@ -202,8 +275,84 @@ where
     pub(crate) tx_data_metrics: watch::Sender<RaftDataMetrics<C>>,
     pub(crate) tx_server_metrics: watch::Sender<RaftServerMetrics<C>>,
 
+    /// Broadcasts discrete events to subscribers, see [`Raft::events()`].
+    ///
+    /// [`Raft::events()`]: crate::Raft::events
+    pub(crate) tx_events: broadcast::Sender<RaftEvent<C>>,
+
     pub(crate) command_state: CommandState,
 
+    /// Latency of storage operations, reported in metrics.
+    pub(crate) storage_metrics: StorageMetrics,
+
+    /// The number of times the logs to replicate are found purged, reported in metrics.
+    pub(crate) replication_log_purged: u64,
+
+    /// Why each replication target rejected the last AppendEntries, reported in metrics.
+    pub(crate) replication_rejections: BTreeMap<C::NodeId, ReplicationRejection<C::NodeId>>,
+
+    /// The progress of the snapshot being received from the leader, reported in metrics.
+    pub(crate) installing_snapshot: Option<SnapshotInstallProgress<C::NodeId>>,
+
+    /// The last log id that is reported durable by the log storage, checked before applying.
+    pub(crate) durable_log: DurableLog<C::NodeId>,
+
+    /// Picks a new election timeout every time this node starts an election.
+    pub(crate) election_timeout_rng: ElectionTimeoutRng,
+
+    /// Log appends whose flushes are not yet waited for, with [`LogFlushMode::Batched`].
+    pub(crate) pending_flush: Option<PendingFlush<C>>,
+
+    /// When this node stepped down from leader most recently.
+    ///
+    /// It is used to shorten the election timeout, see [`Config::leader_affinity_window`].
+    pub(crate) stepped_down_at: Option<InstantOf<C>>,
+
+    /// Until when this node does not start an election, see [`Raft::yield_leadership_for()`].
+    ///
+    /// [`Raft::yield_leadership_for()`]: crate::Raft::yield_leadership_for
+    pub(crate) quiesced_until: Option<InstantOf<C>>,
+
+    /// Tracks how fresh the local state machine is, for [`Raft::follower_read()`].
+    ///
+    /// [`Raft::follower_read()`]: crate::Raft::follower_read
+    pub(crate) read_freshness: ReadFreshness<C>,
+
+    /// Sends events to the observer installed by application to watch events in `RaftCore`.
+    pub(crate) observer: Option<ObserverHandle<C>>,
+
+    /// Since when this node has no known leader, for reporting [`LeadershipStatus`] in metrics.
+    pub(crate) leaderless_since: Option<InstantOf<C>>,
+
+    /// When the last tick is received, to detect a stalled process or a clock jump.
+    pub(crate) last_tick_at: Option<InstantOf<C>>,
+
+    /// The storage error that made this node enter read-only degraded mode.
+    ///
+    /// See [`Config::degrade_on_storage_error`].
+    pub(crate) storage_degraded: Option<StorageError<C::NodeId>>,
+
+    /// Whether new client writes are rejected, set by [`Raft::drain_and_shutdown()`].
+    ///
+    /// [`Raft::drain_and_shutdown()`]: crate::Raft::drain_and_shutdown
+    pub(crate) draining: bool,
+
+    /// Waiters to notify once no client write is pending, after `draining` is set.
+    pub(crate) drain_waiters: Vec<ResultSender<C, ()>>,
+
+    /// The log id of the last committed membership that includes this node, since this node
+    /// started.
+    pub(crate) member_since: Option<LogId<C::NodeId>>,
+
+    /// Waiters to notify once this node is removed from membership, see
+    /// [`Raft::removed_from_cluster()`].
+    ///
+    /// [`Raft::removed_from_cluster()`]: crate::Raft::removed_from_cluster
+    pub(crate) removed_waiters: Vec<ResultSender<C, ()>>,
+
+    /// The number of times this node has become leader, see [`RaftMetrics::leadership_epoch`].
+    pub(crate) leadership_epoch: u64,
+
     pub(crate) span: Span,
 
     pub(crate) _p: PhantomData<SM>,
@@ -240,6 +389,8 @@ where
             curr.running_state = Err(err.clone());
 
             let _ = self.tx_metrics.send(curr);
+
+            self.liveness.update(ServerState::Shutdown, false, None);
         }
 
         tracing::info!("RaftCore shutdown complete");
@@ -248,7 +399,7 @@ where
     }
 
     #[tracing::instrument(level="trace", skip_all, fields(id=display(self.id), cluster=%self.config.cluster_name))]
-    async fn do_main(&mut self, rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
+    async fn do_main(&mut self, mut rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
         tracing::debug!("raft node is initializing");
 
         self.engine.startup();
@@ -258,7 +409,52 @@ where
         // Initialize metrics.
         self.report_metrics(None);
 
-        self.runtime_loop(rx_shutdown).await
+        loop {
+            // Safe unwrap: res is Result<Infallible, _>
+            let err = self.runtime_loop(&mut rx_shutdown).await.unwrap_err();
+
+            match err {
+                Fatal::StorageError(e) if self.config.degrades_on(&e) => {
+                    self.enter_storage_degraded(e);
+                }
+                _ => return Err(err),
+            }
+        }
+    }
+
+    /// Enter read-only degraded mode because of a storage error.
+    ///
+    /// The queued commands are discarded, because the in-memory state may not match the storage
+    /// any more. A leader steps down, and pending client writes are told to find another leader.
+    ///
+    /// From then on the raft state is frozen before re-entering the runtime loop: nothing is
+    /// written to the storage, and requests or notifications that would change the raft state are
+    /// rejected or ignored, see [`Self::reject_if_storage_degraded()`]. The node has to be
+    /// restarted to reload the state from the storage.
+    ///
+    /// See [`Config::degrade_on_storage_error`].
+    fn enter_storage_degraded(&mut self, error: StorageError<C::NodeId>) {
+        tracing::error!(
+            error = display(&error),
+            "storage error, enter read-only degraded mode: {}",
+            func_name!()
+        );
+
+        // Publish the degraded state before dropping any response sender, so that a caller that
+        // finds its sender dropped gets the storage error instead of waiting for RaftCore to quit.
+        self.storage_degraded = Some(error);
+        self.report_metrics(None);
+
+        self.engine.output.clear_commands();
+        self.pending_flush = None;
+
+        for (_, tx) in std::mem::take(&mut self.client_resp_channels) {
+            tx.send(Err(ClientWriteError::ForwardToLeader(ForwardToLeader::empty())));
+        }
+
+        if self.engine.state.is_leader(&self.id) {
+            let _ = self.engine.leader_yield();
+        }
     }
 
     /// Handle `is_leader` requests.
@@ -428,6 +624,80 @@ where
         let _ = C::AsyncRuntime::spawn(waiting_fu.instrument(tracing::debug_span!("spawn_is_leader_waiting")));
     }
 
+    /// Serve a lease based read without contacting other nodes.
+    ///
+    /// The read is fenced: it is rejected as soon as a quorum has not acknowledged this leader
+    /// within the read lease, even though this node has not yet stepped down.
+    /// A new leader does not serve it until its blank log is committed and
+    /// [`Config::read_lease_warmup`] has passed.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(super) fn handle_lease_read_request(&mut self, tx: ClientReadTx<C>) {
+        let (resp, noop_log_id) = {
+            let lh = match self.engine.leader_handler() {
+                Ok(leading_handler) => leading_handler,
+                Err(forward) => {
+                    let _ = tx.send(Err(forward.into()));
+                    return;
+                }
+            };
+
+            let read_log_id = lh.get_read_log_id();
+            let noop_log_id = lh.leader.noop_log_id;
+            let applied = self.engine.state.io_applied().copied();
+
+            ((read_log_id, applied), noop_log_id)
+        };
+
+        let lease = self.config.read_lease();
+        let now = InstantOf::<C>::now();
+
+        let committed = self.engine.state.committed().copied();
+        let warmup = Duration::from_millis(self.config.read_lease_warmup);
+        let elapsed = self.leader_data.as_ref().map(|l| now - l.became_leader_at).unwrap_or_default();
+
+        if committed < noop_log_id || elapsed < warmup {
+            let err = LeaderNotReady {
+                noop_log_id,
+                committed,
+                elapsed,
+                warmup,
+            };
+            tracing::info!("reject lease read: {}", err);
+            let _ = tx.send(Err(err.into()));
+            return;
+        }
+
+        let last_quorum_acked = self.last_quorum_acked_time();
+
+        if last_quorum_acked.map(|t| now < t + lease) == Some(true) {
+            let _ = tx.send(Ok(resp));
+            return;
+        }
+
+        tracing::info!(
+            last_quorum_acked = debug(last_quorum_acked),
+            lease = debug(lease),
+            "quorum contact is lost, reject lease read"
+        );
+
+        // Safe unwrap(): it is a leader.
+        let leading = self.engine.internal_server_state.leading().unwrap();
+        let got = leading
+            .clock_progress
+            .iter()
+            .filter(|(id, t)| {
+                leading.clock_progress.is_voter(id) == Some(true) && t.map(|t| now < t + lease) == Some(true)
+            })
+            .map(|(id, _t)| *id)
+            .collect();
+
+        let _ = tx.send(Err(QuorumNotEnough {
+            cluster: self.engine.state.membership_state.effective().membership().to_string(),
+            got,
+        }
+        .into()));
+    }
+
     /// Submit change-membership by writing a Membership log entry.
     ///
     /// If `retain` is `true`, removed `voter` will becomes `learner`. Otherwise they will
@@ -461,10 +731,56 @@ where
             }
         };
 
+        if let Err(e) = self.ensure_new_voters_line_rate(&new_membership) {
+            tracing::info!("reject change_membership: {}", e);
+            tx.send(Err(ClientWriteError::ChangeMembershipError(e.into())));
+            return;
+        }
+
         let ent = C::Entry::new_membership(LogId::default(), new_membership);
         self.write_entry(ent, Some(tx));
     }
 
+    /// Ensures every node that becomes a voter in `membership` is a line-rate learner, if
+    /// [`Config::reject_lagging_new_voters`] is enabled.
+    ///
+    /// A voter that lags far behind can not ack new logs, and counting it in the quorum stalls
+    /// commits until it catches up. Nodes that are already voters are not checked.
+    fn ensure_new_voters_line_rate(&self, membership: &Membership<C>) -> Result<(), LearnerNotReady<C>> {
+        if !self.config.reject_lagging_new_voters {
+            return Ok(());
+        }
+
+        let leading = match self.engine.internal_server_state.leading() {
+            Some(x) => x,
+            // Not a leader: the write will be rejected with ForwardToLeader.
+            None => return Ok(()),
+        };
+
+        let effective = self.engine.state.membership_state.effective();
+        let last_log_index = self.engine.state.last_log_id().index();
+
+        for id in membership.voter_ids() {
+            if id == self.id || effective.is_voter(&id) {
+                continue;
+            }
+
+            let matching = leading.progress.try_get(&id).and_then(|p| p.matching);
+            let lag = replication_lag(&matching.index(), &last_log_index);
+
+            if lag > self.config.replication_lag_threshold {
+                return Err(LearnerNotReady {
+                    node_id: id,
+                    matching,
+                    last_log_index,
+                    committed: *effective.log_id(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Write a log entry to the cluster through raft protocol.
     ///
     /// I.e.: append the log entry to local store, forward it to a quorum(including the leader),
@@ -482,6 +798,20 @@ where
             return false;
         };
 
+        if let Err(e) = Self::check_client_writes(
+            &self.config,
+            self.draining,
+            lh.leader,
+            lh.state,
+            std::slice::from_ref(&entry),
+        ) {
+            self.compact_if_log_full(&e);
+            if let Some(tx) = tx {
+                tx.send(Err(e));
+            }
+            return false;
+        }
+
         let entries = vec![entry];
         // TODO: it should returns membership config error etc. currently this is done by the
         //       caller.
@@ -496,6 +826,141 @@ where
         true
     }
 
+    /// Write a batch of entries as one contiguous block in the log.
+    ///
+    /// Every entry is checked before any of them is appended: if one is rejected, all of them are
+    /// rejected with the same error. Since the entries are contiguous, they are applied
+    /// consecutively. They may still be committed in more than one step.
+    ///
+    /// The result of applying every entry is sent to the responder at the same position in `txs`.
+    #[tracing::instrument(level = "debug", skip_all, fields(id = display(self.id)))]
+    pub(crate) fn write_entries(&mut self, entries: Vec<C::Entry>, txs: Vec<ResponderOf<C>>) -> bool {
+        tracing::debug!(n = display(entries.len()), "write_entries");
+
+        debug_assert_eq!(entries.len(), txs.len());
+
+        if entries.is_empty() {
+            return true;
+        }
+
+        let mut lh = match self.engine.leader_handler() {
+            Ok(lh) => lh,
+            Err(forward_err) => {
+                for tx in txs {
+                    tx.send(Err(forward_err.clone().into()));
+                }
+                return false;
+            }
+        };
+
+        if let Err(e) = Self::check_client_writes(&self.config, self.draining, lh.leader, lh.state, &entries) {
+            self.compact_if_log_full(&e);
+            for tx in txs {
+                tx.send(Err(e.clone()));
+            }
+            return false;
+        }
+
+        let first = lh.state.last_log_id().next_index();
+        lh.leader_append_entries(entries);
+        let last = lh.state.last_log_id().unwrap().index;
+
+        // Install callback channels.
+        for (index, tx) in (first..=last).zip(txs) {
+            self.client_resp_channels.insert(index, tx);
+        }
+
+        true
+    }
+
+    /// Check if client writes of `entries` are allowed on this leader.
+    ///
+    /// It does not check anything for a membership or blank entry.
+    fn check_client_writes(
+        config: &Config,
+        draining: bool,
+        leader: &mut Leading<C, LeaderQuorumSet<C::NodeId>>,
+        state: &RaftState<C>,
+        entries: &[C::Entry],
+    ) -> Result<(), ClientWriteError<C>> {
+        let n = entries.iter().filter(|e| e.payload_kind() == EntryPayloadKind::Normal).count() as u64;
+
+        if draining && n > 0 {
+            tracing::info!("reject write: node is draining before shutdown");
+            return Err(ClientWriteError::NotReady(NotReady { vote: leader.vote }));
+        }
+
+        // The quorum acked time is set upon the first response from a quorum in this term.
+        if config.require_quorum_before_writes && n > 0 && leader.last_quorum_acked_time().is_none() {
+            tracing::info!("reject write: leader is not yet acknowledged by a quorum");
+            return Err(ClientWriteError::NotReady(NotReady { vote: leader.vote }));
+        }
+
+        let max_size = config.max_entry_size_bytes;
+        for entry in entries {
+            if let Some(size) = entry.app_data_size() {
+                if max_size > 0 && size > max_size {
+                    tracing::warn!(
+                        size = display(size),
+                        max_size = display(max_size),
+                        "reject too large entry: {}",
+                        entry
+                    );
+
+                    return Err(ClientWriteError::EntryTooLarge(EntryTooLarge { size, max: max_size }));
+                }
+            }
+        }
+
+        if config.reject_writes_during_membership_change && n > 0 && state.membership_state.is_in_transition() {
+            let membership_state = &state.membership_state;
+            tracing::info!(
+                membership_state = display(membership_state),
+                "reject write: membership change in progress"
+            );
+            return Err(ClientWriteError::MembershipChanging(MembershipChanging {
+                membership_log_id: *membership_state.effective().log_id(),
+                committed: *membership_state.committed().log_id(),
+            }));
+        }
+
+        let max_entries = config.max_log_entries;
+        if max_entries > 0 && n > 0 {
+            let entries_in_log = state.last_log_id().next_index() - state.last_purged_log_id().next_index();
+            if entries_in_log + n > max_entries {
+                tracing::warn!(
+                    entries = display(entries_in_log),
+                    max_entries = display(max_entries),
+                    "reject write: log is full"
+                );
+                return Err(ClientWriteError::LogFull(LogFull {
+                    entries: entries_in_log,
+                    max: max_entries,
+                }));
+            }
+        }
+
+        let max_rate = config.max_write_rate;
+        if max_rate > 0 && n > 0 && !leader.write_rate_limiter.try_acquire_n(max_rate, n, InstantOf::<C>::now()) {
+            tracing::info!(
+                max_rate = display(max_rate),
+                n = display(n),
+                "reject write: rate limited"
+            );
+            return Err(ClientWriteError::RateLimited(RateLimited { max_rate }));
+        }
+
+        Ok(())
+    }
+
+    /// Trigger building a snapshot if a write is rejected because the log is full, so that the
+    /// log can be purged and writes can be accepted again.
+    fn compact_if_log_full(&mut self, err: &ClientWriteError<C>) {
+        if let ClientWriteError::LogFull(_) = err {
+            self.engine.snapshot_handler().trigger_snapshot();
+        }
+    }
+
     /// Send a heartbeat message to every followers/learners.
     ///
     /// Currently heartbeat is a blank log
@@ -536,41 +1001,96 @@ where
     pub(crate) fn report_metrics(&mut self, replication: Option<ReplicationMetrics<C::NodeId>>) {
         let last_quorum_acked = self.last_quorum_acked_time();
         let millis_since_quorum_ack = last_quorum_acked.map(|t| t.elapsed().as_millis() as u64);
+        let fully_replicated_index = self.fully_replicated_index();
+        let commit_quorum = self.commit_quorum();
+        let millis_since_last_ack = self.millis_since_last_ack();
+
+        // A leader does not hear committed log id from others.
+        let (leader_commit_seen, millis_since_leader_commit_seen) =
+            match self.read_freshness.last_heard().filter(|_| !self.engine.state.is_leader(&self.id)) {
+                Some((committed, t)) => (*committed, Some(t.elapsed().as_millis() as u64)),
+                None => (None, None),
+            };
+
+        let current_leader = self.current_leader();
+
+        let leadership_status = if current_leader.is_some() {
+            self.leaderless_since = None;
+            LeadershipStatus::HasLeader
+        } else {
+            let since = *self.leaderless_since.get_or_insert_with(InstantOf::<C>::now);
+            if since.elapsed() >= Duration::from_millis(self.config.leaderless_grace) {
+                LeadershipStatus::Leaderless
+            } else {
+                LeadershipStatus::ElectingNewLeader
+            }
+        };
 
         let st = &self.engine.state;
 
+        let last_heartbeat = if st.is_leader(&self.id) {
+            last_quorum_acked
+        } else {
+            st.vote_last_modified()
+        };
+        self.liveness.update(st.server_state, current_leader.is_some(), last_heartbeat);
+
         let membership_config = st.membership_state.effective().stored_membership().clone();
-        let current_leader = self.current_leader();
 
         let m = RaftMetrics {
             running_state: Ok(()),
+            storage_degraded: self.storage_degraded.clone(),
             id: self.id,
 
             // --- data ---
             current_term: st.vote_ref().leader_id().get_term(),
             vote: *st.io_state().vote(),
             last_log_index: st.last_log_id().index(),
+            committed: st.committed().copied(),
             last_applied: st.io_applied().copied(),
             snapshot: st.io_snapshot_last_log_id().copied(),
+            installing_snapshot: self.installing_snapshot.clone(),
             purged: st.io_purged().copied(),
+            storage: self.storage_metrics.clone(),
 
             // --- cluster ---
             state: st.server_state,
             current_leader,
+            leadership_status,
             millis_since_quorum_ack,
             membership_config: membership_config.clone(),
+            leader_commit_seen,
+            millis_since_leader_commit_seen,
 
             // --- replication ---
             replication: replication.clone(),
+            millis_since_last_ack: millis_since_last_ack.clone(),
+            fully_replicated_index,
+            commit_quorum: commit_quorum.clone(),
+            replication_log_purged: self.replication_log_purged,
+            replication_rejections: self.replication_rejections.clone(),
+            pending_client_writes: self.client_resp_channels.len() as u64,
+            stale_term_requests: self.engine.stale_term_requests,
+            leadership_epoch: self.leadership_epoch,
         };
 
         let data_metrics = RaftDataMetrics {
             last_log: st.last_log_id().copied(),
             last_applied: st.io_applied().copied(),
             snapshot: st.io_snapshot_last_log_id().copied(),
+            installing_snapshot: self.installing_snapshot.clone(),
             purged: st.io_purged().copied(),
+            storage: self.storage_metrics.clone(),
             millis_since_quorum_ack,
+            leader_commit_seen,
+            millis_since_leader_commit_seen,
             replication,
+            millis_since_last_ack,
+            fully_replicated_index,
+            commit_quorum,
+            replication_log_purged: self.replication_log_purged,
+            replication_rejections: self.replication_rejections.clone(),
+            pending_client_writes: self.client_resp_channels.len() as u64,
         };
 
         let server_metrics = RaftServerMetrics {
@@ -578,6 +1098,7 @@ where
             vote: *st.io_state().vote(),
             state: st.server_state,
             current_leader,
+            leadership_status,
             membership_config,
         };
 
@@ -602,6 +1123,24 @@ where
             false
         });
 
+        let (leader_changed, membership_changed) = {
+            let prev = self.tx_metrics.borrow();
+            (
+                prev.current_leader != m.current_leader,
+                prev.membership_config != m.membership_config,
+            )
+        };
+        if leader_changed {
+            self.send_event(RaftEvent::LeaderChanged {
+                leader: m.current_leader,
+            });
+        }
+        if membership_changed {
+            self.send_event(RaftEvent::MembershipChanged {
+                membership: m.membership_config.clone(),
+            });
+        }
+
         tracing::debug!("report_metrics: {}", m);
         let res = self.tx_metrics.send(m);
 
@@ -620,11 +1159,17 @@ where
     pub(crate) fn handle_initialize(
         &mut self,
         member_nodes: BTreeMap<C::NodeId, C::Node>,
+        weights: BTreeMap<C::NodeId, u64>,
         tx: ResultSender<C, (), InitializeError<C>>,
     ) {
-        tracing::debug!(member_nodes = debug(&member_nodes), "{}", func_name!());
+        tracing::debug!(
+            member_nodes = debug(&member_nodes),
+            weights = debug(&weights),
+            "{}",
+            func_name!()
+        );
 
-        let membership = Membership::from(member_nodes);
+        let membership = Membership::from(member_nodes).with_weights(weights);
 
         let entry = C::Entry::new_membership(LogId::default(), membership);
         let res = self.engine.initialize(entry);
@@ -668,7 +1213,7 @@ where
 
         let vote = self.engine.state.vote_ref();
 
-        if !vote.is_committed() {
+        if !vote.is_committed() || self.engine.state.yielded {
             return None;
         }
 
@@ -685,6 +1230,120 @@ where
         }
     }
 
+    /// Build a report explaining the leadership status of this node.
+    pub(crate) fn leadership_diag(&self) -> LeadershipDiag<C> {
+        let st = &self.engine.state;
+        let vote = *st.vote_ref();
+
+        let now = InstantOf::<C>::now();
+        let since_vote_updated = st.vote_last_modified().map(|t| now - t);
+
+        // A committed vote of another node that is refreshed within the lease means the leader is
+        // sending heartbeats to this node.
+        let leader_lease = self.engine.config.timer_config.leader_lease;
+        let receiving_heartbeat =
+            vote.is_committed() && !st.is_leader(&self.id) && matches!(since_vote_updated, Some(d) if d < leader_lease);
+
+        LeadershipDiag {
+            id: self.id,
+            server_state: st.server_state,
+            current_term: vote.leader_id().term,
+            vote,
+            current_leader: self.current_leader(),
+            last_log_id: st.last_log_id().copied(),
+            electability: st.electability(&self.id),
+            election_enabled: self.runtime_config.enable_elect.load(Ordering::Relaxed),
+            seen_greater_log: self.engine.is_there_greater_log(),
+            since_vote_updated,
+            receiving_heartbeat,
+            last_election: self.engine.last_election.clone(),
+        }
+    }
+
+    /// Build a view of the replication and commit state of every node, if this node is the leader.
+    pub(crate) fn cluster_commit_view(&self) -> Result<ClusterCommitView<C>, ForwardToLeader<C>> {
+        let st = &self.engine.state;
+
+        let leading = match self.engine.internal_server_state.leading() {
+            Some(l) if st.is_leader(&self.id) => l,
+            _ => return Err(st.forward_to_leader()),
+        };
+
+        let membership = st.membership_state.effective().membership();
+        let committed = st.committed().copied();
+
+        let nodes = leading
+            .progress
+            .iter()
+            .map(|(id, p)| {
+                let role = if membership.is_voter(id) {
+                    NodeRole::Voter
+                } else {
+                    NodeRole::Learner
+                };
+
+                let n = NodeCommitView {
+                    role,
+                    matched: p.matching,
+                    committed: std::cmp::min(committed, p.matching),
+                };
+                (*id, n)
+            })
+            .collect();
+
+        Ok(ClusterCommitView {
+            leader_id: self.id,
+            vote: *st.vote_ref(),
+            taken_at: InstantOf::<C>::now(),
+            committed,
+            last_log_id: st.last_log_id().copied(),
+            last_applied: st.io_applied().copied(),
+            nodes,
+        })
+    }
+
+    /// Build the topology of the cluster: every node in the effective membership with its role,
+    /// and the replication state of it if this node is the leader.
+    pub(crate) fn cluster_topology(&self) -> Topology<C> {
+        let st = &self.engine.state;
+        let effective = st.membership_state.effective();
+        let membership = effective.membership();
+
+        let leading = self.engine.internal_server_state.leading();
+        let next_index = st.last_log_id().next_index();
+
+        let nodes = membership
+            .nodes()
+            .map(|(id, node)| {
+                let role = if membership.is_voter(id) {
+                    NodeRole::Voter
+                } else {
+                    NodeRole::Learner
+                };
+
+                let replication = leading.and_then(|l| l.progress.try_get(id)).map(|p| NodeReplication {
+                    matched: p.matching,
+                    lag: next_index.saturating_sub(p.matching.next_index()),
+                });
+
+                let n = NodeTopology {
+                    role,
+                    read_replica: self.engine.config.read_replicas.contains(id),
+                    node: node.clone(),
+                    replication,
+                };
+                (*id, n)
+            })
+            .collect();
+
+        Topology {
+            id: self.id,
+            current_leader: self.current_leader(),
+            membership_log_id: *effective.log_id(),
+            nodes,
+        }
+    }
+
     /// Retrieves the most recent timestamp that is acknowledged by a quorum.
     ///
     /// This function returns the latest known time at which the leader received acknowledgment
@@ -695,6 +1354,43 @@ where
         leading.and_then(|l| l.last_quorum_acked_time())
     }
 
+    /// Returns the greatest log index that is replicated to every voter.
+    ///
+    /// If the node is not a leader or any voter has not yet accepted any log, `None` is returned.
+    fn fully_replicated_index(&self) -> Option<u64> {
+        let leading = self.engine.internal_server_state.leading()?;
+        let progress = &leading.progress;
+
+        progress
+            .iter()
+            .filter(|(id, _)| progress.is_voter(id) == Some(true))
+            .map(|(_, p)| p.matching.index())
+            .min()
+            .flatten()
+    }
+
+    /// The elapsed time since the last acknowledged request to each replication target was sent.
+    fn millis_since_last_ack(&self) -> Option<BTreeMap<C::NodeId, Option<u64>>> {
+        let leading = self.engine.internal_server_state.leading()?;
+
+        let res = leading
+            .clock_progress
+            .iter()
+            .filter(|(id, _)| *id != self.id)
+            .map(|(id, t)| (*id, t.map(|t| t.elapsed().as_millis() as u64)))
+            .collect();
+        Some(res)
+    }
+
+    /// The voters that had accepted the committed log id when it was last advanced by this leader.
+    fn commit_quorum(&self) -> Option<BTreeSet<C::NodeId>> {
+        let leading = self.engine.internal_server_state.leading()?;
+        if leading.commit_quorum.is_empty() {
+            return None;
+        }
+        Some(leading.commit_quorum.clone())
+    }
+
     pub(crate) fn get_leader_node(&self, leader_id: Option<C::NodeId>) -> Option<C::Node> {
         let leader_id = match leader_id {
             None => return None,
@@ -705,27 +1401,189 @@ where
     }
 
     /// A temp wrapper to make non-blocking `append_to_log` a blocking.
+    ///
+    /// It returns whether the log store reported the entries flushed, see
+    /// [`Self::wait_log_flushed()`].
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) async fn append_to_log<I>(
         &mut self,
         entries: I,
         last_log_id: LogId<C::NodeId>,
-    ) -> Result<(), StorageError<C::NodeId>>
+    ) -> Result<bool, StorageError<C::NodeId>>
     where
         I: IntoIterator<Item = C::Entry> + OptionalSend,
         I::IntoIter: OptionalSend,
     {
         tracing::debug!("append_to_log");
 
+        let start = InstantOf::<C>::now();
+
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let callback = LogFlushed::new(Some(last_log_id), tx);
+        self.log_store.append(entries, callback).await?;
+
+        if !self.wait_log_flushed(rx).await? {
+            return Ok(false);
+        }
+
+        self.storage_metrics.record(StorageOp::Append, start.elapsed());
+        self.durable_log.update(last_log_id);
+        Ok(true)
+    }
+
+    /// Wait for the log store to report a log flush, and return whether it is reported.
+    ///
+    /// A log store that drops the callback without calling it never reports the logs durable.
+    /// A leader keeps running without counting the logs as accepted by itself; if the followers
+    /// commit them anyway, [`DurableLog::check_apply()`] refuses to apply them. A follower must
+    /// not acknowledge logs that are not durable to the leader, thus for a follower it is an
+    /// error.
+    async fn wait_log_flushed(
+        &self,
+        rx: OneshotReceiverOf<C, Result<Option<LogId<C::NodeId>>, io::Error>>,
+    ) -> Result<bool, StorageError<C::NodeId>> {
+        match rx.await {
+            Ok(res) => {
+                res.map_err(|e| StorageIOError::write_logs(AnyError::error(e)))?;
+                Ok(true)
+            }
+            Err(e) if self.engine.state.is_leader(&self.id) => {
+                tracing::error!(
+                    error = display(&e),
+                    "log store dropped the flush callback, the logs are not durable"
+                );
+                Ok(false)
+            }
+            Err(e) => Err(StorageIOError::write_logs(AnyError::error(e)).into()),
+        }
+    }
+
+    /// Submit entries to the log store without waiting for them to be flushed, on a leader.
+    ///
+    /// The flush is waited for later in a batch by [`Self::flush_pending_appends()`], which is
+    /// called at once if there are `max_entries` entries pending.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn submit_to_log(
+        &mut self,
+        entries: Vec<C::Entry>,
+        last_log_id: LogId<C::NodeId>,
+        max_entries: u64,
+    ) -> Result<(), StorageError<C::NodeId>> {
+        let n_entries = entries.len() as u64;
+
         let (tx, rx) = C::AsyncRuntime::oneshot();
         let callback = LogFlushed::new(Some(last_log_id), tx);
         self.log_store.append(entries, callback).await?;
-        rx.await
-            .map_err(|e| StorageIOError::write_logs(AnyError::error(e)))?
-            .map_err(|e| StorageIOError::write_logs(AnyError::error(e)))?;
+
+        let pending = self.pending_flush.get_or_insert_with(|| PendingFlush {
+            since: InstantOf::<C>::now(),
+            n_entries: 0,
+            last_log_id,
+            callbacks: vec![],
+        });
+        pending.n_entries += n_entries;
+        pending.last_log_id = last_log_id;
+        pending.callbacks.push(rx);
+
+        tracing::debug!(
+            n_entries = pending.n_entries,
+            last_log_id = display(last_log_id),
+            "submitted to log, flush pending"
+        );
+
+        if pending.n_entries >= max_entries {
+            self.flush_pending_appends().await?;
+        }
+
         Ok(())
     }
 
+    /// Wait for all pending log appends to be flushed, then report the flushed log id to the
+    /// leader, if this node is a leader.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn flush_pending_appends(&mut self) -> Result<(), StorageError<C::NodeId>> {
+        let Some(pending) = self.pending_flush.take() else {
+            return Ok(());
+        };
+
+        tracing::debug!(
+            n_entries = pending.n_entries,
+            last_log_id = display(pending.last_log_id),
+            "{}",
+            func_name!()
+        );
+
+        let mut flushed = true;
+        for rx in pending.callbacks {
+            flushed &= self.wait_log_flushed(rx).await?;
+        }
+
+        if !flushed {
+            return Ok(());
+        }
+
+        self.storage_metrics.record(StorageOp::Append, pending.since.elapsed());
+        self.durable_log.update(pending.last_log_id);
+
+        // The leader may have changed.
+        // But reporting to a different leader is not a problem.
+        if let Ok(mut lh) = self.engine.leader_handler() {
+            lh.replication_handler().update_local_progress(Some(pending.last_log_id));
+        }
+
+        Ok(())
+    }
+
+    /// Read at most `max` committed log entries starting from index `start`.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn read_committed(&mut self, start: u64, max: u64) -> Result<Vec<C::Entry>, ReplayError<C>> {
+        if let Some(purged) = self.engine.state.last_purged_log_id() {
+            if start <= purged.index {
+                return Err(LogPurged {
+                    index: start,
+                    last_purged_log_id: *purged,
+                }
+                .into());
+            }
+        }
+
+        let end = std::cmp::min(self.engine.state.committed().next_index(), start + max);
+        if start >= end {
+            return Ok(vec![]);
+        }
+
+        let entries = self.log_store.get_log_entries(start..end).await?;
+        Ok(entries)
+    }
+
+    /// Read summaries of the log entries after the committed log id, if this node is a leader.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn read_uncommitted(&mut self) -> Result<Vec<LogEntrySummary<C>>, UncommittedEntriesError<C>> {
+        if !self.engine.state.is_leader(&self.id) {
+            return Err(self.engine.state.forward_to_leader().into());
+        }
+
+        let start = self.engine.state.committed().next_index();
+        let end = self.engine.state.last_log_id().next_index();
+        if start >= end {
+            return Ok(vec![]);
+        }
+
+        let entries = self.log_store.get_log_entries(start..end).await?;
+        Ok(entries.iter().map(LogEntrySummary::new).collect())
+    }
+
+    /// Returns when the pending log appends must be waited for, if there are any.
+    fn flush_deadline(&self) -> Option<InstantOf<C>> {
+        let pending = self.pending_flush.as_ref()?;
+
+        match &self.config.log_flush_mode {
+            // Nothing is left pending in this mode, unless the config is changed. Flush at once.
+            LogFlushMode::PerEntry => Some(pending.since),
+            LogFlushMode::Batched { max_delay, .. } => Some(pending.since + *max_delay),
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) async fn apply_to_state_machine(
         &mut self,
@@ -748,7 +1606,10 @@ where
             return Ok(());
         }
 
+        let start = InstantOf::<C>::now();
         let entries = self.log_store.get_log_entries(since..end).await?;
+        self.storage_metrics.record(StorageOp::GetLogEntries, start.elapsed());
+
         tracing::debug!(
             entries = display(DisplaySlice::<_>(entries.as_slice())),
             "about to apply"
@@ -764,17 +1625,28 @@ where
 
     /// When received results of applying log entries to the state machine, send back responses to
     /// the callers that proposed the entries.
+    ///
+    /// The results of a batch are for the log range `[since, end)`. The waiting senders in this
+    /// range are taken out at once, and are matched with the results in index order in a single
+    /// pass, instead of being looked up one by one.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn handle_apply_result(&mut self, res: ApplyResult<C>) {
         tracing::debug!(last_applied = display(res.last_applied), "{}", func_name!());
 
+        let rest = self.client_resp_channels.split_off(&res.end);
+        let mut before_end = std::mem::replace(&mut self.client_resp_channels, rest);
+        let in_range = before_end.split_off(&res.since);
+        // Senders before `since` are not expected, but keep them to be answered later.
+        self.client_resp_channels.append(&mut before_end);
+
+        let mut senders = in_range.into_iter().peekable();
         let mut results = res.apply_results.into_iter();
         let mut applying_entries = res.applying_entries.into_iter();
 
         for log_index in res.since..res.end {
             let ent = applying_entries.next().unwrap();
             let apply_res = results.next().unwrap();
-            let tx = self.client_resp_channels.remove(&log_index);
+            let tx = senders.next_if(|(index, _)| *index == log_index).map(|(_, tx)| tx);
 
             Self::send_response(ent, apply_res, tx);
         }
@@ -838,81 +1710,256 @@ where
     pub async fn remove_all_replication(&mut self) {
         tracing::info!("remove all replication");
 
-        if let Some(l) = &mut self.leader_data {
-            let nodes = std::mem::take(&mut l.replications);
+        if let Some(l) = &mut self.leader_data {
+            let nodes = std::mem::take(&mut l.replications);
+
+            tracing::debug!(
+                targets = debug(nodes.iter().map(|x| *x.0).collect::<Vec<_>>()),
+                "remove all targets from replication_metrics"
+            );
+
+            for (target, s) in nodes {
+                let handle = s.join_handle;
+
+                // Drop sender to notify the task to shutdown
+                drop(s.tx_repl);
+
+                tracing::debug!("joining removed replication: {}", target);
+                let _x = handle.await;
+                tracing::info!("Done joining removed replication : {}", target);
+            }
+        } else {
+            unreachable!("it has to be a leader!!!");
+        };
+    }
+
+    /// Run as many commands as possible.
+    ///
+    /// If there is a command that waits for a callback, just return and wait for
+    /// next RaftMsg.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn run_engine_commands(&mut self) -> Result<(), StorageError<C::NodeId>> {
+        self.send_server_state_transitions();
+        self.send_election_rounds();
+
+        if tracing::enabled!(Level::DEBUG) {
+            tracing::debug!("queued commands: start...");
+            for c in self.engine.output.iter_commands() {
+                tracing::debug!("queued commands: {:?}", c);
+            }
+            tracing::debug!("queued commands: end...");
+        }
+
+        while let Some(cmd) = self.engine.output.pop_command() {
+            tracing::debug!("run command: {:?}", cmd);
+
+            // In degraded mode nothing is written to the storage. A command waiting for an IO that
+            // will never happen is dropped too, otherwise it would block all following commands.
+            if self.storage_degraded.is_some() && (cmd.writes_storage() || cmd.condition().is_some()) {
+                tracing::info!("storage is degraded, skip command: {:?}", cmd);
+                continue;
+            }
+
+            // Other than appending more entries or sending entries to other nodes, a command may
+            // depend on the appended entries being persisted, e.g., committing. Entries are
+            // pending only on a leader.
+            if !matches!(cmd, Command::AppendInputEntries { .. } | Command::Replicate { .. }) {
+                self.flush_pending_appends().await?;
+            }
+
+            let res = self.run_command(cmd).await?;
+
+            if let Some(cmd) = res {
+                tracing::debug!("early return: postpone command: {:?}", cmd);
+                self.engine.output.postpone_command(cmd);
+
+                if tracing::enabled!(Level::DEBUG) {
+                    for c in self.engine.output.iter_commands().take(8) {
+                        tracing::debug!("postponed, first 8 queued commands: {:?}", c);
+                    }
+                }
+
+                return Ok(());
+            }
+        }
+
+        if let Some(deadline) = self.flush_deadline() {
+            if InstantOf::<C>::now() >= deadline {
+                self.flush_pending_appends().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Broadcast the server state transitions happened in the engine.
+    fn send_server_state_transitions(&mut self) {
+        let transitions = self.engine.output.take_server_state_transitions();
+        if transitions.is_empty() {
+            return;
+        }
+
+        tracing::debug!(transitions = debug(&transitions), "{}", func_name!());
+
+        for (from, to) in transitions.iter() {
+            self.send_event(RaftEvent::ServerStateChanged { from: *from, to: *to });
+        }
+    }
+
+    /// Broadcast every election round started by this node that is finished.
+    fn send_election_rounds(&mut self) {
+        self.engine.finish_superseded_election_round();
+
+        for round in self.engine.output.take_election_rounds() {
+            self.send_event(RaftEvent::ElectionRoundFinished { round });
+        }
+    }
+
+    /// Broadcast a new term that is persisted.
+    fn send_term_change(&mut self, term: u64) {
+        tracing::debug!(term, "{}", func_name!());
+
+        self.send_event(RaftEvent::TermChanged { term });
+    }
+
+    /// Return `true` if the time elapsed since the last tick exceeds the max election timeout.
+    ///
+    /// Openraft times everything with a monotonic clock, so a wall clock adjustment, e.g., by NTP,
+    /// does not affect it. But the monotonic clock still jumps forward when the process is paused,
+    /// e.g., a VM is suspended or there is a long GC pause. Such a gap only makes a lease expire
+    /// earlier, which is safe, but it should not make a follower start an election before
+    /// handling the messages the leader sent during the gap.
+    fn detect_tick_gap(&mut self, now: InstantOf<C>) -> bool {
+        let Some(prev) = self.last_tick_at.replace(now) else {
+            return false;
+        };
+
+        let gap = now - prev;
+        if gap <= Duration::from_millis(self.config.election_timeout_max) {
+            return false;
+        }
+
+        tracing::warn!(
+            "{} ms elapsed since the last tick, exceeds election_timeout_max: {} ms; \
+             the process may have been paused or the clock jumped forward, defer the election check",
+            gap.as_millis(),
+            self.config.election_timeout_max
+        );
+        true
+    }
+
+    /// Append a blank entry if this node is a leader and its log has been idle for
+    /// [`Config::idle_noop_interval`].
+    fn handle_tick_idle_noop(&mut self, now: InstantOf<C>) {
+        if self.config.idle_noop_interval == 0 {
+            return;
+        }
+
+        let last_log_id = self.engine.state.last_log_id().copied();
 
-            tracing::debug!(
-                targets = debug(nodes.iter().map(|x| *x.0).collect::<Vec<_>>()),
-                "remove all targets from replication_metrics"
-            );
+        let Some(l) = &mut self.leader_data else {
+            return;
+        };
 
-            for (target, s) in nodes {
-                let handle = s.join_handle;
+        if l.last_log_seen.0 != last_log_id {
+            l.last_log_seen = (last_log_id, now);
+            return;
+        }
 
-                // Drop sender to notify the task to shutdown
-                drop(s.tx_repl);
+        if now < l.last_log_seen.1 + Duration::from_millis(self.config.idle_noop_interval) {
+            return;
+        }
 
-                tracing::debug!("joining removed replication: {}", target);
-                let _x = handle.await;
-                tracing::info!("Done joining removed replication : {}", target);
-            }
-        } else {
-            unreachable!("it has to be a leader!!!");
-        };
+        // Reset the timer even if the blank entry can not be appended.
+        l.last_log_seen.1 = now;
+
+        if let Ok(mut lh) = self.engine.leader_handler() {
+            tracing::info!(
+                last_log_id = display(last_log_id.display()),
+                "log is idle for {} ms, append a blank entry: {}",
+                self.config.idle_noop_interval,
+                func_name!()
+            );
+            lh.append_noop();
+        }
     }
 
-    /// Run as many commands as possible.
+    /// Broadcast an event to every subscriber of [`Raft::events()`].
     ///
-    /// If there is a command that waits for a callback, just return and wait for
-    /// next RaftMsg.
-    #[tracing::instrument(level = "debug", skip_all)]
-    pub(crate) async fn run_engine_commands(&mut self) -> Result<(), StorageError<C::NodeId>> {
-        if tracing::enabled!(Level::DEBUG) {
-            tracing::debug!("queued commands: start...");
-            for c in self.engine.output.iter_commands() {
-                tracing::debug!("queued commands: {:?}", c);
-            }
-            tracing::debug!("queued commands: end...");
+    /// [`Raft::events()`]: crate::Raft::events
+    fn send_event(&self, event: RaftEvent<C>) {
+        tracing::debug!(event = display(&event), "{}", func_name!());
+
+        // Error means there is no subscriber.
+        let _ = self.tx_events.send(event);
+    }
+
+    /// Notify the waiters of draining if no client write is pending.
+    fn notify_drained(&mut self) {
+        if self.drain_waiters.is_empty() || !self.client_resp_channels.is_empty() {
+            return;
         }
 
-        while let Some(cmd) = self.engine.output.pop_command() {
-            tracing::debug!("run command: {:?}", cmd);
+        tracing::info!("all pending client writes are done, notify drain waiters");
 
-            let res = self.run_command(cmd).await?;
+        for tx in self.drain_waiters.drain(..) {
+            let _ = tx.send(Ok(()));
+        }
+    }
 
-            if let Some(cmd) = res {
-                tracing::debug!("early return: postpone command: {:?}", cmd);
-                self.engine.output.postpone_command(cmd);
+    /// Notify the waiters of removal if the committed membership no longer includes this node,
+    /// after a committed membership including it has been seen.
+    ///
+    /// An uncommitted membership that removes this node may still be truncated by a new leader,
+    /// thus it is not a removal.
+    fn notify_removed(&mut self) {
+        let committed = self.engine.state.membership_state.committed();
 
-                if tracing::enabled!(Level::DEBUG) {
-                    for c in self.engine.output.iter_commands().take(8) {
-                        tracing::debug!("postponed, first 8 queued commands: {:?}", c);
-                    }
-                }
+        if committed.membership().contains(&self.id) {
+            self.member_since = *committed.log_id();
+            return;
+        }
 
-                return Ok(());
-            }
+        if self.removed_waiters.is_empty() || self.member_since.is_none() {
+            return;
         }
 
-        Ok(())
+        if committed.log_id() <= &self.member_since {
+            return;
+        }
+
+        tracing::info!(
+            membership = display(committed.as_ref()),
+            "this node is removed from membership, notify removal waiters"
+        );
+
+        for tx in self.removed_waiters.drain(..) {
+            let _ = tx.send(Ok(()));
+        }
     }
 
     /// Run an event handling loop
     ///
     /// It always returns a [`Fatal`] error upon returning.
     #[tracing::instrument(level="debug", skip_all, fields(id=display(self.id)))]
-    async fn runtime_loop(&mut self, mut rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
+    async fn runtime_loop(&mut self, rx_shutdown: &mut OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
         // Ratio control the ratio of number of RaftMsg to process to number of Notify to process.
         let mut balancer = Balancer::new(10_000);
 
         loop {
             self.flush_metrics();
+            self.notify_drained();
+            self.notify_removed();
 
             // In each loop, it does not have to check rx_shutdown and flush metrics for every RaftMsg
             // processed.
             // In each loop, the first step is blocking waiting for any message from any channel.
             // Then if there is any message, process as many as possible to maximize throughput.
 
+            // Wait for pending log appends to be flushed if there is no message for a while.
+            let flush_deadline = self.flush_deadline();
+            let flush_at = flush_deadline.unwrap_or_else(InstantOf::<C>::now);
+
             select! {
                 // Check shutdown in each loop first so that a message flood in `tx_api` won't block shutting down.
                 // `select!` without `biased` provides a random fairness.
@@ -920,7 +1967,7 @@ where
                 // See: https://docs.rs/tokio/latest/tokio/macro.select.html#fairness
                 biased;
 
-                _ = &mut rx_shutdown => {
+                _ = &mut *rx_shutdown => {
                     tracing::info!("recv from rx_shutdown");
                     return Err(Fatal::Stopped);
                 }
@@ -944,6 +1991,10 @@ where
                         }
                     };
                 }
+
+                _ = AsyncRuntimeOf::<C>::sleep_until(flush_at), if flush_deadline.is_some() => {
+                    self.flush_pending_appends().await?;
+                }
             }
 
             self.run_engine_commands().await?;
@@ -1039,6 +2090,11 @@ where
     }
 
     /// Spawn parallel vote requests to all cluster members.
+    ///
+    /// Every [`RaftNetworkV2::vote()`] call runs in its own task with an independent deadline of
+    /// `election_timeout_min`. A response is fed back to `RaftCore` as soon as it arrives, and the
+    /// engine becomes leader once a quorum granted the vote. Thus a slow or dead voter never delays
+    /// an election that can be won without it.
     #[tracing::instrument(level = "trace", skip_all)]
     async fn spawn_parallel_vote_requests(&mut self, vote_req: &VoteRequest<C>) {
         let members = self.engine.state.membership_state.effective().voter_ids();
@@ -1090,7 +2146,14 @@ where
                                 sender_vote: vote,
                             });
                         }
-                        Err(err) => tracing::error!({error=%err, target=display(target)}, "while requesting vote"),
+                        Err(err) => match err {
+                            // A candidate keeps requesting votes while peers are down: these are
+                            // expected and do not deserve error level logging.
+                            RPCError::Timeout(_) | RPCError::Unreachable(_) | RPCError::Network(_) => {
+                                tracing::warn!({error=%err, target=display(target)}, "while requesting vote")
+                            }
+                            _ => tracing::error!({error=%err, target=display(target)}, "while requesting vote"),
+                        },
                     }
                 }
                 .instrument(tracing::debug_span!(
@@ -1102,6 +2165,41 @@ where
         }
     }
 
+    /// Spawn a one-shot AppendEntries request to tell a removed node that it is no longer a member.
+    ///
+    /// The request is not retried and its result is only logged, so that an unreachable removed
+    /// node never blocks the membership change.
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn spawn_notify_removed(&mut self, target: C::NodeId, node: &C::Node, req: AppendEntriesRequest<C>) {
+        let mut client = self.network.new_client(target, node).await;
+
+        let ttl = Duration::from_millis(self.config.heartbeat_interval);
+        let option = RPCOption::new(ttl);
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::AsyncRuntime::spawn(
+            async move {
+                match C::AsyncRuntime::timeout(ttl, client.append_entries(req, option)).await {
+                    Ok(Ok(resp)) => {
+                        tracing::info!(target = display(target), "removed node is notified: {}", resp);
+                    }
+                    Ok(Err(err)) => {
+                        tracing::warn!({error=%err, target=display(target)}, "failed to notify removed node");
+                    }
+                    Err(_timeout) => {
+                        tracing::warn!(target = display(target), "timeout notifying removed node");
+                    }
+                }
+            }
+            .instrument(tracing::debug_span!(
+                parent: &Span::current(),
+                "notify_removed",
+                target = display(target)
+            )),
+        );
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub(super) fn handle_vote_request(&mut self, req: VoteRequest<C>, tx: VoteTx<C>) {
         tracing::info!(req = display(&req), func = func_name!());
@@ -1113,22 +2211,181 @@ where
         });
     }
 
+    /// Check if the local state machine is fresh enough for a read that tolerates `max_staleness`.
+    ///
+    /// A follower is fresh as of the latest time at which it had applied every log the leader had
+    /// committed. A leader is fresh as of the last time a quorum acknowledged it, once it has
+    /// applied every committed log.
+    ///
+    /// It returns the applied log id that a local read reflects.
+    pub(crate) fn handle_follower_read(
+        &mut self,
+        max_staleness: Duration,
+    ) -> Result<Option<LogId<C::NodeId>>, ForwardToLeader<C>> {
+        let applied = self.engine.state.io_applied().copied();
+        let now = InstantOf::<C>::now();
+
+        let fresh_as_of = if self.engine.state.is_leader(&self.id) {
+            if applied.as_ref() >= self.engine.state.committed() {
+                self.last_quorum_acked_time()
+            } else {
+                None
+            }
+        } else {
+            self.read_freshness.fresh_as_of(applied.as_ref())
+        };
+
+        if fresh_as_of.map(|t| now - t <= max_staleness) == Some(true) {
+            return Ok(applied);
+        }
+
+        tracing::debug!(
+            fresh_as_of = debug(fresh_as_of),
+            max_staleness = debug(max_staleness),
+            "local state machine is too stale, reject follower read"
+        );
+
+        Err(self.engine.state.forward_to_leader())
+    }
+
+    /// Spawn a ReadIndex request to the leader, and send back the log id returned by the leader
+    /// through `tx`.
+    ///
+    /// If this node knows of no leader, or is itself the leader, a [`ForwardToLeader`] error is
+    /// sent back at once.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn spawn_read_index_request(
+        &mut self,
+        tx: ResultSender<C, Option<LogId<C::NodeId>>, FollowerReadError<C>>,
+    ) {
+        let leader_id = self.current_leader();
+        let leader_node = self.get_leader_node(leader_id);
+
+        let (target, target_node) = match (leader_id, leader_node) {
+            (Some(id), Some(node)) if id != self.id => (id, node),
+            _ => {
+                let _ = tx.send(Err(self.engine.state.forward_to_leader().into()));
+                return;
+            }
+        };
+
+        let mut client = self.network.new_client(target, &target_node).await;
+
+        let ttl = Duration::from_millis(self.config.election_timeout_max);
+        let id = self.id;
+        let option = RPCOption::new(ttl);
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::AsyncRuntime::spawn(
+            async move {
+                let tm_res = C::AsyncRuntime::timeout(ttl, client.read_index(ReadIndexRequest::new(id), option)).await;
+                let res = match tm_res {
+                    Ok(Ok(resp)) => Ok(resp.read_log_id),
+                    Ok(Err(err)) => {
+                        tracing::warn!({error=%err, target=display(target)}, "while requesting read index");
+                        Err(err.into())
+                    }
+                    Err(_timeout) => {
+                        let timeout_err = Timeout::<C> {
+                            action: RPCTypes::ReadIndex,
+                            id,
+                            target,
+                            timeout: ttl,
+                        };
+                        tracing::warn!({error = %timeout_err, target = display(target)}, "timeout");
+                        Err(FollowerReadError::RPCError(RPCError::Timeout(timeout_err)))
+                    }
+                };
+
+                let _ = tx.send(res);
+            }
+            .instrument(tracing::debug_span!(
+                parent: &Span::current(),
+                "send_read_index_req",
+                target = display(target)
+            )),
+        );
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub(super) fn handle_append_entries_request(&mut self, req: AppendEntriesRequest<C>, tx: AppendEntriesTx<C>) {
         tracing::debug!(req = display(&req), func = func_name!());
 
+        let num_entries = req.entries.len() as u64;
+
         let is_ok = self.engine.handle_append_entries(&req.vote, req.prev_log_id, req.entries, Some(tx));
 
         if is_ok {
+            if let Some(observer) = &self.observer {
+                observer.send(ObserverEvent::AppendEntriesReceived {
+                    leader_id: req.vote.leader_id().voted_for(),
+                    prev_log_id: req.prev_log_id,
+                    num_entries,
+                    leader_commit: req.leader_commit,
+                });
+            }
+
+            let now = InstantOf::<C>::now();
+            self.read_freshness.heard_commit(req.leader_commit, now, self.engine.state.io_applied());
+
             self.engine.handle_commit_entries(req.leader_commit);
         }
     }
 
+    /// In read-only degraded mode, reject a message that requires writing to the storage, or that
+    /// changes the raft state, which is frozen.
+    ///
+    /// It returns the message back if it should be handled as usual.
+    /// AppendEntries and Vote requests fail with [`Fatal::StorageError`]. Other rejected requests
+    /// whose response can not carry an error have their sender dropped, and the caller then gets
+    /// the storage error from the metrics.
+    fn reject_if_storage_degraded(&self, msg: RaftMsg<C>) -> Option<RaftMsg<C>> {
+        let Some(storage_error) = &self.storage_degraded else {
+            return Some(msg);
+        };
+
+        match msg {
+            RaftMsg::ClientWriteRequest { tx, .. } | RaftMsg::ChangeMembership { tx, .. } => {
+                tx.send(Err(ClientWriteError::ForwardToLeader(ForwardToLeader::empty())));
+            }
+            RaftMsg::ClientWriteBatchRequest { txs, .. } => {
+                for tx in txs {
+                    tx.send(Err(ClientWriteError::ForwardToLeader(ForwardToLeader::empty())));
+                }
+            }
+            RaftMsg::AppendEntries { rpc, tx } => {
+                tracing::info!("storage is degraded, reject AppendEntries: {}", rpc);
+                let _ = tx.send(Err(Fatal::StorageError(storage_error.clone())));
+            }
+            RaftMsg::RequestVote { rpc, tx } => {
+                tracing::info!("storage is degraded, reject RequestVote: {}", rpc);
+                let _ = tx.send(Err(Fatal::StorageError(storage_error.clone())));
+            }
+            RaftMsg::InstallFullSnapshot { .. }
+            | RaftMsg::BeginReceivingSnapshot { .. }
+            | RaftMsg::Initialize { .. }
+            | RaftMsg::ExternalCommand {
+                cmd: ExternalCommand::Elect | ExternalCommand::Snapshot | ExternalCommand::PurgeLog { .. },
+            } => {
+                tracing::info!("storage is degraded, reject: {}", msg);
+            }
+            _ => return Some(msg),
+        }
+
+        None
+    }
+
     // TODO: Make this method non-async. It does not need to run any async command in it.
     #[tracing::instrument(level = "debug", skip(self, msg), fields(state = debug(self.engine.state.server_state), id=display(self.id)))]
     pub(crate) async fn handle_api_msg(&mut self, msg: RaftMsg<C>) {
         tracing::debug!("recv from rx_api: {}", msg);
 
+        let msg = match self.reject_if_storage_degraded(msg) {
+            Some(msg) => msg,
+            None => return,
+        };
+
         match msg {
             RaftMsg::AppendEntries { rpc, tx } => {
                 self.handle_append_entries_request(rpc, tx);
@@ -1150,20 +2407,34 @@ where
             RaftMsg::InstallFullSnapshot { vote, snapshot, tx } => {
                 self.engine.handle_install_full_snapshot(vote, snapshot, tx);
             }
+            RaftMsg::SnapshotChunkReceived { progress } => {
+                self.installing_snapshot = progress;
+            }
             RaftMsg::CheckIsLeaderRequest { tx } => {
                 self.handle_check_is_leader_request(tx).await;
             }
-            RaftMsg::ClientWriteRequest { app_data, tx } => {
-                self.write_entry(C::Entry::from_app_data(app_data), Some(tx));
+            RaftMsg::LeaseReadRequest { tx } => {
+                self.handle_lease_read_request(tx);
+            }
+            RaftMsg::ClientWriteRequest { app_data, context, tx } => {
+                let mut entry = C::Entry::from_app_data(app_data);
+                if !context.is_empty() {
+                    entry.set_context(context);
+                }
+                self.write_entry(entry, Some(tx));
+            }
+            RaftMsg::ClientWriteBatchRequest { app_data, txs } => {
+                let entries = app_data.into_iter().map(C::Entry::from_app_data).collect();
+                self.write_entries(entries, txs);
             }
-            RaftMsg::Initialize { members, tx } => {
+            RaftMsg::Initialize { members, weights, tx } => {
                 tracing::info!(
                     members = debug(&members),
                     "received RaftMsg::Initialize: {}",
                     func_name!()
                 );
 
-                self.handle_initialize(members, tx);
+                self.handle_initialize(members, weights, tx);
             }
             RaftMsg::ChangeMembership { changes, retain, tx } => {
                 tracing::info!(
@@ -1205,6 +2476,81 @@ where
                     ExternalCommand::PurgeLog { upto } => {
                         self.engine.trigger_purge_log(upto);
                     }
+                    ExternalCommand::SetObserver { observer } => {
+                        self.observer = Some(ObserverHandle::spawn(observer));
+                    }
+                    ExternalCommand::SetQuorumPolicy { policy } => {
+                        self.engine.config.quorum_policy = QuorumPolicyRef(policy);
+                    }
+                    ExternalCommand::GetLeadershipDiag { tx } => {
+                        let _ = tx.send(Ok(self.leadership_diag()));
+                    }
+                    ExternalCommand::GetTopology { tx } => {
+                        let _ = tx.send(Ok(self.cluster_topology()));
+                    }
+                    ExternalCommand::GetClusterCommitView { tx } => {
+                        let _ = tx.send(self.cluster_commit_view());
+                    }
+                    ExternalCommand::SetReadReplica { id, read_replica } => {
+                        if read_replica {
+                            self.engine.config.read_replicas.insert(id);
+                        } else {
+                            self.engine.config.read_replicas.remove(&id);
+                        }
+                    }
+                    ExternalCommand::SetReplicationPriority { id, priority } => {
+                        if priority > 0 {
+                            self.engine.config.replication_priorities.insert(id, priority);
+                        } else {
+                            self.engine.config.replication_priorities.remove(&id);
+                        }
+                    }
+                    ExternalCommand::Drain { tx } => {
+                        tracing::info!(
+                            pending_client_writes = display(self.client_resp_channels.len()),
+                            "start draining: reject new client writes"
+                        );
+                        self.draining = true;
+                        self.drain_waiters.push(tx);
+                    }
+                    ExternalCommand::WaitRemoved { tx } => {
+                        self.removed_waiters.push(tx);
+                    }
+                    ExternalCommand::ResyncFollower { id } => {
+                        if let Ok(mut lh) = self.engine.leader_handler() {
+                            lh.replication_handler().resync_target(id);
+                        } else {
+                            tracing::warn!("not a leader, ignore resync of node-{}", id);
+                        }
+                    }
+                    ExternalCommand::ReadCommitted { start, max, tx } => {
+                        let res = self.read_committed(start, max).await;
+                        let _ = tx.send(res);
+                    }
+                    ExternalCommand::ReadUncommitted { tx } => {
+                        let res = self.read_uncommitted().await;
+                        let _ = tx.send(res);
+                    }
+                    ExternalCommand::StepDown { quiesce, tx } => {
+                        let res = self.engine.leader_yield();
+                        if res.is_ok() {
+                            self.quiesced_until = quiesce.map(|d| InstantOf::<C>::now() + d);
+                        }
+                        let _ = tx.send(res);
+                    }
+                    ExternalCommand::FollowerRead { max_staleness, tx } => {
+                        let res = self.handle_follower_read(max_staleness);
+                        let _ = tx.send(res);
+                    }
+                    ExternalCommand::ReadIndexFromLeader { tx } => {
+                        self.spawn_read_index_request(tx).await;
+                    }
+                    ExternalCommand::StateMachineCommand { sm_cmd } => {
+                        let res = self.sm_handle.send(sm_cmd);
+                        if let Err(e) = res {
+                            tracing::error!(error = display(e), "error sending sm::Command to sm::Worker");
+                        }
+                    }
                 }
             }
         };
@@ -1215,6 +2561,19 @@ where
     pub(crate) fn handle_notify(&mut self, notify: Notify<C>) -> Result<(), Fatal<C>> {
         tracing::debug!("recv from rx_notify: {}", notify);
 
+        // In degraded mode the raft state is frozen: it may not match the storage, and must not be
+        // used to make any further decision, such as becoming a leader. Only results of the state
+        // machine commands already sent, and ticks, are still handled.
+        if self.storage_degraded.is_some()
+            && matches!(
+                notify,
+                Notify::VoteResponse { .. } | Notify::HigherVote { .. } | Notify::Network { .. }
+            )
+        {
+            tracing::info!("storage is degraded, ignore: {}", notify);
+            return Ok(());
+        }
+
         match notify {
             Notify::VoteResponse {
                 target,
@@ -1249,18 +2608,43 @@ where
                 );
 
                 if self.does_vote_match(&vote, "HigherVote") {
+                    self.replication_rejections.insert(target, ReplicationRejection::HigherVote(higher));
                     // Rejected vote change is ok.
                     let _ = self.engine.vote_handler().update_vote(&higher);
                 }
             }
 
+            Notify::ApplyFailed { log_id, error } => {
+                tracing::error!(
+                    log_id = display(&log_id),
+                    error = display(&error),
+                    "state machine failed to apply: {}",
+                    func_name!()
+                );
+
+                if let Some(observer) = &self.observer {
+                    observer.send(ObserverEvent::ApplyError {
+                        log_id,
+                        error: error.clone(),
+                    });
+                }
+                self.send_event(RaftEvent::ApplyFailed { log_id, error });
+
+                // The error itself is returned by the state machine worker right after this
+                // notification and is handled as a `Notify::StateMachine` result.
+            }
+
             Notify::Tick { i } => {
                 // check every timer
 
                 let now = InstantOf::<C>::now();
                 tracing::debug!("received tick: {}, now: {:?}", i, now);
 
-                self.handle_tick_election();
+                // After a gap, messages from the leader may be still queued: do not let the
+                // election timeout expire before they are handled.
+                if !self.detect_tick_gap(now) {
+                    self.handle_tick_election();
+                }
 
                 // TODO: test: fixture: make isolated_nodes a single-way isolating.
 
@@ -1280,6 +2664,8 @@ where
                     }
                 }
 
+                self.handle_tick_idle_noop(now);
+
                 // When a membership that removes the leader is committed,
                 // the leader continue to work for a short while before reverting to a learner.
                 // This way, let the leader replicate the `membership-log-is-committed` message to
@@ -1319,6 +2705,29 @@ where
                         }
                     }
 
+                    replication::Response::LogPurged {
+                        target,
+                        request_id,
+                        needed_index,
+                        session_id,
+                    } => {
+                        tracing::warn!(
+                            target = display(target),
+                            request_id = display(request_id),
+                            needed_index = display(needed_index),
+                            snapshot = display(self.engine.state.snapshot_last_log_id().display()),
+                            "logs to replicate are purged, replicate by snapshot"
+                        );
+
+                        if self.does_replication_session_match(&session_id, "ReplicationLogPurged") {
+                            self.replication_log_purged += 1;
+
+                            if self.engine.internal_server_state.is_leading() {
+                                self.engine.replication_handler().update_log_purged(target, request_id);
+                            }
+                        }
+                    }
+
                     replication::Response::StorageError { error } => {
                         tracing::error!(
                             error = display(&error),
@@ -1339,6 +2748,8 @@ where
                         );
 
                         if self.does_vote_match(&vote, "HigherVote") {
+                            self.replication_rejections.insert(target, ReplicationRejection::HigherVote(higher));
+
                             // Rejected vote change is ok.
                             let _ = self.engine.vote_handler().update_vote(&higher);
                         }
@@ -1350,6 +2761,7 @@ where
                 tracing::debug!("sm::StateMachine command result: {:?}", command_result);
 
                 let seq = command_result.command_seq;
+                let elapsed = command_result.elapsed;
                 let res = command_result.result?;
 
                 match res {
@@ -1378,6 +2790,8 @@ where
                         // Update in-memory state first, then the io state.
                         // In-memory state should always be ahead or equal to the io state.
 
+                        self.storage_metrics.record(StorageOp::BuildSnapshot, elapsed);
+
                         let last_log_id = meta.last_log_id;
                         self.engine.finish_building_snapshot(meta);
 
@@ -1395,9 +2809,16 @@ where
                             let st = self.engine.state.io_state_mut();
                             st.update_applied(meta.last_log_id);
                             st.update_snapshot(meta.last_log_id);
+
+                            if let Some(log_id) = meta.last_log_id {
+                                self.durable_log.update(log_id);
+                            }
+
+                            self.send_event(RaftEvent::SnapshotInstalled { meta });
                         }
                     }
                     sm::Response::Apply(res) => {
+                        self.storage_metrics.record(StorageOp::Apply, elapsed);
                         self.engine.state.io_state_mut().update_applied(Some(res.last_applied));
 
                         self.handle_apply_result(res);
@@ -1414,6 +2835,19 @@ where
 
         tracing::debug!("try to trigger election by tick, now: {:?}", now);
 
+        if self.storage_degraded.is_some() {
+            tracing::debug!("storage is degraded, do not elect");
+            return;
+        }
+
+        if let Some(until) = self.quiesced_until {
+            if now < until {
+                tracing::debug!("leadership is yielded until {:?}, do not elect", until);
+                return;
+            }
+            self.quiesced_until = None;
+        }
+
         // TODO: leader lease should be extended. Or it has to examine if it is leader
         //       before electing.
         if self.engine.state.server_state == ServerState::Leader {
@@ -1431,7 +2865,9 @@ where
             return;
         }
 
-        if self.engine.state.membership_state.effective().voter_ids().count() == 1 {
+        let voter_count = self.engine.state.membership_state.effective().voter_ids().count();
+
+        if voter_count == 1 {
             tracing::debug!("this is the only voter, do election at once");
         } else {
             tracing::debug!("there are multiple voter, check election timeout");
@@ -1440,12 +2876,26 @@ where
             let utime = self.engine.state.vote_last_modified();
             let timer_config = &self.engine.config.timer_config;
 
-            let mut election_timeout = if current_vote.is_committed() {
-                timer_config.leader_lease + timer_config.election_timeout
+            // A node that was just leader starts an election earlier, to take back the leadership.
+            // Unless it gave up the leadership on purpose.
+            let in_affinity_window = self.stepped_down_at.map(|t| now - t < timer_config.leader_affinity_window);
+            let base_timeout = if in_affinity_window == Some(true) && !self.engine.state.yielded {
+                timer_config.affinity_election_timeout
             } else {
                 timer_config.election_timeout
             };
 
+            // Larger cluster takes longer to finish a vote round, extend the timeout accordingly.
+            // The voter count is read from the current effective membership, so that it follows
+            // membership changes.
+            let base_timeout = base_timeout + timer_config.voter_count_timeout(voter_count);
+
+            let mut election_timeout = if current_vote.is_committed() {
+                timer_config.leader_lease + base_timeout
+            } else {
+                base_timeout
+            };
+
             if self.engine.is_there_greater_log() {
                 election_timeout += timer_config.smaller_log_timeout;
             }
@@ -1470,6 +2920,10 @@ where
         // Every time elect, reset this flag.
         self.engine.reset_greater_log();
 
+        // Every time elect, pick a new election timeout, so that nodes that timed out at the
+        // same time are unlikely to do it again.
+        self.engine.config.timer_config.election_timeout = self.election_timeout_rng.next_timeout::<C::AsyncRuntime>();
+
         tracing::info!("do trigger election");
         self.engine.elect();
     }
@@ -1500,6 +2954,22 @@ where
 
         // A leader may have stepped down.
         if self.engine.internal_server_state.is_leading() {
+            if let Ok(repl_res) = &result {
+                match repl_res.result {
+                    Ok(_) => {
+                        self.replication_rejections.remove(&target);
+                    }
+                    Err(conflict) => {
+                        tracing::info!(
+                            target = display(target),
+                            prev_log_id = display(conflict),
+                            "target rejected AppendEntries: log mismatch"
+                        );
+                        self.replication_rejections.insert(target, ReplicationRejection::LogMismatch(conflict));
+                    }
+                }
+            }
+
             self.engine.replication_handler().update_progress(target, request_id, result);
         }
     }
@@ -1587,37 +3057,62 @@ where
             Command::BecomeLeader => {
                 debug_assert!(self.leader_data.is_none(), "can not become leader twice");
                 self.leader_data = Some(LeaderData::new());
+                self.replication_rejections.clear();
+                self.leadership_epoch += 1;
             }
             Command::QuitLeader => {
                 self.leader_data = None;
+                self.stepped_down_at = Some(InstantOf::<C>::now());
             }
             Command::AppendEntry { entry } => {
                 let log_id = *entry.get_log_id();
                 tracing::debug!("AppendEntry: {}", &entry);
 
-                self.append_to_log([entry], log_id).await?;
+                let flushed = self.append_to_log([entry], log_id).await?;
 
                 // The leader may have changed.
                 // But reporting to a different leader is not a problem.
-                if let Ok(mut lh) = self.engine.leader_handler() {
-                    lh.replication_handler().update_local_progress(Some(log_id));
+                if flushed {
+                    if let Ok(mut lh) = self.engine.leader_handler() {
+                        lh.replication_handler().update_local_progress(Some(log_id));
+                    }
                 }
             }
             Command::AppendInputEntries { entries } => {
                 let last_log_id = *entries.last().unwrap().get_log_id();
                 tracing::debug!("AppendInputEntries: {}", DisplaySlice::<_>(&entries),);
 
-                self.append_to_log(entries, last_log_id).await?;
+                // A follower responds to the leader right after appending, which waits for the
+                // flush anyway, and the leader sends no more entries before the response.
+                if let LogFlushMode::Batched { max_entries, .. } = self.config.log_flush_mode {
+                    if self.engine.state.is_leader(&self.id) {
+                        self.submit_to_log(entries, last_log_id, max_entries).await?;
+                        return Ok(None);
+                    }
+                }
+
+                let flushed = self.append_to_log(entries, last_log_id).await?;
 
                 // The leader may have changed.
                 // But reporting to a different leader is not a problem.
-                if let Ok(mut lh) = self.engine.leader_handler() {
-                    lh.replication_handler().update_local_progress(Some(last_log_id));
+                if flushed {
+                    if let Ok(mut lh) = self.engine.leader_handler() {
+                        lh.replication_handler().update_local_progress(Some(last_log_id));
+                    }
                 }
             }
             Command::SaveVote { vote } => {
+                let start = InstantOf::<C>::now();
                 self.log_store.save_vote(&vote).await?;
+                self.storage_metrics.record(StorageOp::SaveVote, start.elapsed());
+
+                let prev_term = self.engine.state.io_state().vote().leader_id().term;
                 self.engine.state.io_state_mut().update_vote(vote);
+
+                let term = vote.leader_id().term;
+                if term > prev_term {
+                    self.send_term_change(term);
+                }
             }
             Command::PurgeLog { upto } => {
                 self.log_store.purge(upto).await?;
@@ -1626,6 +3121,14 @@ where
             Command::DeleteConflictLog { since } => {
                 self.log_store.truncate(since).await?;
 
+                let prev = match since.index {
+                    0 => None,
+                    i => {
+                        self.engine.state.get_log_id(i - 1).or_else(|| self.engine.state.last_purged_log_id().copied())
+                    }
+                };
+                self.durable_log.truncate(since.index, prev);
+
                 // Inform clients waiting for logs to be applied.
                 let removed = self.client_resp_channels.split_off(&since.index);
                 if !removed.is_empty() {
@@ -1649,6 +3152,22 @@ where
             Command::SendVote { vote_req } => {
                 self.spawn_parallel_vote_requests(&vote_req).await;
             }
+            Command::NotifyRemoved {
+                target,
+                node,
+                vote,
+                prev_log_id,
+                membership_entry,
+                leader_commit,
+            } => {
+                let req = AppendEntriesRequest {
+                    vote,
+                    prev_log_id,
+                    entries: vec![membership_entry],
+                    leader_commit,
+                };
+                self.spawn_notify_removed(target, &node, req).await;
+            }
             Command::ReplicateCommitted { committed } => {
                 if let Some(l) = &self.leader_data {
                     for node in l.replications.values() {
@@ -1663,7 +3182,13 @@ where
                 ref already_committed,
                 ref upto,
             } => {
-                self.log_store.save_committed(Some(*upto)).await?;
+                // Committing logs that are not durable is a bug. Do not apply them, which can not
+                // be undone: enter degraded mode and step down instead.
+                self.durable_log.check_apply(upto)?;
+
+                if self.config.persist_commit_index {
+                    self.log_store.save_committed(Some(*upto)).await?;
+                }
                 self.apply_to_state_machine(seq, already_committed.next_index(), upto.index).await?;
             }
             Command::Replicate { req, target } => {
@@ -1,10 +1,24 @@
 //! This mod defines external command sent by application to Raft.
 
 use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::core::raft_msg::ResultSender;
+use crate::core::sm;
+use crate::error::FollowerReadError;
+use crate::error::ForwardToLeader;
+use crate::error::ReplayError;
+use crate::error::UncommittedEntriesError;
+use crate::raft::QuorumPolicy;
+use crate::raft::RaftObserver;
+use crate::ClusterCommitView;
+use crate::LeadershipDiag;
+use crate::LogEntrySummary;
+use crate::LogId;
 use crate::RaftTypeConfig;
 use crate::Snapshot;
+use crate::Topology;
 
 /// Application-triggered Raft actions for testing and administration.
 ///
@@ -31,6 +45,77 @@ pub(crate) enum ExternalCommand<C: RaftTypeConfig> {
     ///
     /// [`max_in_snapshot_log_to_keep`]: `crate::Config::max_in_snapshot_log_to_keep`
     PurgeLog { upto: u64 },
+
+    /// Install an observer that is notified of events in `RaftCore`, replacing the previous one.
+    SetObserver { observer: Arc<dyn RaftObserver<C>> },
+
+    /// Install a policy that decides the committed log id on a leader.
+    SetQuorumPolicy { policy: Arc<dyn QuorumPolicy<C>> },
+
+    /// Get a report explaining the leadership status of this node.
+    GetLeadershipDiag { tx: ResultSender<C, LeadershipDiag<C>> },
+
+    /// Get every node in the membership with its role and replication state.
+    GetTopology { tx: ResultSender<C, Topology<C>> },
+
+    /// Get the replication and commit state of every node, as last known by the leader.
+    GetClusterCommitView {
+        tx: ResultSender<C, ClusterCommitView<C>, ForwardToLeader<C>>,
+    },
+
+    /// Mark or unmark a learner as a read replica, which is replicated with a higher priority.
+    SetReadReplica { id: C::NodeId, read_replica: bool },
+
+    /// Set the replication priority of a target, `0` resets it to the default.
+    SetReplicationPriority { id: C::NodeId, priority: u64 },
+
+    /// Reject new client writes, and notify `tx` once no client write is pending.
+    Drain { tx: ResultSender<C, ()> },
+
+    /// Notify `tx` once this node observes a membership that removes it.
+    WaitRemoved { tx: ResultSender<C, ()> },
+
+    /// Reset the replication progress of a target and find the matching log from scratch.
+    ResyncFollower { id: C::NodeId },
+
+    /// Read at most `max` committed log entries starting from index `start`.
+    ///
+    /// An empty `Vec` is sent back if there is no committed log at `start` yet.
+    ReadCommitted {
+        start: u64,
+        max: u64,
+        tx: ResultSender<C, Vec<C::Entry>, ReplayError<C>>,
+    },
+
+    /// Read summaries of the log entries after the committed log id, if this node is a leader.
+    ReadUncommitted {
+        tx: ResultSender<C, Vec<LogEntrySummary<C>>, UncommittedEntriesError<C>>,
+    },
+
+    /// Give up leadership and become a follower, if this node is a leader.
+    ///
+    /// If `quiesce` is `Some`, this node does not start an election for that duration.
+    StepDown {
+        quiesce: Option<Duration>,
+        tx: ResultSender<C, (), ForwardToLeader<C>>,
+    },
+
+    /// Check if a local read tolerating `max_staleness` can be served, see
+    /// [`Raft::follower_read()`](crate::Raft::follower_read).
+    FollowerRead {
+        max_staleness: Duration,
+        tx: ResultSender<C, Option<LogId<C::NodeId>>, ForwardToLeader<C>>,
+    },
+
+    /// Ask the leader for the log id a linearizable read on this node has to wait for, see
+    /// [`Raft::follower_read_linearizable()`](crate::Raft::follower_read_linearizable).
+    ReadIndexFromLeader {
+        tx: ResultSender<C, Option<LogId<C::NodeId>>, FollowerReadError<C>>,
+    },
+
+    /// Send a command to the state machine worker, see
+    /// [`Raft::with_state_machine()`](crate::Raft::with_state_machine).
+    StateMachineCommand { sm_cmd: sm::Command<C> },
 }
 
 impl<C> fmt::Debug for ExternalCommand<C>
@@ -61,6 +146,54 @@ where C: RaftTypeConfig
             ExternalCommand::PurgeLog { upto } => {
                 write!(f, "PurgeLog[..={}]", upto)
             }
+            ExternalCommand::SetObserver { .. } => {
+                write!(f, "SetObserver")
+            }
+            ExternalCommand::SetQuorumPolicy { .. } => {
+                write!(f, "SetQuorumPolicy")
+            }
+            ExternalCommand::GetLeadershipDiag { .. } => {
+                write!(f, "GetLeadershipDiag")
+            }
+            ExternalCommand::GetTopology { .. } => {
+                write!(f, "GetTopology")
+            }
+            ExternalCommand::GetClusterCommitView { .. } => {
+                write!(f, "GetClusterCommitView")
+            }
+            ExternalCommand::SetReadReplica { id, read_replica } => {
+                write!(f, "SetReadReplica: {}: {}", id, read_replica)
+            }
+            ExternalCommand::SetReplicationPriority { id, priority } => {
+                write!(f, "SetReplicationPriority: {}: {}", id, priority)
+            }
+            ExternalCommand::Drain { .. } => {
+                write!(f, "Drain")
+            }
+            ExternalCommand::WaitRemoved { .. } => {
+                write!(f, "WaitRemoved")
+            }
+            ExternalCommand::ResyncFollower { id } => {
+                write!(f, "ResyncFollower: {}", id)
+            }
+            ExternalCommand::ReadCommitted { start, max, .. } => {
+                write!(f, "ReadCommitted: start: {}, max: {}", start, max)
+            }
+            ExternalCommand::ReadUncommitted { .. } => {
+                write!(f, "ReadUncommitted")
+            }
+            ExternalCommand::StepDown { quiesce, .. } => {
+                write!(f, "StepDown: quiesce: {:?}", quiesce)
+            }
+            ExternalCommand::FollowerRead { max_staleness, .. } => {
+                write!(f, "FollowerRead: max_staleness: {:?}", max_staleness)
+            }
+            ExternalCommand::ReadIndexFromLeader { .. } => {
+                write!(f, "ReadIndexFromLeader")
+            }
+            ExternalCommand::StateMachineCommand { sm_cmd } => {
+                write!(f, "StateMachineCommand: {:?}", sm_cmd)
+            }
         }
     }
 }
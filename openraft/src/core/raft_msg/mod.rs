@@ -2,9 +2,12 @@ use std::collections::BTreeMap;
 use std::fmt;
 
 use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::display_ext::DisplayOptionExt;
 use crate::error::CheckIsLeaderError;
+use crate::error::Fatal;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::metrics::SnapshotInstallProgress;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
 use crate::raft::BoxCoreFn;
@@ -26,10 +29,14 @@ pub(crate) mod external_command;
 pub(crate) type ResultSender<C, T, E = Infallible> = OneshotSenderOf<C, Result<T, E>>;
 
 /// TX for Vote Response
-pub(crate) type VoteTx<C> = ResultSender<C, VoteResponse<C>>;
+///
+/// It fails with [`Fatal`] if this node can not serve the request, e.g., its storage is degraded.
+pub(crate) type VoteTx<C> = ResultSender<C, VoteResponse<C>, Fatal<C>>;
 
 /// TX for Append Entries Response
-pub(crate) type AppendEntriesTx<C> = ResultSender<C, AppendEntriesResponse<C>>;
+///
+/// It fails with [`Fatal`] if this node can not serve the request, e.g., its storage is degraded.
+pub(crate) type AppendEntriesTx<C> = ResultSender<C, AppendEntriesResponse<C>, Fatal<C>>;
 
 /// TX for Linearizable Read Response
 pub(crate) type ClientReadTx<C> = ResultSender<C, (Option<LogIdOf<C>>, Option<LogIdOf<C>>), CheckIsLeaderError<C>>;
@@ -66,17 +73,45 @@ where C: RaftTypeConfig
         tx: ResultSender<C, Box<SnapshotDataOf<C>>, Infallible>,
     },
 
+    /// A chunk of the snapshot being received is written, or the receiving finished or failed.
+    ///
+    /// `progress` is `None` if no snapshot is being received any more.
+    SnapshotChunkReceived {
+        progress: Option<SnapshotInstallProgress<C::NodeId>>,
+    },
+
     ClientWriteRequest {
         app_data: C::D,
+
+        /// An opaque context to attach to the entry, empty if there is none.
+        context: Vec<u8>,
+
         tx: ResponderOf<C>,
     },
 
+    /// Write a batch of application data as contiguous log entries.
+    ///
+    /// Every `app_data` has a responder at the same position in `txs`.
+    ClientWriteBatchRequest {
+        app_data: Vec<C::D>,
+        txs: Vec<ResponderOf<C>>,
+    },
+
     CheckIsLeaderRequest {
         tx: ClientReadTx<C>,
     },
 
+    /// Get the read log id with the leader lease, without contacting other nodes.
+    LeaseReadRequest {
+        tx: ClientReadTx<C>,
+    },
+
     Initialize {
         members: BTreeMap<C::NodeId, C::Node>,
+        /// Voting weight of voters, see [`Membership::with_weights()`].
+        ///
+        /// [`Membership::with_weights()`]: crate::Membership::with_weights
+        weights: BTreeMap<C::NodeId, u64>,
         tx: ResultSender<C, (), InitializeError<C>>,
     },
 
@@ -117,8 +152,15 @@ where C: RaftTypeConfig
             RaftMsg::InstallFullSnapshot { vote, snapshot, .. } => {
                 write!(f, "InstallFullSnapshot: vote: {}, snapshot: {}", vote, snapshot)
             }
+            RaftMsg::SnapshotChunkReceived { progress } => {
+                write!(f, "SnapshotChunkReceived: {}", progress.display())
+            }
             RaftMsg::ClientWriteRequest { .. } => write!(f, "ClientWriteRequest"),
+            RaftMsg::ClientWriteBatchRequest { app_data, .. } => {
+                write!(f, "ClientWriteBatchRequest: n: {}", app_data.len())
+            }
             RaftMsg::CheckIsLeaderRequest { .. } => write!(f, "CheckIsLeaderRequest"),
+            RaftMsg::LeaseReadRequest { .. } => write!(f, "LeaseReadRequest"),
             RaftMsg::Initialize { members, .. } => {
                 // TODO: avoid using Debug
                 write!(f, "Initialize: {:?}", members)
@@ -0,0 +1,124 @@
+//! Track how fresh the state machine of a follower is, for serving bounded-staleness reads.
+
+use std::collections::VecDeque;
+
+use crate::type_config::alias::InstantOf;
+use crate::LogId;
+use crate::RaftTypeConfig;
+
+/// Tracks the latest time at which the leader's committed log id is applied to the local state
+/// machine.
+///
+/// Every accepted AppendEntries tells the committed log id of the leader at the time it is
+/// received. Once the local state machine has applied up to that log id, reads on this node reflect
+/// every write the leader had committed at that time, i.e., the reads are fresh as of that time.
+pub(crate) struct ReadFreshness<C>
+where C: RaftTypeConfig
+{
+    /// Committed log ids heard from the leader that are not yet applied, and when they are heard.
+    ///
+    /// Committed log ids are in ascending order. A repeated log id only updates the time.
+    heard: VecDeque<(Option<LogId<C::NodeId>>, InstantOf<C>)>,
+
+    /// The latest time at which every log the leader had committed is applied.
+    fresh_as_of: Option<InstantOf<C>>,
+
+    /// The committed log id last heard from the leader and when it is heard.
+    last_heard: Option<(Option<LogId<C::NodeId>>, InstantOf<C>)>,
+}
+
+impl<C> ReadFreshness<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            heard: VecDeque::new(),
+            fresh_as_of: None,
+            last_heard: None,
+        }
+    }
+
+    /// Record the leader's committed log id received at `now`.
+    pub(crate) fn heard_commit(
+        &mut self,
+        committed: Option<LogId<C::NodeId>>,
+        now: InstantOf<C>,
+        applied: Option<&LogId<C::NodeId>>,
+    ) {
+        self.last_heard = Some((committed, now));
+
+        match self.heard.back_mut() {
+            Some((c, t)) if *c == committed => *t = now,
+            _ => self.heard.push_back((committed, now)),
+        }
+
+        self.update_applied(applied);
+    }
+
+    /// Returns the latest time as of which the local state machine is fresh, with the given
+    /// applied log id.
+    pub(crate) fn fresh_as_of(&mut self, applied: Option<&LogId<C::NodeId>>) -> Option<InstantOf<C>> {
+        self.update_applied(applied);
+        self.fresh_as_of
+    }
+
+    /// Returns the committed log id last heard from the leader and when it is heard.
+    pub(crate) fn last_heard(&self) -> Option<&(Option<LogId<C::NodeId>>, InstantOf<C>)> {
+        self.last_heard.as_ref()
+    }
+
+    fn update_applied(&mut self, applied: Option<&LogId<C::NodeId>>) {
+        while let Some((committed, t)) = self.heard.front() {
+            if committed.as_ref() > applied {
+                break;
+            }
+
+            self.fresh_as_of = Some(*t);
+            self.heard.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::core::read_freshness::ReadFreshness;
+    use crate::engine::testing::UTConfig;
+    use crate::testing::log_id;
+    use crate::type_config::alias::InstantOf;
+    use crate::Instant;
+
+    #[test]
+    fn test_read_freshness() -> anyhow::Result<()> {
+        let t0 = InstantOf::<UTConfig>::now();
+        let t = |ms: u64| t0 + Duration::from_millis(ms);
+
+        let mut rf = ReadFreshness::<UTConfig>::new();
+        assert_eq!(None, rf.fresh_as_of(None));
+
+        // Not applied yet
+        rf.heard_commit(Some(log_id(1, 1, 3)), t(1), Some(&log_id(1, 1, 2)));
+        assert_eq!(None, rf.fresh_as_of(Some(&log_id(1, 1, 2))));
+
+        // Heard the same committed log id again, only the time is updated
+        rf.heard_commit(Some(log_id(1, 1, 3)), t(2), Some(&log_id(1, 1, 2)));
+        rf.heard_commit(Some(log_id(1, 1, 5)), t(3), Some(&log_id(1, 1, 2)));
+        assert_eq!(2, rf.heard.len());
+
+        assert_eq!(Some(&(Some(log_id(1, 1, 5)), t(3))), rf.last_heard());
+
+        // Applied up to 3, fresh as of the last time committed log id 3 is heard
+        assert_eq!(Some(t(2)), rf.fresh_as_of(Some(&log_id(1, 1, 4))));
+
+        // Applied up to 5
+        assert_eq!(Some(t(3)), rf.fresh_as_of(Some(&log_id(1, 1, 5))));
+        assert!(rf.heard.is_empty());
+
+        // A heartbeat with an applied committed log id refreshes at once
+        rf.heard_commit(Some(log_id(1, 1, 5)), t(4), Some(&log_id(1, 1, 5)));
+        assert_eq!(Some(t(4)), rf.fresh_as_of(Some(&log_id(1, 1, 5))));
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use std::any::Any;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 
@@ -6,9 +7,27 @@ use crate::display_ext::DisplaySlice;
 use crate::error::Infallible;
 use crate::log_id::RaftLogId;
 use crate::type_config::alias::SnapshotDataOf;
+use crate::BoxFuture;
+use crate::OptionalSend;
 use crate::RaftTypeConfig;
 use crate::Snapshot;
 
+/// A type erased [`SmFunc`], because [`Command`] is not generic over the state machine type.
+#[cfg(not(feature = "singlethreaded"))]
+pub(crate) type BoxAny = Box<dyn Any + Send>;
+
+/// A type erased [`SmFunc`], because [`Command`] is not generic over the state machine type.
+#[cfg(feature = "singlethreaded")]
+pub(crate) type BoxAny = Box<dyn Any>;
+
+/// A function to run against the state machine in the state machine worker.
+#[cfg(not(feature = "singlethreaded"))]
+pub(crate) type SmFunc<SM> = Box<dyn for<'a> FnOnce(&'a mut SM) -> BoxFuture<'a, ()> + Send>;
+
+/// A function to run against the state machine in the state machine worker.
+#[cfg(feature = "singlethreaded")]
+pub(crate) type SmFunc<SM> = Box<dyn for<'a> FnOnce(&'a mut SM) -> BoxFuture<'a, ()>>;
+
 #[derive(PartialEq)]
 pub(crate) struct Command<C>
 where C: RaftTypeConfig
@@ -49,6 +68,18 @@ where C: RaftTypeConfig
         self.seq = seq;
     }
 
+    /// Return whether the command writes to the state machine or the snapshot storage.
+    pub(crate) fn writes_storage(&self) -> bool {
+        match &self.payload {
+            CommandPayload::BuildSnapshot => true,
+            CommandPayload::GetSnapshot { .. } => false,
+            CommandPayload::BeginReceivingSnapshot { .. } => true,
+            CommandPayload::InstallFullSnapshot { .. } => true,
+            CommandPayload::Apply { .. } => true,
+            CommandPayload::Func { .. } => false,
+        }
+    }
+
     pub(crate) fn build_snapshot() -> Self {
         let payload = CommandPayload::BuildSnapshot;
         Command::new(payload)
@@ -73,6 +104,19 @@ where C: RaftTypeConfig
         let payload = CommandPayload::Apply { entries };
         Command::new(payload)
     }
+
+    pub(crate) fn func<SM, F>(func: F) -> Self
+    where
+        SM: 'static,
+        F: for<'a> FnOnce(&'a mut SM) -> BoxFuture<'a, ()> + OptionalSend + 'static,
+    {
+        let func: SmFunc<SM> = Box::new(func);
+        let payload = CommandPayload::Func {
+            func: Box::new(func),
+            input_sm_type: std::any::type_name::<SM>(),
+        };
+        Command::new(payload)
+    }
 }
 
 // TODO: move to other mod, it is shared by log, sm and replication
@@ -106,6 +150,15 @@ where C: RaftTypeConfig
     Apply {
         entries: Vec<C::Entry>,
     },
+
+    /// Run a user defined function against the state machine, see
+    /// [`Raft::with_state_machine()`](crate::Raft::with_state_machine).
+    ///
+    /// `func` is a [`SmFunc`] of the state machine type named `input_sm_type`.
+    Func {
+        func: BoxAny,
+        input_sm_type: &'static str,
+    },
 }
 
 impl<C> Debug for CommandPayload<C>
@@ -122,6 +175,7 @@ where C: RaftTypeConfig
                 write!(f, "BeginReceivingSnapshot")
             }
             CommandPayload::Apply { entries } => write!(f, "Apply: {}", DisplaySlice::<_>(entries)),
+            CommandPayload::Func { input_sm_type, .. } => write!(f, "Func: state machine: {}", input_sm_type),
         }
     }
 }
@@ -3,6 +3,7 @@ use tokio::sync::mpsc;
 use crate::async_runtime::AsyncOneshotSendExt;
 use crate::core::notify::Notify;
 use crate::core::raft_msg::ResultSender;
+use crate::core::sm::command::SmFunc;
 use crate::core::sm::handle::Handle;
 use crate::core::sm::Command;
 use crate::core::sm::CommandPayload;
@@ -14,14 +15,24 @@ use crate::core::ApplyingEntry;
 use crate::display_ext::DisplayOptionExt;
 use crate::entry::RaftPayload;
 use crate::storage::RaftStateMachine;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::JoinHandleOf;
 use crate::AsyncRuntime;
+use crate::ErrorSubject;
+use crate::Instant;
+use crate::LogId;
+use crate::LogIdOptionExt;
 use crate::RaftLogId;
 use crate::RaftSnapshotBuilder;
 use crate::RaftTypeConfig;
 use crate::Snapshot;
 use crate::StorageError;
 
+/// Runs state machine commands in a task separate from `RaftCore`.
+///
+/// Commands are executed one by one in the order they are sent, so that committed entries are
+/// applied in log order and exactly once; the result of every command is sent back to `RaftCore`
+/// with the command sequence number.
 pub(crate) struct Worker<C, SM>
 where
     C: RaftTypeConfig,
@@ -32,6 +43,9 @@ where
     cmd_rx: mpsc::UnboundedReceiver<Command<C>>,
 
     resp_tx: mpsc::UnboundedSender<Notify<C>>,
+
+    /// The last log id applied to, or installed by a snapshot into, the state machine.
+    last_applied: Option<LogId<C::NodeId>>,
 }
 
 impl<C, SM> Worker<C, SM>
@@ -40,13 +54,18 @@ where
     SM: RaftStateMachine<C>,
 {
     /// Spawn a new state machine worker, return a controlling handle.
-    pub(crate) fn spawn(state_machine: SM, resp_tx: mpsc::UnboundedSender<Notify<C>>) -> Handle<C> {
+    pub(crate) fn spawn(
+        state_machine: SM,
+        last_applied: Option<LogId<C::NodeId>>,
+        resp_tx: mpsc::UnboundedSender<Notify<C>>,
+    ) -> Handle<C> {
         let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
 
         let worker = Worker {
             state_machine,
             cmd_rx,
             resp_tx,
+            last_applied,
         };
 
         let join_handle = worker.do_spawn();
@@ -103,6 +122,7 @@ where
 
                     let meta = snapshot.meta.clone();
                     self.state_machine.install_snapshot(&meta, snapshot.snapshot).await?;
+                    self.last_applied = meta.last_log_id;
 
                     tracing::info!("Done install complete snapshot, meta: {}", meta);
 
@@ -118,10 +138,43 @@ where
                     // No response to RaftCore
                 }
                 CommandPayload::Apply { entries } => {
-                    let resp = self.apply(entries).await?;
-                    let res = CommandResult::new(cmd.seq, Ok(Response::Apply(resp)));
+                    let start = InstantOf::<C>::now();
+                    let first = entries.first().map(|x| *x.get_log_id()).unwrap();
+
+                    let resp = match self.apply(entries).await {
+                        Ok(x) => x,
+                        Err(err) => {
+                            // Use the log id in the error if the state machine provides one,
+                            // otherwise the first entry of the batch is reported.
+                            let log_id = match err.subject() {
+                                ErrorSubject::Apply(log_id) => *log_id,
+                                _ => first,
+                            };
+                            let _ = self.resp_tx.send(Notify::ApplyFailed {
+                                log_id,
+                                error: err.clone(),
+                            });
+                            return Err(err);
+                        }
+                    };
+                    let res = CommandResult::new(cmd.seq, Ok(Response::Apply(resp))).with_elapsed(start.elapsed());
                     let _ = self.resp_tx.send(Notify::sm(res));
                 }
+                CommandPayload::Func { func, input_sm_type } => {
+                    tracing::debug!("{}: run user defined Func", func_name!());
+
+                    match func.downcast::<SmFunc<SM>>() {
+                        Ok(f) => (*f)(&mut self.state_machine).await,
+                        Err(_) => {
+                            tracing::warn!(
+                                expect = std::any::type_name::<SM>(),
+                                got = input_sm_type,
+                                "state machine type mismatch, Func is dropped"
+                            );
+                        }
+                    }
+                    // No response to RaftCore
+                }
             };
         }
     }
@@ -144,6 +197,8 @@ where
 
         let n_entries = applying_entries.len();
 
+        self.verify_apply_order(&entries);
+
         let apply_results = self.state_machine.apply(entries).await?;
 
         let n_replies = apply_results.len();
@@ -165,6 +220,26 @@ where
         Ok(resp)
     }
 
+    /// Track the last applied log id, and with feature `verify-apply-order`, assert that entries
+    /// are applied one by one, without gaps or repeats.
+    fn verify_apply_order(&mut self, entries: &[C::Entry]) {
+        for entry in entries {
+            let log_id = *entry.get_log_id();
+
+            if cfg!(feature = "verify-apply-order") {
+                assert_eq!(
+                    self.last_applied.next_index(),
+                    log_id.index,
+                    "entries must be applied in order: last applied: {}, applying: {}",
+                    self.last_applied.display(),
+                    log_id
+                );
+            }
+
+            self.last_applied = Some(log_id);
+        }
+    }
+
     /// Build a snapshot from the state machine.
     ///
     /// Building snapshot is a read-only operation, so it can be run in another task in parallel.
@@ -184,9 +259,10 @@ where
         let mut builder = self.state_machine.get_snapshot_builder().await;
 
         let _handle = C::AsyncRuntime::spawn(async move {
+            let start = InstantOf::<C>::now();
             let res = builder.build_snapshot().await;
             let res = res.map(|snap| Response::BuildSnapshot(snap.meta));
-            let cmd_res = CommandResult::new(seq, res);
+            let cmd_res = CommandResult::new(seq, res).with_elapsed(start.elapsed());
             let _ = resp_tx.send(Notify::sm(cmd_res));
         });
         tracing::info!("{} returning; spawned building snapshot task", func_name!());
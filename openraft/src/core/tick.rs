@@ -126,6 +126,10 @@ where C: RaftTypeConfig
         self.enabled.store(enabled, Ordering::Relaxed);
     }
 
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
     /// Signal the tick loop to stop. And return a JoinHandle to wait for the loop to stop.
     ///
     /// If it is called twice, the second call will return None.
@@ -158,6 +162,7 @@ mod tests {
 
     use tokio::time::Duration;
 
+    use crate::core::notify::Notify;
     use crate::core::Tick;
     use crate::type_config::alias::AsyncRuntimeOf;
     use crate::AsyncRuntime;
@@ -204,4 +209,33 @@ mod tests {
 
         Ok(())
     }
+
+    /// Tick reads time only via `AsyncRuntime`, thus with a paused tokio clock, the number of ticks
+    /// is exactly determined by how far the time is advanced.
+    #[cfg(not(feature = "singlethreaded"))]
+    #[tokio::test(start_paused = true)]
+    async fn test_tick_with_paused_clock() -> anyhow::Result<()> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let th = Tick::<TickUTConfig>::spawn(Duration::from_millis(100), tx, true);
+
+        // The paused clock auto-advances to every pending tick before waking up this sleep.
+        AsyncRuntimeOf::<TickUTConfig>::sleep(Duration::from_millis(550)).await;
+        let _ = th.shutdown().unwrap().await;
+
+        let mut received = vec![];
+        while let Some(x) = rx.recv().await {
+            received.push(x);
+        }
+
+        let ticks = received
+            .iter()
+            .map(|x| match x {
+                Notify::Tick { i } => *i,
+                _ => unreachable!("only Tick is expected"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 3, 4, 5], ticks);
+
+        Ok(())
+    }
 }
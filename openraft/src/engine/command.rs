@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use crate::async_runtime::AsyncOneshotSendExt;
 use crate::core::sm;
 use crate::engine::CommandKind;
+use crate::error::Fatal;
 use crate::error::Infallible;
 use crate::error::InitializeError;
 use crate::error::InstallSnapshotError;
@@ -75,6 +76,20 @@ where C: RaftTypeConfig
         targets: Vec<(C::NodeId, ProgressEntry<C::NodeId>)>,
     },
 
+    /// Tell a node that is removed from membership that it is no longer a member, by sending it
+    /// the membership log entry that removes it.
+    ///
+    /// It is a best-effort, one-shot AppendEntries request; the result is ignored.
+    NotifyRemoved {
+        target: C::NodeId,
+        /// The node info of `target` in the membership before it is removed.
+        node: C::Node,
+        vote: Vote<C::NodeId>,
+        prev_log_id: Option<LogId<C::NodeId>>,
+        membership_entry: C::Entry,
+        leader_commit: Option<LogId<C::NodeId>>,
+    },
+
     /// Save vote to storage
     SaveVote { vote: Vote<C::NodeId> },
 
@@ -127,6 +142,9 @@ where
             (Command::Commit { seq, already_committed, upto, }, Command::Commit { seq: b_seq, already_committed: b_committed, upto: b_upto, }, ) => seq == b_seq && already_committed == b_committed && upto == b_upto,
             (Command::Replicate { target, req },               Command::Replicate { target: b_target, req: other_req, }, )                     => target == b_target && req == other_req,
             (Command::RebuildReplicationStreams { targets },   Command::RebuildReplicationStreams { targets: b }, )                            => targets == b,
+            (Command::NotifyRemoved { target, node, vote, prev_log_id, membership_entry, leader_commit },
+             Command::NotifyRemoved { target: b_target, node: b_node, vote: b_vote, prev_log_id: b_prev, membership_entry: b_entry, leader_commit: b_commit }, )
+                                                                                                                                               => target == b_target && node == b_node && vote == b_vote && prev_log_id == b_prev && membership_entry == b_entry && leader_commit == b_commit,
             (Command::SaveVote { vote },                       Command::SaveVote { vote: b })                                                  => vote == b,
             (Command::SendVote { vote_req },                   Command::SendVote { vote_req: b }, )                                            => vote_req == b,
             (Command::PurgeLog { upto },                       Command::PurgeLog { upto: b })                                                  => upto == b,
@@ -159,6 +177,7 @@ where C: RaftTypeConfig
             Command::ReplicateCommitted { .. }        => CommandKind::Network,
             Command::Replicate { .. }                 => CommandKind::Network,
             Command::SendVote { .. }                  => CommandKind::Network,
+            Command::NotifyRemoved { .. }             => CommandKind::Network,
 
             Command::StateMachine { .. }              => CommandKind::StateMachine,
             // Apply is firstly handled by RaftCore, then forwarded to state machine worker.
@@ -183,6 +202,7 @@ where C: RaftTypeConfig
             Command::RebuildReplicationStreams { .. } => None,
             Command::SaveVote { .. }                  => None,
             Command::SendVote { .. }                  => None,
+            Command::NotifyRemoved { .. }             => None,
             Command::PurgeLog { .. }                  => None,
             Command::DeleteConflictLog { .. }         => None,
             Command::Respond { when, .. }             => when.as_ref(),
@@ -190,6 +210,29 @@ where C: RaftTypeConfig
             Command::StateMachine { .. }              => None,
         }
     }
+
+    /// Return whether the command writes to the log store or the state machine.
+    #[rustfmt::skip]
+    pub(crate) fn writes_storage(&self) -> bool {
+        match self {
+            Command::BecomeLeader                     => false,
+            Command::QuitLeader                       => false,
+            Command::AppendEntry { .. }               => true,
+            Command::AppendInputEntries { .. }        => true,
+            Command::ReplicateCommitted { .. }        => false,
+            // Commit saves the committed log id and applies logs to the state machine.
+            Command::Commit { .. }                    => true,
+            Command::Replicate { .. }                 => false,
+            Command::RebuildReplicationStreams { .. } => false,
+            Command::SaveVote { .. }                  => true,
+            Command::SendVote { .. }                  => false,
+            Command::NotifyRemoved { .. }             => false,
+            Command::PurgeLog { .. }                  => true,
+            Command::DeleteConflictLog { .. }         => true,
+            Command::Respond { .. }                   => false,
+            Command::StateMachine { command }         => command.writes_storage(),
+        }
+    }
 }
 
 /// A condition to wait for before running a command.
@@ -223,8 +266,8 @@ where NID: NodeId
 pub(crate) enum Respond<C>
 where C: RaftTypeConfig
 {
-    Vote(ValueSender<C, Result<VoteResponse<C>, Infallible>>),
-    AppendEntries(ValueSender<C, Result<AppendEntriesResponse<C>, Infallible>>),
+    Vote(ValueSender<C, Result<VoteResponse<C>, Fatal<C>>>),
+    AppendEntries(ValueSender<C, Result<AppendEntriesResponse<C>, Fatal<C>>>),
     ReceiveSnapshotChunk(ValueSender<C, Result<(), InstallSnapshotError>>),
     InstallSnapshot(ValueSender<C, Result<InstallSnapshotResponse<C>, InstallSnapshotError>>),
     InstallFullSnapshot(ValueSender<C, Result<SnapshotResponse<C>, Infallible>>),
@@ -1,14 +1,15 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::time::Duration;
 
 use crate::engine::time_state;
-use crate::type_config::alias::AsyncRuntimeOf;
+use crate::raft::quorum_policy::QuorumPolicyRef;
 use crate::Config;
 use crate::RaftTypeConfig;
 use crate::SnapshotPolicy;
 
 /// Config for Engine
 #[derive(Clone, Debug)]
-#[derive(PartialEq, Eq)]
 pub(crate) struct EngineConfig<C: RaftTypeConfig> {
     /// The id of this node.
     pub(crate) id: C::NodeId,
@@ -19,30 +20,74 @@ pub(crate) struct EngineConfig<C: RaftTypeConfig> {
     /// The maximum number of applied logs to keep before purging.
     pub(crate) max_in_snapshot_log_to_keep: u64,
 
+    /// Whether to purge logs by policy after building a snapshot.
+    pub(crate) purge_after_snapshot: bool,
+
+    /// The maximum number of terms to advance by elections beyond the greatest term seen from
+    /// other nodes. `0` means no limit.
+    pub(crate) max_term_jump: u64,
+
     /// The minimal number of applied logs to purge in a batch.
     pub(crate) purge_batch_size: u64,
 
     /// The maximum number of entries per payload allowed to be transmitted during replication
     pub(crate) max_payload_entries: u64,
 
+    /// Whether to send the membership log to a node that is removed by it.
+    pub(crate) notify_removed_nodes: bool,
+
+    /// The maximum number of targets a leader sends snapshot to at the same time. `0` means no
+    /// limit.
+    pub(crate) max_concurrent_snapshot_transfers: u64,
+
+    /// Learners that serve reads and are replicated with a higher priority than other learners.
+    ///
+    /// See [`Raft::set_read_replica()`](crate::Raft::set_read_replica).
+    pub(crate) read_replicas: BTreeSet<C::NodeId>,
+
+    /// Priority of replication targets: a target with a higher priority is sent to first. A
+    /// target not in it has priority `0`.
+    ///
+    /// See [`Raft::set_replication_priority()`](crate::Raft::set_replication_priority).
+    pub(crate) replication_priorities: BTreeMap<C::NodeId, u64>,
+
+    /// The policy to decide the committed log id, [`MajorityQuorumPolicy`] by default.
+    ///
+    /// See [`Raft::set_quorum_policy()`](crate::Raft::set_quorum_policy).
+    ///
+    /// [`MajorityQuorumPolicy`]: crate::raft::MajorityQuorumPolicy
+    pub(crate) quorum_policy: QuorumPolicyRef<C>,
+
     pub(crate) timer_config: time_state::Config,
 }
 
 impl<C> EngineConfig<C>
 where C: RaftTypeConfig
 {
-    pub(crate) fn new(id: C::NodeId, config: &Config) -> Self {
-        let election_timeout = Duration::from_millis(config.new_rand_election_timeout::<AsyncRuntimeOf<C>>());
+    /// Create with the first election timeout of this node, see [`ElectionTimeoutRng`].
+    ///
+    /// [`ElectionTimeoutRng`]: crate::core::election_timeout_rng::ElectionTimeoutRng
+    pub(crate) fn new(id: C::NodeId, config: &Config, election_timeout: Duration) -> Self {
         Self {
             id,
             snapshot_policy: config.snapshot_policy.clone(),
             max_in_snapshot_log_to_keep: config.max_in_snapshot_log_to_keep,
+            purge_after_snapshot: config.purge_after_snapshot,
+            max_term_jump: config.max_term_jump,
             purge_batch_size: config.purge_batch_size,
             max_payload_entries: config.max_payload_entries,
+            notify_removed_nodes: config.notify_removed_nodes,
+            max_concurrent_snapshot_transfers: config.max_concurrent_snapshot_transfers,
+            read_replicas: BTreeSet::new(),
+            replication_priorities: BTreeMap::new(),
+            quorum_policy: QuorumPolicyRef::default(),
             timer_config: time_state::Config {
                 election_timeout,
                 smaller_log_timeout: Duration::from_millis(config.election_timeout_max * 2),
                 leader_lease: Duration::from_millis(config.election_timeout_max),
+                leader_affinity_window: Duration::from_millis(config.leader_affinity_window),
+                affinity_election_timeout: Duration::from_millis(config.election_timeout_min / 2),
+                election_timeout_per_voter: Duration::from_millis(config.election_timeout_per_voter),
             },
         }
     }
@@ -53,9 +98,32 @@ where C: RaftTypeConfig
             id,
             snapshot_policy: SnapshotPolicy::LogsSinceLast(5000),
             max_in_snapshot_log_to_keep: 1000,
+            purge_after_snapshot: true,
+            max_term_jump: 0,
             purge_batch_size: 256,
             max_payload_entries: 300,
+            notify_removed_nodes: false,
+            max_concurrent_snapshot_transfers: 0,
+            read_replicas: BTreeSet::new(),
+            replication_priorities: BTreeMap::new(),
+            quorum_policy: QuorumPolicyRef::default(),
             timer_config: time_state::Config::default(),
         }
     }
+
+    /// The replication priority of `target`, `0` if it is not set.
+    pub(crate) fn replication_priority(&self, target: &C::NodeId) -> u64 {
+        self.replication_priorities.get(target).copied().unwrap_or_default()
+    }
+
+    /// The maximum number of entries to send to `target` in one AppendEntries request.
+    ///
+    /// A read replica is allowed twice as many entries so that it keeps up with the leader.
+    pub(crate) fn max_payload_entries_for(&self, target: &C::NodeId) -> u64 {
+        if self.read_replicas.contains(target) {
+            self.max_payload_entries * 2
+        } else {
+            self.max_payload_entries
+        }
+    }
 }
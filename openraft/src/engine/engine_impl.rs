@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use validit::Valid;
@@ -29,6 +30,9 @@ use crate::error::NotInMembers;
 use crate::error::RejectAppendEntries;
 use crate::internal_server_state::InternalServerState;
 use crate::membership::EffectiveMembership;
+use crate::metrics::ElectionRound;
+use crate::metrics::ElectionRoundOutcome;
+use crate::metrics::VoteReply;
 use crate::raft::responder::Responder;
 use crate::raft::AppendEntriesResponse;
 use crate::raft::SnapshotResponse;
@@ -39,6 +43,7 @@ use crate::raft_state::RaftState;
 use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::ResponderOf;
 use crate::type_config::alias::SnapshotDataOf;
+use crate::ElectionOutcome;
 use crate::Instant;
 use crate::LogId;
 use crate::LogIdOptionExt;
@@ -74,6 +79,26 @@ where C: RaftTypeConfig
     /// should be greater.
     pub(crate) seen_greater_log: bool,
 
+    /// The outcome of the last election started by this node, for diagnostics.
+    pub(crate) last_election: Option<ElectionOutcome<C>>,
+
+    /// The election round started by this node that is not yet finished.
+    ///
+    /// It is pushed to the output when it is won or lost, see [`RaftEvent::ElectionRoundFinished`].
+    ///
+    /// [`RaftEvent::ElectionRoundFinished`]: crate::metrics::RaftEvent::ElectionRoundFinished
+    pub(crate) election_round: Option<ElectionRound<C>>,
+
+    /// The number of AppendEntries, Vote and InstallSnapshot requests received with a term lower
+    /// than the current term of this node.
+    pub(crate) stale_term_requests: u64,
+
+    /// The greatest term seen from other nodes, or the term this node started with.
+    ///
+    /// With [`EngineConfig::max_term_jump`], an election does not raise the term beyond it by more
+    /// than that.
+    pub(crate) confirmed_term: u64,
+
     /// The internal server state used by Engine.
     pub(crate) internal_server_state: InternalServerState<C>,
 
@@ -85,10 +110,15 @@ impl<C> Engine<C>
 where C: RaftTypeConfig
 {
     pub(crate) fn new(init_state: RaftState<C>, config: EngineConfig<C>) -> Self {
+        let confirmed_term = init_state.vote_ref().leader_id().term;
         Self {
             config,
             state: Valid::new(init_state),
             seen_greater_log: false,
+            last_election: None,
+            election_round: None,
+            stale_term_requests: 0,
+            confirmed_term,
             internal_server_state: InternalServerState::default(),
             output: EngineOutput::new(4096),
         }
@@ -159,6 +189,10 @@ where C: RaftTypeConfig
     pub(crate) fn initialize(&mut self, mut entry: C::Entry) -> Result<(), InitializeError<C>> {
         self.check_initialize()?;
 
+        if let Some(m) = entry.get_membership() {
+            m.ensure_consistent()?;
+        }
+
         self.state.assign_log_ids([&mut entry]);
         let log_id = *entry.get_log_id();
         self.state.extend_log_ids_from_same_leader(&[log_id]);
@@ -184,12 +218,53 @@ where C: RaftTypeConfig
     /// Start to elect this node as leader
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn elect(&mut self) {
-        let v = Vote::new(self.state.vote_ref().leader_id().term + 1, self.config.id);
+        let term = self.state.vote_ref().leader_id().term;
+        let max_jump = self.config.max_term_jump;
+
+        let v = if max_jump > 0 && term + 1 > self.confirmed_term + max_jump {
+            let vote = *self.state.vote_ref();
+
+            if vote.leader_id().voted_for() != Some(self.config.id) || vote.is_committed() {
+                tracing::info!(
+                    vote = display(&vote),
+                    confirmed_term = display(self.confirmed_term),
+                    max_term_jump = display(max_jump),
+                    "term can not be increased and it has voted for another node, skip election"
+                );
+                return;
+            }
+
+            tracing::info!(
+                vote = display(&vote),
+                confirmed_term = display(self.confirmed_term),
+                max_term_jump = display(max_jump),
+                "term can not be increased, re-elect with the current vote"
+            );
+            vote
+        } else {
+            Vote::new(term + 1, self.config.id)
+        };
+
         tracing::info!(vote = display(&v), "{}", func_name!());
 
+        // The previous round, if not yet finished, timed out.
+        self.finish_election_round(ElectionRoundOutcome::Lost);
+
         // Safe unwrap(): it won't reject itself ˙–˙
         self.vote_handler().update_vote(&v).unwrap();
 
+        self.last_election = Some(ElectionOutcome::InProgress { vote: v });
+
+        let requested =
+            self.state.membership_state.effective().voter_ids().filter(|id| *id != self.config.id).collect();
+        self.election_round = Some(ElectionRound {
+            vote: v,
+            last_log_id: self.state.last_log_id().copied(),
+            requested,
+            replies: BTreeMap::new(),
+            outcome: ElectionRoundOutcome::Lost,
+        });
+
         // TODO: simplify voting initialization.
         //       - update_vote() should be moved to after initialize_voting(), because it can be considered
         //         as a local RPC
@@ -251,6 +326,9 @@ where C: RaftTypeConfig
         let vote_utime = self.state.vote_last_modified().unwrap_or_else(|| now - lease - Duration::from_millis(1));
 
         tracing::info!(req = display(&req), "Engine::handle_vote_req");
+
+        self.count_stale_term(&req.vote);
+        self.see_term(&req.vote);
         tracing::info!(
             my_vote = display(self.state.vote_ref()),
             my_last_log_id = display(self.state.last_log_id().display()),
@@ -333,6 +411,8 @@ where C: RaftTypeConfig
             func_name!()
         );
 
+        self.see_term(&resp.vote);
+
         let voting = if let Some(voting) = self.internal_server_state.voting_mut() {
             // TODO check the sending vote matches current vote
             voting
@@ -342,6 +422,14 @@ where C: RaftTypeConfig
             return;
         };
 
+        if let Some(round) = &mut self.election_round {
+            round.replies.insert(target, VoteReply {
+                granted: resp.vote_granted,
+                vote: resp.vote,
+                last_log_id: resp.last_log_id,
+            });
+        }
+
         if &resp.vote < self.state.vote_ref() {
             debug_assert!(!resp.vote_granted);
         }
@@ -359,6 +447,13 @@ where C: RaftTypeConfig
 
         debug_assert!(self.state.membership_state.effective().is_voter(&self.config.id));
 
+        self.last_election = Some(ElectionOutcome::Rejected {
+            vote: *self.state.vote_ref(),
+            by: target,
+            peer_vote: resp.vote,
+            peer_last_log_id: resp.last_log_id,
+        });
+
         // If peer's vote is greater than current vote, revert to follower state.
         //
         // Explicitly ignore the returned error:
@@ -416,11 +511,15 @@ where C: RaftTypeConfig
         prev_log_id: Option<LogId<C::NodeId>>,
         entries: Vec<C::Entry>,
     ) -> Result<(), RejectAppendEntries<C>> {
+        self.count_stale_term(vote);
+        self.see_term(vote);
         self.vote_handler().update_vote(vote)?;
 
         // Vote is legal.
 
         let mut fh = self.following_handler();
+        fh.ensure_term_monotonic(prev_log_id, &entries)?;
+        fh.ensure_no_log_gap(prev_log_id, &entries)?;
         fh.ensure_log_consecutive(prev_log_id)?;
         fh.append_entries(prev_log_id, entries);
 
@@ -452,6 +551,9 @@ where C: RaftTypeConfig
     ) {
         tracing::info!(vote = display(vote), snapshot = display(&snapshot), "{}", func_name!());
 
+        self.count_stale_term(&vote);
+        self.see_term(&vote);
+
         let vote_res = self.vote_handler().accept_vote(&vote, tx, |state, _rejected| {
             Ok(SnapshotResponse::new(*state.vote_ref()))
         });
@@ -482,6 +584,24 @@ where C: RaftTypeConfig
         self.output.push_command(Command::from(sm::Command::begin_receiving_snapshot(tx)));
     }
 
+    /// Count a request whose term is lower than the current term, which is sent by a stale
+    /// leader or candidate and will be rejected.
+    fn count_stale_term(&mut self, vote: &Vote<C::NodeId>) {
+        let term = vote.leader_id().term;
+        let my_term = self.state.vote_ref().leader_id().term;
+
+        if term < my_term {
+            tracing::info!(term, my_term, "received a request from a stale term");
+            self.stale_term_requests += 1;
+        }
+    }
+
+    /// Record the term of a vote received from another node, which lifts the limit of
+    /// [`EngineConfig::max_term_jump`].
+    fn see_term(&mut self, vote: &Vote<C::NodeId>) {
+        self.confirmed_term = std::cmp::max(self.confirmed_term, vote.leader_id().term);
+    }
+
     /// Leader steps down(convert to learner) once the membership not containing it is committed.
     ///
     /// This is only called by leader.
@@ -506,6 +626,32 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Leader gives up its leadership and becomes a follower, without leaving the cluster.
+    ///
+    /// The vote is kept so that this node does not vote for another node in the same term. A new
+    /// leader is elected in a greater term by a normal election, after the election timeout.
+    ///
+    /// It returns an error if this node is not a leader.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn leader_yield(&mut self) -> Result<(), ForwardToLeader<C>> {
+        self.leader_handler()?;
+
+        tracing::info!(
+            "leader_yield: node_id:{}, vote: {}",
+            self.config.id,
+            self.state.vote_ref()
+        );
+
+        // Restart the election timer, so that this node waits as long as other nodes do,
+        // before starting the next election.
+        self.state.vote.touch(InstantOf::<C>::now());
+        self.state.yielded = true;
+
+        self.vote_handler().update_internal_server_state();
+
+        Ok(())
+    }
+
     /// Update Engine state when a new snapshot is built.
     ///
     /// NOTE:
@@ -610,6 +756,10 @@ where C: RaftTypeConfig
     fn establish_leader(&mut self) {
         tracing::info!("{}", func_name!());
 
+        // The term is granted by a quorum.
+        let vote = *self.state.vote_ref();
+        self.see_term(&vote);
+
         // Mark the vote as committed, i.e., being granted and saved by a quorum.
         //
         // The committed vote, is not necessary in original raft.
@@ -631,6 +781,9 @@ where C: RaftTypeConfig
 
             let _res = self.vote_handler().update_vote(&vote);
             debug_assert!(_res.is_ok(), "commit vote can not fail but: {:?}", _res);
+
+            self.last_election = Some(ElectionOutcome::Won { vote });
+            self.finish_election_round(ElectionRoundOutcome::Won);
         }
 
         // Update the noop log index
@@ -695,6 +848,28 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Finish the ongoing election round, if there is one, and push it to the output.
+    pub(crate) fn finish_election_round(&mut self, outcome: ElectionRoundOutcome) {
+        if let Some(mut round) = self.election_round.take() {
+            round.outcome = outcome;
+            tracing::info!(round = display(&round), "{}", func_name!());
+            self.output.push_election_round(round);
+        }
+    }
+
+    /// Finish the ongoing election round as lost, if this node gave up its vote, or is no longer
+    /// waiting for it to be granted, e.g., it has seen a greater vote.
+    pub(crate) fn finish_superseded_election_round(&mut self) {
+        let Some(round) = &self.election_round else {
+            return;
+        };
+
+        let voting = self.internal_server_state.leading().and_then(|l| l.voting()).is_some();
+        if !voting || self.state.vote_ref() != &round.vote {
+            self.finish_election_round(ElectionRoundOutcome::Lost);
+        }
+    }
+
     pub(crate) fn is_there_greater_log(&self) -> bool {
         self.seen_greater_log
     }
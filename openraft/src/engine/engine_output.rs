@@ -2,7 +2,9 @@ use std::collections::VecDeque;
 
 use crate::core::sm::CommandSeq;
 use crate::engine::Command;
+use crate::metrics::ElectionRound;
 use crate::RaftTypeConfig;
+use crate::ServerState;
 
 /// The entry of output from Engine to the runtime.
 #[derive(Debug, Default)]
@@ -14,6 +16,12 @@ where C: RaftTypeConfig
 
     /// Command queue that need to be executed by `RaftRuntime`.
     pub(crate) commands: VecDeque<Command<C>>,
+
+    /// Server state transitions `(from, to)` that have not yet been taken by the runtime.
+    pub(crate) server_state_transitions: Vec<(ServerState, ServerState)>,
+
+    /// Finished election rounds that have not yet been taken by the runtime.
+    pub(crate) election_rounds: Vec<ElectionRound<C>>,
 }
 
 impl<C> EngineOutput<C>
@@ -34,6 +42,8 @@ where C: RaftTypeConfig
         Self {
             seq: 0,
             commands: VecDeque::with_capacity(command_buffer_size),
+            server_state_transitions: Vec::new(),
+            election_rounds: Vec::new(),
         }
     }
 
@@ -49,10 +59,16 @@ where C: RaftTypeConfig
             Command::QuitLeader => {}
             Command::AppendEntry { .. } => {}
             Command::AppendInputEntries { .. } => {}
-            Command::ReplicateCommitted { .. } => {}
+            Command::ReplicateCommitted { .. } => {
+                // A queued one that is not yet run is superseded: replication streams only need the
+                // latest committed log id. This avoids sending a committed log id to every target
+                // for each of a burst of commits, e.g., when commands are postponed.
+                self.commands.retain(|c| !matches!(c, Command::ReplicateCommitted { .. }));
+            }
             Command::Commit { .. } => {}
             Command::Replicate { .. } => {}
             Command::RebuildReplicationStreams { .. } => {}
+            Command::NotifyRemoved { .. } => {}
             Command::SaveVote { .. } => {}
             Command::SendVote { .. } => {}
             Command::PurgeLog { .. } => {}
@@ -75,6 +91,26 @@ where C: RaftTypeConfig
         self.commands.pop_front()
     }
 
+    /// Record a server state transition.
+    pub(crate) fn push_server_state_transition(&mut self, from: ServerState, to: ServerState) {
+        self.server_state_transitions.push((from, to))
+    }
+
+    /// Take all recorded server state transitions, in the order they happened.
+    pub(crate) fn take_server_state_transitions(&mut self) -> Vec<(ServerState, ServerState)> {
+        std::mem::take(&mut self.server_state_transitions)
+    }
+
+    /// Record a finished election round.
+    pub(crate) fn push_election_round(&mut self, round: ElectionRound<C>) {
+        self.election_rounds.push(round)
+    }
+
+    /// Take all finished election rounds, in the order they finished.
+    pub(crate) fn take_election_rounds(&mut self) -> Vec<ElectionRound<C>> {
+        std::mem::take(&mut self.election_rounds)
+    }
+
     /// Iterate all queued commands.
     pub(crate) fn iter_commands(&self) -> impl Iterator<Item = &Command<C>> {
         self.commands.iter()
@@ -87,7 +123,6 @@ where C: RaftTypeConfig
     }
 
     /// Clear all queued commands.
-    #[cfg(test)]
     pub(crate) fn clear_commands(&mut self) {
         self.commands.clear()
     }
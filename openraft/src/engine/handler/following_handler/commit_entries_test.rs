@@ -5,6 +5,7 @@ use maplit::btreeset;
 use crate::engine::testing::UTConfig;
 use crate::engine::Command;
 use crate::engine::Engine;
+use crate::engine::LogIdList;
 use crate::raft_state::Accepted;
 use crate::raft_state::LogStateReader;
 use crate::testing::log_id;
@@ -79,6 +80,38 @@ fn test_following_handler_commit_entries_ge_accepted() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_following_handler_commit_entries_in_local_log() -> anyhow::Result<()> {
+    let mut eng = eng();
+    let l = eng.state.vote_ref().leader_id();
+    eng.state.accepted = Accepted::new(*l, Some(log_id(1, 1, 2)));
+    eng.state.log_ids = LogIdList::new(vec![log_id(1, 1, 1), log_id(2, 1, 3)]);
+
+    // Not in local log: committed up to accepted.
+    eng.following_handler().commit_entries(Some(log_id(2, 1, 4)));
+    assert_eq!(Some(&log_id(1, 1, 2)), eng.state.committed());
+
+    // A different log at the same index: not committed.
+    eng.output.take_commands();
+    eng.following_handler().commit_entries(Some(log_id(3, 1, 3)));
+    assert_eq!(Some(&log_id(1, 1, 2)), eng.state.committed());
+    assert_eq!(0, eng.output.take_commands().len());
+
+    // In local log but not yet accepted: committed.
+    eng.following_handler().commit_entries(Some(log_id(2, 1, 3)));
+    assert_eq!(Some(&log_id(2, 1, 3)), eng.state.committed());
+    assert_eq!(
+        vec![Command::Commit {
+            seq: 2,
+            already_committed: Some(log_id(1, 1, 2)),
+            upto: log_id(2, 1, 3),
+        }],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_following_handler_commit_entries_le_accepted() -> anyhow::Result<()> {
     let mut eng = eng();
@@ -133,6 +133,7 @@ fn test_follower_do_append_entries_one_membership_entry() -> anyhow::Result<()>
             blank_ent(3, 1, 4),
             Entry::<UTConfig> {
                 log_id: log_id(3, 1, 5),
+                context: vec![],
                 payload: EntryPayload::<UTConfig>::Membership(m34()),
             },
         ],
@@ -169,6 +170,7 @@ fn test_follower_do_append_entries_one_membership_entry() -> anyhow::Result<()>
                 blank_ent(3, 1, 4),
                 Entry::<UTConfig> {
                     log_id: log_id(3, 1, 5),
+                    context: vec![],
                     payload: EntryPayload::<UTConfig>::Membership(m34()),
                 },
             ]
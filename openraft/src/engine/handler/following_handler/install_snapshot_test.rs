@@ -45,6 +45,7 @@ fn eng() -> Engine<UTConfig> {
         last_log_id: Some(log_id(2, 1, 2)),
         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
         snapshot_id: "1-2-3-4".to_string(),
+        checksum: None,
     };
     eng.state.server_state = eng.calc_server_state();
 
@@ -62,6 +63,7 @@ fn test_install_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
@@ -73,6 +75,7 @@ fn test_install_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         eng.state.snapshot_meta
     );
@@ -94,6 +97,7 @@ fn test_install_snapshot_lt_committed() -> anyhow::Result<()> {
             last_log_id: Some(log_id(4, 1, 5)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
@@ -105,6 +109,7 @@ fn test_install_snapshot_lt_committed() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         eng.state.snapshot_meta
     );
@@ -123,6 +128,7 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(4, 1, 6)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
@@ -134,6 +140,7 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(4, 1, 6)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         eng.state.snapshot_meta
     );
@@ -152,6 +159,7 @@ fn test_install_snapshot_not_conflict() -> anyhow::Result<()> {
                         last_log_id: Some(log_id(4, 1, 6)),
                         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
                         snapshot_id: "1-2-3-4".to_string(),
+                        checksum: None,
                     },
                     snapshot: Box::new(Cursor::new(vec![0u8])),
                 })
@@ -187,6 +195,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         };
 
         eng.state.server_state = eng.calc_server_state();
@@ -199,6 +208,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(5, 1, 6)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
@@ -210,6 +220,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(5, 1, 6)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         eng.state.snapshot_meta
     );
@@ -229,6 +240,7 @@ fn test_install_snapshot_conflict() -> anyhow::Result<()> {
                         last_log_id: Some(log_id(5, 1, 6)),
                         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
                         snapshot_id: "1-2-3-4".to_string(),
+                        checksum: None,
                     },
                     snapshot: Box::new(Cursor::new(vec![0u8])),
                 })
@@ -252,6 +264,7 @@ fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
             last_log_id: Some(log_id(100, 1, 100)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
@@ -263,6 +276,7 @@ fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
             last_log_id: Some(log_id(100, 1, 100)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         eng.state.snapshot_meta
     );
@@ -284,6 +298,7 @@ fn test_install_snapshot_advance_last_log_id() -> anyhow::Result<()> {
                         last_log_id: Some(log_id(100, 1, 100)),
                         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
                         snapshot_id: "1-2-3-4".to_string(),
+                        checksum: None,
                     },
                     snapshot: Box::new(Cursor::new(vec![0u8])),
                 })
@@ -309,6 +324,7 @@ fn test_install_snapshot_update_accepted() -> anyhow::Result<()> {
             last_log_id: Some(log_id(100, 1, 100)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         snapshot: Box::new(Cursor::new(vec![0u8])),
     });
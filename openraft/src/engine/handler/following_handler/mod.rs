@@ -13,6 +13,7 @@ use crate::engine::EngineConfig;
 use crate::engine::EngineOutput;
 use crate::entry::RaftPayload;
 use crate::error::RejectAppendEntries;
+use crate::error::TermRegression;
 use crate::raft_state::LogStateReader;
 use crate::EffectiveMembership;
 use crate::LogId;
@@ -33,6 +34,13 @@ use crate::StoredMembership;
 /// Receive replication request and deal with them.
 ///
 /// It mainly implements the logic of a follower/learner
+///
+/// AppendEntries and snapshot installation never interleave: `RaftCore` handles one request at a
+/// time in a single task, and every log mutation they produce is queued as a [`Command`] in the
+/// order the requests are handled. When a snapshot is installed, all logs it covers are treated as
+/// committed and are purged. An AppendEntries handled afterward, e.g., one sent by the leader
+/// before the snapshot, skips every entry the snapshot covers, and only the entries after the
+/// snapshot are appended. A snapshot that is not newer than the committed log id is ignored.
 pub(crate) struct FollowingHandler<'x, C>
 where C: RaftTypeConfig
 {
@@ -87,6 +95,67 @@ where C: RaftTypeConfig
         self.do_append_entries(entries, since);
     }
 
+    /// Ensures the terms of the log ids to append never decrease, starting from `prev_log_id`.
+    ///
+    /// A leader's log never has a term regression, thus such entries are corrupted and must not be
+    /// stored. It is checked before any change is made to the local log.
+    pub(crate) fn ensure_term_monotonic(
+        &self,
+        prev_log_id: Option<LogId<C::NodeId>>,
+        entries: &[C::Entry],
+    ) -> Result<(), RejectAppendEntries<C>> {
+        let mut prev = prev_log_id;
+
+        for entry in entries {
+            let log_id = *entry.get_log_id();
+
+            if let Some(p) = prev {
+                if log_id.leader_id.term < p.leader_id.term {
+                    tracing::error!(
+                        prev = display(p),
+                        log_id = display(log_id),
+                        "term regression in AppendEntries"
+                    );
+                    return Err(TermRegression { prev: p, log_id }.into());
+                }
+            }
+
+            prev = Some(log_id);
+        }
+
+        Ok(())
+    }
+
+    /// Ensures the entries to append are consecutive, starting right after `prev_log_id`.
+    ///
+    /// Otherwise appending them would leave a gap in the log, and a later entry may be mistaken as
+    /// matching the leader's log, e.g., after the logs below it are purged by a snapshot. It is
+    /// checked before any change is made to the local log.
+    pub(crate) fn ensure_no_log_gap(
+        &self,
+        prev_log_id: Option<LogId<C::NodeId>>,
+        entries: &[C::Entry],
+    ) -> Result<(), RejectAppendEntries<C>> {
+        let mut expect_index = prev_log_id.next_index();
+
+        for entry in entries {
+            let log_id = *entry.get_log_id();
+
+            if log_id.index != expect_index {
+                tracing::error!(
+                    expect_index = display(expect_index),
+                    log_id = display(log_id),
+                    "log gap in AppendEntries"
+                );
+                return Err(RejectAppendEntries::ByLogGap { expect_index, log_id });
+            }
+
+            expect_index += 1;
+        }
+
+        Ok(())
+    }
+
     /// Ensures the log to replicate is consecutive to the local log.
     ///
     /// If not, truncate the local log and return an error.
@@ -140,10 +209,25 @@ where C: RaftTypeConfig
     }
 
     /// Commit entries that are already committed by the leader.
+    ///
+    /// Entries up to the accepted log id are committed. Besides, if the leader's committed log id
+    /// is already in the local log, it is committed too, even if it is not yet accepted from this
+    /// leader, e.g., when a heartbeat carries a `prev_log_id` older than the local log. By the Log
+    /// Matching Property, the local log up to a log id that is the same as the leader's is
+    /// identical to the leader's log, thus it is safe. An entry that is not in the local log is
+    /// never committed.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn commit_entries(&mut self, leader_committed: Option<LogId<C::NodeId>>) {
         let accepted = self.state.accepted().copied();
-        let committed = std::cmp::min(accepted, leader_committed);
+        let mut committed = std::cmp::min(accepted, leader_committed);
+
+        if let Some(leader_committed) = leader_committed {
+            if committed < Some(leader_committed)
+                && self.state.get_log_id(leader_committed.index) == Some(leader_committed)
+            {
+                committed = Some(leader_committed);
+            }
+        }
 
         tracing::debug!(
             leader_committed = display(DisplayOption(&leader_committed)),
@@ -191,6 +191,51 @@ fn test_leader_append_entries_single_node_leader() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// A single-voter leader commits its own logs as soon as they are appended locally, without any
+/// replication stream.
+#[test]
+fn test_leader_append_entries_single_node_leader_commit_at_once() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 3)), m1())));
+    eng.vote_handler().become_leading();
+
+    eng.output.clear_commands();
+
+    eng.leader_handler()?.leader_append_entries(vec![
+        blank_ent(1, 1, 1), //
+        blank_ent(1, 1, 1),
+    ]);
+    assert_eq!(Some(&log_id(0, 1, 0)), eng.state.committed());
+
+    // RaftCore reports the local progress once the entries are written to the log store.
+    eng.leader_handler()?.replication_handler().update_local_progress(Some(log_id(3, 1, 5)));
+
+    assert_eq!(Some(&log_id(3, 1, 5)), eng.state.committed());
+    assert_eq!(
+        vec![
+            Command::AppendInputEntries {
+                entries: vec![
+                    blank_ent(3, 1, 4), //
+                    blank_ent(3, 1, 5),
+                ]
+            },
+            Command::ReplicateCommitted {
+                committed: Some(log_id(3, 1, 5))
+            },
+            Command::Commit {
+                seq: 1,
+                already_committed: Some(log_id(0, 1, 0)),
+                upto: log_id(3, 1, 5)
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_leader_append_entries_with_membership_log() -> anyhow::Result<()> {
     let mut eng = eng();
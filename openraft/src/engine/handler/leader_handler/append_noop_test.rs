@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+#[allow(unused_imports)] use pretty_assertions::assert_eq;
+#[allow(unused_imports)] use pretty_assertions::assert_ne;
+#[allow(unused_imports)] use pretty_assertions::assert_str_eq;
+
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::entry::RaftEntry;
+use crate::raft_state::LogStateReader;
+use crate::testing::log_id;
+use crate::utime::UTime;
+use crate::EffectiveMembership;
+use crate::Entry;
+use crate::Membership;
+use crate::MembershipState;
+use crate::TokioInstant;
+use crate::Vote;
+
+fn m01() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {0,1}], None)
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.state.committed = Some(log_id(0, 1, 0));
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(3, 1));
+    eng.state.log_ids.append(log_id(1, 1, 1));
+    eng.state.log_ids.append(log_id(2, 1, 3));
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m01())),
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m01())),
+    );
+    eng.state.server_state = eng.calc_server_state();
+
+    eng
+}
+
+#[test]
+fn test_leader_append_noop() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.vote_handler().become_leading();
+    eng.output.clear_commands();
+
+    eng.leader_handler()?.append_noop();
+
+    assert_eq!(Some(&log_id(3, 1, 4)), eng.state.last_log_id());
+
+    let commands = eng.output.take_commands();
+    assert_eq!(
+        Command::AppendEntry {
+            entry: Entry::<UTConfig>::new_blank(log_id(3, 1, 4))
+        },
+        commands[0]
+    );
+    assert!(
+        commands.iter().any(|c| matches!(c, Command::Replicate { target: 0, .. })),
+        "the blank entry is replicated, got: {:?}",
+        commands
+    );
+
+    Ok(())
+}
@@ -13,6 +13,7 @@ use crate::RaftState;
 use crate::RaftTypeConfig;
 
 #[cfg(test)] mod append_entries_test;
+#[cfg(test)] mod append_noop_test;
 #[cfg(test)] mod get_read_log_id_test;
 #[cfg(test)] mod send_heartbeat_test;
 
@@ -77,6 +78,18 @@ where C: RaftTypeConfig
         rh.initiate_replication(SendNone::False);
     }
 
+    /// Append a blank entry and replicate it, so that entries before it can be committed.
+    ///
+    /// It is used by a leader whose log has been idle for [`Config::idle_noop_interval`].
+    ///
+    /// [`Config::idle_noop_interval`]: crate::Config::idle_noop_interval
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn append_noop(&mut self) {
+        let mut rh = self.replication_handler();
+        rh.append_blank_log();
+        rh.initiate_replication(SendNone::False);
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn send_heartbeat(&mut self) -> () {
         let mut rh = self.replication_handler();
@@ -50,8 +50,15 @@ where C: RaftTypeConfig
     ///
     /// This method is called after building a snapshot, because openraft only purge logs that are
     /// already included in snapshot.
+    ///
+    /// Nothing is scheduled if `purge_after_snapshot` is disabled.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn schedule_policy_based_purge(&mut self) {
+        if !self.config.purge_after_snapshot {
+            tracing::debug!("purge_after_snapshot is disabled, keep logs");
+            return;
+        }
+
         if let Some(purge_upto) = self.calc_purge_upto() {
             self.update_purge_upto(purge_upto);
         }
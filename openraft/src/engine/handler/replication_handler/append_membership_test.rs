@@ -7,6 +7,7 @@ use crate::engine::testing::UTConfig;
 use crate::engine::Command;
 use crate::engine::Engine;
 use crate::engine::LogIdList;
+use crate::entry::RaftEntry;
 use crate::progress::entry::ProgressEntry;
 use crate::progress::Inflight;
 use crate::progress::Progress;
@@ -14,6 +15,7 @@ use crate::testing::log_id;
 use crate::utime::UTime;
 use crate::CommittedLeaderId;
 use crate::EffectiveMembership;
+use crate::Entry;
 use crate::LogId;
 use crate::Membership;
 use crate::MembershipState;
@@ -24,6 +26,10 @@ fn m01() -> Membership<UTConfig> {
     Membership::<UTConfig>::new(vec![btreeset! {0,1}], None)
 }
 
+fn m2() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {2}], None)
+}
+
 fn m23() -> Membership<UTConfig> {
     Membership::<UTConfig>::new(vec![btreeset! {2,3}], None)
 }
@@ -181,3 +187,36 @@ fn test_leader_append_membership_update_learner_process() -> anyhow::Result<()>
 
     Ok(())
 }
+
+#[test]
+fn test_leader_append_membership_notify_removed_nodes() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.notify_removed_nodes = true;
+    eng.state.log_ids = LogIdList::new([log_id(1, 1, 1), log_id(2, 1, 3), log_id(3, 1, 4)]);
+
+    eng.state.server_state = ServerState::Leader;
+    // Make it a real leader: voted for itself and vote is committed.
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 2));
+    eng.vote_handler().become_leading();
+
+    // Remove node-3 from {2,3}
+    eng.replication_handler().append_membership(&log_id(3, 1, 4), &m2());
+
+    assert_eq!(
+        vec![
+            //
+            Command::RebuildReplicationStreams { targets: vec![] },
+            Command::NotifyRemoved {
+                target: 3,
+                node: (),
+                vote: Vote::new_committed(2, 2),
+                prev_log_id: Some(log_id(2, 1, 3)),
+                membership_entry: Entry::new_membership(log_id(3, 1, 4), m2()),
+                leader_commit: None,
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
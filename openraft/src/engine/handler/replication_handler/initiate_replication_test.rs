@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+
+use crate::core::ServerState;
+use crate::engine::handler::replication_handler::SendNone;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::progress::entry::ProgressEntry;
+use crate::progress::Inflight;
+use crate::progress::Progress;
+use crate::testing::log_id;
+use crate::utime::UTime;
+use crate::CommittedLeaderId;
+use crate::EffectiveMembership;
+use crate::LogId;
+use crate::Membership;
+use crate::TokioInstant;
+use crate::Vote;
+
+fn m23_45() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {2,3}], Some(btreeset! {4,5}))
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.config.id = 2;
+    eng.config.max_payload_entries = 2;
+    eng.state.log_ids = LogIdList::new([
+        LogId::new(CommittedLeaderId::new(0, 0), 0),
+        log_id(1, 1, 1),
+        log_id(5, 1, 10),
+    ]);
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 3)), m23_45())));
+
+    eng.state.server_state = ServerState::Leader;
+    // Make it a real leader: voted for itself and vote is committed.
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 2));
+    eng.vote_handler().become_leading();
+
+    if let Some(l) = eng.internal_server_state.leading_mut() {
+        for id in [3, 4, 5] {
+            let _ = l.progress.update(&id, ProgressEntry::new(Some(log_id(1, 1, 4))));
+        }
+    }
+
+    eng.output.clear_commands();
+    eng
+}
+
+#[test]
+fn test_initiate_replication_without_read_replica() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.replication_handler().initiate_replication(SendNone::False);
+
+    let logs = Inflight::logs(Some(log_id(1, 1, 4)), Some(log_id(1, 1, 6))).with_id(1);
+    assert_eq!(
+        vec![
+            Command::Replicate { target: 3, req: logs },
+            Command::Replicate { target: 4, req: logs },
+            Command::Replicate { target: 5, req: logs },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_initiate_replication_read_replica_first() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.read_replicas.insert(5);
+
+    eng.replication_handler().initiate_replication(SendNone::False);
+
+    let logs = Inflight::logs(Some(log_id(1, 1, 4)), Some(log_id(1, 1, 6))).with_id(1);
+    assert_eq!(
+        vec![
+            Command::Replicate {
+                target: 5,
+                req: Inflight::logs(Some(log_id(1, 1, 4)), Some(log_id(1, 1, 8))).with_id(1),
+            },
+            Command::Replicate { target: 3, req: logs },
+            Command::Replicate { target: 4, req: logs },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_initiate_replication_priority_first() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.read_replicas.insert(5);
+    eng.config.replication_priorities.insert(4, 10);
+    eng.config.replication_priorities.insert(3, 5);
+
+    eng.replication_handler().initiate_replication(SendNone::False);
+
+    let logs = Inflight::logs(Some(log_id(1, 1, 4)), Some(log_id(1, 1, 6))).with_id(1);
+    assert_eq!(
+        vec![
+            Command::Replicate { target: 4, req: logs },
+            Command::Replicate { target: 3, req: logs },
+            Command::Replicate {
+                target: 5,
+                req: Inflight::logs(Some(log_id(1, 1, 4)), Some(log_id(1, 1, 8))).with_id(1),
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_initiate_replication_max_concurrent_snapshot_transfers() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.max_concurrent_snapshot_transfers = 1;
+
+    // Logs needed by every target are purged.
+    eng.state.enable_validation(false);
+    eng.state.purge_upto = Some(log_id(1, 1, 5));
+    eng.state.snapshot_meta.last_log_id = Some(log_id(1, 1, 5));
+
+    eng.replication_handler().initiate_replication(SendNone::True);
+
+    assert_eq!(
+        vec![
+            Command::Replicate {
+                target: 3,
+                req: Inflight::snapshot(Some(log_id(1, 1, 5))).with_id(1),
+            },
+            // Other targets wait for the snapshot transfer and receive heartbeats.
+            Command::Replicate {
+                target: 4,
+                req: Inflight::None,
+            },
+            Command::Replicate {
+                target: 5,
+                req: Inflight::None,
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    let leading = eng.internal_server_state.leading().unwrap();
+    assert!(leading.progress.get(&4).inflight.is_none());
+    assert!(leading.progress.get(&5).inflight.is_none());
+
+    Ok(())
+}
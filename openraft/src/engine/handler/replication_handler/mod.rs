@@ -1,6 +1,9 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
 use std::ops::Deref;
 
 use crate::display_ext::DisplayOptionExt;
+use crate::engine::handler::leader_handler::LeaderHandler;
 use crate::engine::handler::log_handler::LogHandler;
 use crate::engine::handler::snapshot_handler::SnapshotHandler;
 use crate::engine::Command;
@@ -25,6 +28,9 @@ use crate::RaftTypeConfig;
 use crate::ServerState;
 
 #[cfg(test)] mod append_membership_test;
+#[cfg(test)] mod initiate_replication_test;
+#[cfg(test)] mod resync_target_test;
+#[cfg(test)] mod update_log_purged_test;
 #[cfg(test)] mod update_matching_test;
 
 /// Handle replication operations.
@@ -89,6 +95,8 @@ where C: RaftTypeConfig
             "Only leader is allowed to call update_effective_membership()"
         );
 
+        let prev_membership = self.state.membership_state.effective().clone();
+
         self.state.membership_state.append(EffectiveMembership::new_arc(Some(*log_id), m.clone()));
 
         // TODO(9): currently only a leader has replication setup.
@@ -103,6 +111,46 @@ where C: RaftTypeConfig
         self.rebuild_progresses();
         self.rebuild_replication_streams();
         self.initiate_replication(SendNone::False);
+
+        if self.config.notify_removed_nodes {
+            self.notify_removed_nodes(&prev_membership, log_id, m);
+        }
+    }
+
+    /// Send the membership log to every node in `prev_membership` that is removed by it.
+    ///
+    /// A removed node no longer has a replication stream and won't receive this log otherwise.
+    /// With this log it knows it is not a voter and stops electing. It is sent once when the log is
+    /// appended and once more when it is committed, so that the removed node learns the removal
+    /// is committed.
+    pub(crate) fn notify_removed_nodes(
+        &mut self,
+        prev_membership: &EffectiveMembership<C>,
+        log_id: &LogId<C::NodeId>,
+        m: &Membership<C>,
+    ) {
+        let prev_log_id = log_id.index.checked_sub(1).and_then(|index| self.state.log_ids.get(index));
+
+        for (target, node) in prev_membership.nodes() {
+            if target == &self.config.id || m.get_node(target).is_some() {
+                continue;
+            }
+
+            tracing::info!(
+                target = display(target),
+                "notify removed node with membership log: {}",
+                log_id
+            );
+
+            self.output.push_command(Command::NotifyRemoved {
+                target: *target,
+                node: node.clone(),
+                vote: *self.state.vote_ref(),
+                prev_log_id,
+                membership_entry: C::Entry::new_membership(*log_id, m.clone()),
+                leader_commit: self.state.committed().copied(),
+            });
+        }
     }
 
     /// Rebuild leader's replication progress to reflect replication changes.
@@ -203,10 +251,7 @@ where C: RaftTypeConfig
 
         debug_assert!(log_id.is_some(), "a valid update can never set matching to None");
 
-        // The value granted by a quorum may not yet be a committed.
-        // A committed is **granted** and also is in current term.
-        let quorum_accepted = *self
-            .leader
+        self.leader
             .progress
             .update_with(&node_id, |prog_entry| {
                 let res = prog_entry.update_matching(inflight_id, log_id);
@@ -217,19 +262,26 @@ where C: RaftTypeConfig
             })
             .expect("it should always update existing progress");
 
-        tracing::debug!(
-            quorum_accepted = display(quorum_accepted.display()),
-            "after updating progress"
-        );
-
-        self.try_commit_quorum_accepted(quorum_accepted);
+        self.try_commit_quorum_accepted();
     }
 
     /// Commit the log id that is granted(accepted) by a quorum of voters.
     ///
+    /// The quorum is decided by the [`QuorumPolicy`](crate::raft::QuorumPolicy) in the config.
     /// In raft a log that is granted and in the leader term is committed.
     #[tracing::instrument(level = "debug", skip_all)]
-    pub(crate) fn try_commit_quorum_accepted(&mut self, granted: Option<LogId<C::NodeId>>) {
+    pub(crate) fn try_commit_quorum_accepted(&mut self) {
+        // The value granted by a quorum may not yet be a committed.
+        // A committed is **granted** and also is in current term.
+        let matching = self.leader.progress.iter().map(|(id, p)| (*id, p.matching)).collect::<BTreeMap<_, _>>();
+        let granted = self
+            .config
+            .quorum_policy
+            .0
+            .committed(&matching, self.state.membership_state.effective().membership());
+
+        tracing::debug!(granted = display(granted.display()), "after updating progress");
+
         // Only when the log id is proposed by current leader, it is committed.
         if let Some(c) = granted {
             if !self.state.vote_ref().is_same_leader(c.committed_leader_id()) {
@@ -237,24 +289,98 @@ where C: RaftTypeConfig
             }
         }
 
+        let prev_committed_membership = self.state.membership_state.committed().clone();
+
         if let Some(prev_committed) = self.state.update_committed(&granted) {
             self.output.push_command(Command::ReplicateCommitted {
                 committed: self.state.committed().copied(),
             });
 
+            // Notify the removed nodes again, with a `leader_commit` that tells them the removal
+            // is committed.
+            let committed_membership = self.state.membership_state.committed().clone();
+            if self.config.notify_removed_nodes && committed_membership.log_id() != prev_committed_membership.log_id() {
+                if let Some(log_id) = committed_membership.log_id() {
+                    self.notify_removed_nodes(&prev_committed_membership, log_id, committed_membership.membership());
+                }
+            }
+
+            let upto = self.state.committed().copied().unwrap();
+
+            let progress = &self.leader.progress;
+            self.leader.commit_quorum = progress
+                .iter()
+                .filter(|(id, p)| progress.is_voter(id) == Some(true) && p.matching.index() >= Some(upto.index))
+                .map(|(id, _)| *id)
+                .collect();
+
             let seq = self.output.next_sm_seq();
             self.output.push_command(Command::Commit {
                 seq,
                 already_committed: prev_committed,
-                upto: self.state.committed().copied().unwrap(),
+                upto,
             });
 
             if self.config.snapshot_policy.should_snapshot(&self.state) {
                 self.snapshot_handler().trigger_snapshot();
             }
+
+            self.complete_inherited_joint_membership();
         }
     }
 
+    /// Propose the final uniform config, if the committed effective membership is a joint config
+    /// proposed by a previous leader.
+    ///
+    /// A leader that crashed between the joint config log and the uniform config log leaves the
+    /// cluster in joint consensus. The next leader drives it forward once the joint config is
+    /// committed. Voters removed by the change are retained as learners, because whether the
+    /// crashed leader was asked to remove them is unknown.
+    ///
+    /// A joint config proposed by this leader is left alone: [`Raft::change_membership()`] that
+    /// proposed it will propose the uniform config.
+    ///
+    /// [`Raft::change_membership()`]: crate::Raft::change_membership
+    fn complete_inherited_joint_membership(&mut self) {
+        let ms = &self.state.membership_state;
+        let effective = ms.effective();
+
+        if effective.membership().get_joint_config().len() <= 1 {
+            return;
+        }
+
+        if ms.committed().log_id() != effective.log_id() {
+            return;
+        }
+
+        let Some(log_id) = effective.log_id() else {
+            return;
+        };
+
+        if self.state.vote_ref().is_same_leader(log_id.committed_leader_id()) {
+            return;
+        }
+
+        let goal = effective.membership().get_joint_config().last().unwrap().clone();
+        let uniform = effective.membership().next_coherent(goal, true);
+
+        tracing::info!(
+            joint = display(effective.membership()),
+            uniform = display(&uniform),
+            "complete the joint config inherited from a previous leader"
+        );
+
+        let entry = C::Entry::new_membership(LogId::default(), uniform);
+
+        let mut lh = LeaderHandler {
+            config: self.config,
+            leader: self.leader,
+            state: self.state,
+            output: self.output,
+        };
+        lh.leader_append_entries(vec![entry]);
+    }
+
     /// Update progress when replicated data(logs or snapshot) does not match follower/learner state
     /// and is rejected.
     #[tracing::instrument(level = "debug", skip_all)]
@@ -324,22 +450,159 @@ where C: RaftTypeConfig
         // Thus we just try again to purge when progress is updated.
         self.try_purge_log();
 
+        // A heartbeat response does not finish the data in flight.
+        let inflight_done = self.leader.progress.try_get(&target).map(|p| p.inflight.is_none());
+        if inflight_done == Some(true) && self.leader.pending_resyncs.remove(&target) {
+            self.reset_progress(target);
+        }
+
         // initialize next replication to this target
 
         {
-            let p = self.leader.progress.get_mut(&target).unwrap();
-
-            let r = p.next_send(self.state.deref(), self.config.max_payload_entries);
+            let r = self.next_send(&target);
             tracing::debug!(next_send_res = debug(&r), "next_send");
 
             if let Ok(inflight) = r {
-                Self::send_to_target(self.output, &target, inflight);
+                Self::send_to_target(self.output, &target, &inflight);
             } else {
-                tracing::debug!("nothing to send to target={target}, progress:{}", p);
+                tracing::debug!(
+                    "nothing to send to target={target}, progress:{}",
+                    self.leader.progress.get(&target)
+                );
+            }
+        }
+
+        // A finished snapshot transfer may let a target waiting for a snapshot proceed.
+        if let RequestId::Snapshot { .. } = request_id {
+            if self.config.max_concurrent_snapshot_transfers > 0 {
+                self.initiate_replication(SendNone::False);
             }
         }
     }
 
+    /// Initialize the next replication action to `target`, see [`ProgressEntry::next_send()`].
+    ///
+    /// A snapshot is not sent if there are already [`Config::max_concurrent_snapshot_transfers`]
+    /// snapshot transfers in flight. It returns `Err(Inflight::None)` in such case, so that the
+    /// target still receives heartbeats and will be sent a snapshot later.
+    ///
+    /// [`Config::max_concurrent_snapshot_transfers`]: crate::Config::max_concurrent_snapshot_transfers
+    fn next_send(&mut self, target: &C::NodeId) -> Result<Inflight<C::NodeId>, Inflight<C::NodeId>> {
+        let max_entries = self.config.max_payload_entries_for(target);
+        let max_snapshots = self.config.max_concurrent_snapshot_transfers;
+
+        let sending_snapshots = self
+            .leader
+            .progress
+            .iter()
+            .filter(|(id, p)| id != target && p.inflight.is_sending_snapshot())
+            .count();
+
+        // Safe unwrap(): target is from progress
+        let prog_entry = self.leader.progress.get_mut(target).unwrap();
+
+        let t = prog_entry.next_send(self.state.deref(), max_entries).copied().map_err(|e| *e);
+
+        if let Ok(inflight) = &t {
+            if inflight.is_sending_snapshot() && max_snapshots > 0 && sending_snapshots as u64 >= max_snapshots {
+                tracing::info!(
+                    target = display(target),
+                    sending_snapshots = display(sending_snapshots),
+                    "too many snapshot transfers in flight, postpone sending snapshot"
+                );
+
+                prog_entry.inflight = Inflight::None;
+                return Err(Inflight::None);
+            }
+        }
+
+        t
+    }
+
+    /// The logs to replicate to `target` are found purged when reading them from the log store:
+    /// replicate a snapshot to it instead.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn update_log_purged(&mut self, target: C::NodeId, request_id: RequestId) {
+        tracing::debug!(
+            target = display(target),
+            request_id = display(request_id),
+            "{}",
+            func_name!()
+        );
+
+        let Some(p) = self.leader.progress.get_mut(&target) else {
+            tracing::warn!("leader has removed target: {}", target);
+            return;
+        };
+
+        if !p.inflight.is_my_id(request_id) {
+            tracing::warn!(
+                inflight = debug(&p.inflight),
+                request_id = display(request_id),
+                "stale log purged response, ignore"
+            );
+            return;
+        }
+
+        p.curr_inflight_id += 1;
+        let snapshot_last = self.state.snapshot_last_log_id();
+        p.inflight = Inflight::snapshot(snapshot_last.copied()).with_id(p.curr_inflight_id);
+
+        Self::send_to_target(self.output, &target, &p.inflight);
+    }
+
+    /// Forget the replication progress of `target` and find the matching log id from scratch.
+    ///
+    /// The progress is reset at once if there is no data in flight to `target`, otherwise it is
+    /// reset when the data in flight is acknowledged. The leader then probes the follower log
+    /// backward from the last log, and sends a snapshot if the logs it needs are purged.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn resync_target(&mut self, target: C::NodeId) {
+        tracing::info!(target = display(target), "{}", func_name!());
+
+        if target == self.config.id {
+            tracing::warn!("leader does not replicate to itself, ignore resync");
+            return;
+        }
+
+        let Some(p) = self.leader.progress.try_get(&target) else {
+            tracing::warn!("resync target {} is not replicated by this leader", target);
+            return;
+        };
+
+        if !p.inflight.is_none() {
+            self.leader.pending_resyncs.insert(target);
+            return;
+        }
+
+        self.reset_progress(target);
+
+        if let Ok(inflight) = self.next_send(&target) {
+            Self::send_to_target(self.output, &target, &inflight);
+        }
+    }
+
+    /// Reset the progress of `target` as if nothing is known about its log.
+    fn reset_progress(&mut self, target: C::NodeId) {
+        let Some(p) = self.leader.progress.try_get(&target) else {
+            return;
+        };
+
+        debug_assert!(p.inflight.is_none());
+
+        // Keep the inflight id increasing, so that a late response is not mistaken for a new one.
+        let end = self.state.last_log_id().next_index();
+        let entry = ProgressEntry::empty(end).with_curr_inflight_id(p.curr_inflight_id);
+
+        tracing::info!(
+            target = display(target),
+            progress = display(p),
+            "reset replication progress"
+        );
+
+        self.leader.progress.reset(&target, entry);
+    }
+
     /// Update replication streams to reflect replication progress change.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn rebuild_replication_streams(&mut self) {
@@ -359,24 +622,39 @@ where C: RaftTypeConfig
 
     /// Initiate replication for every target that is not sending data in flight.
     ///
+    /// Targets are sent to in descending order of their replication priority, and among targets
+    /// with the same priority, read replicas are sent to before the others.
+    ///
     /// `send_none` specifies whether to force to send a message even when there is no data to send.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn initiate_replication(&mut self, send_none: SendNone) {
         tracing::debug!(progress = debug(&self.leader.progress), "{}", func_name!());
 
-        for (id, prog_entry) in self.leader.progress.iter_mut() {
-            // TODO: update matching should be done here for leader
-            //       or updating matching should be queued in commands?
-            if id == &self.config.id {
-                continue;
-            }
+        // TODO: update matching should be done here for leader
+        //       or updating matching should be queued in commands?
+        let mut targets = self
+            .leader
+            .progress
+            .iter()
+            .map(|(id, _)| *id)
+            .filter(|id| id != &self.config.id)
+            .collect::<Vec<_>>();
+
+        // Stable sort: the order of other targets is kept.
+        targets.sort_by_key(|id| {
+            (
+                Reverse(self.config.replication_priority(id)),
+                !self.config.read_replicas.contains(id),
+            )
+        });
 
-            let t = prog_entry.next_send(self.state, self.config.max_payload_entries);
+        for id in targets.iter() {
+            let t = self.next_send(id);
             tracing::debug!(target = display(*id), send = debug(&t), "next send");
 
             match t {
                 Ok(inflight) => {
-                    Self::send_to_target(self.output, id, inflight);
+                    Self::send_to_target(self.output, id, &inflight);
                 }
                 Err(e) => {
                     tracing::debug!(
@@ -387,9 +665,9 @@ where C: RaftTypeConfig
                     );
 
                     #[allow(clippy::collapsible_if)]
-                    if e == &Inflight::None {
+                    if e == Inflight::None {
                         if send_none == SendNone::True {
-                            Self::send_to_target(self.output, id, e);
+                            Self::send_to_target(self.output, id, &e);
                         }
                     }
                 }
@@ -452,6 +730,9 @@ where C: RaftTypeConfig
     ///
     /// Writing to local log store does not have to wait for a replication response from remote
     /// node. Thus it can just be done in a fast-path.
+    ///
+    /// If the leader is the only voter, it is a quorum by itself and the log is committed here at
+    /// once, without waiting for any replication stream.
     pub(crate) fn update_local_progress(&mut self, upto: Option<LogId<C::NodeId>>) {
         tracing::debug!(upto = display(upto.display()), "{}", func_name!());
 
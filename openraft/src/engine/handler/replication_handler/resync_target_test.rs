@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+
+use crate::core::ServerState;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::progress::entry::ProgressEntry;
+use crate::progress::Inflight;
+use crate::progress::Progress;
+use crate::replication::request_id::RequestId;
+use crate::replication::response::ReplicationResult;
+use crate::testing::log_id;
+use crate::utime::UTime;
+use crate::CommittedLeaderId;
+use crate::EffectiveMembership;
+use crate::LogId;
+use crate::Membership;
+use crate::TokioInstant;
+use crate::Vote;
+
+fn m23() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {2,3}], None)
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.config.id = 2;
+    eng.state.log_ids = LogIdList::new([
+        LogId::new(CommittedLeaderId::new(0, 0), 0),
+        log_id(1, 1, 1),
+        log_id(5, 1, 10),
+    ]);
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m23())));
+
+    eng.state.server_state = ServerState::Leader;
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 2));
+    eng.vote_handler().become_leading();
+
+    if let Some(l) = eng.internal_server_state.leading_mut() {
+        let prog_entry = ProgressEntry::new(Some(log_id(1, 1, 1))).with_curr_inflight_id(5);
+        let _ = l.progress.update(&3, prog_entry);
+    }
+
+    eng.output.clear_commands();
+    eng
+}
+
+#[test]
+fn test_resync_target() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.replication_handler().resync_target(3);
+
+    let want = Inflight::logs(None, Some(log_id(5, 1, 10))).with_id(6);
+
+    let p = eng.internal_server_state.leading().unwrap().progress.get(&3);
+    assert_eq!(None, p.matching, "matching is reset");
+    assert_eq!(want, p.inflight, "probe from the beginning");
+    assert_eq!(
+        vec![
+            //
+            Command::Replicate { target: 3, req: want },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_resync_target_with_inflight() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let sent = Inflight::logs(Some(log_id(1, 1, 1)), Some(log_id(5, 1, 10))).with_id(5);
+    if let Some(l) = eng.internal_server_state.leading_mut() {
+        l.progress.get_mut(&3).unwrap().inflight = sent;
+    }
+
+    eng.replication_handler().resync_target(3);
+
+    let l = eng.internal_server_state.leading().unwrap();
+    assert_eq!(Some(log_id(1, 1, 1)), l.progress.get(&3).matching);
+    assert_eq!(sent, l.progress.get(&3).inflight, "inflight data is not touched");
+    assert_eq!(btreeset! {3}, l.pending_resyncs);
+    assert!(eng.output.take_commands().is_empty());
+
+    // The reset happens when the data in flight is acknowledged.
+
+    eng.replication_handler().update_progress(
+        3,
+        RequestId::new_append_entries(5),
+        Ok(ReplicationResult {
+            sending_time: TokioInstant::now(),
+            result: Ok(Some(log_id(5, 1, 10))),
+        }),
+    );
+
+    let l = eng.internal_server_state.leading().unwrap();
+    assert_eq!(None, l.progress.get(&3).matching);
+    assert_eq!(
+        Inflight::logs(None, Some(log_id(5, 1, 10))).with_id(6),
+        l.progress.get(&3).inflight
+    );
+    assert!(l.pending_resyncs.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_resync_target_ignore_self_and_unknown() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.replication_handler().resync_target(2);
+    eng.replication_handler().resync_target(9);
+
+    let l = eng.internal_server_state.leading().unwrap();
+    assert_eq!(Some(log_id(1, 1, 1)), l.progress.get(&3).matching);
+    assert!(l.pending_resyncs.is_empty());
+    assert!(eng.output.take_commands().is_empty());
+
+    Ok(())
+}
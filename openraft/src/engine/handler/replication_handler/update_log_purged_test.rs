@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+
+use crate::core::ServerState;
+use crate::engine::testing::UTConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::progress::entry::ProgressEntry;
+use crate::progress::Inflight;
+use crate::progress::Progress;
+use crate::replication::request_id::RequestId;
+use crate::testing::log_id;
+use crate::utime::UTime;
+use crate::CommittedLeaderId;
+use crate::EffectiveMembership;
+use crate::LogId;
+use crate::Membership;
+use crate::TokioInstant;
+use crate::Vote;
+
+fn m23() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new(vec![btreeset! {2,3}], None)
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.config.id = 2;
+    eng.state.log_ids = LogIdList::new([
+        LogId::new(CommittedLeaderId::new(0, 0), 0),
+        log_id(1, 1, 1),
+        log_id(5, 1, 10),
+    ]);
+    eng.state.snapshot_meta.last_log_id = Some(log_id(1, 1, 4));
+    eng.state
+        .membership_state
+        .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m23())));
+
+    eng.state.server_state = ServerState::Leader;
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 2));
+    eng.vote_handler().become_leading();
+
+    if let Some(l) = eng.internal_server_state.leading_mut() {
+        let prog_entry = ProgressEntry::new(Some(log_id(1, 1, 2))).with_curr_inflight_id(5);
+        let _ = l.progress.update(&3, prog_entry);
+        l.progress.get_mut(&3).unwrap().inflight =
+            Inflight::logs(Some(log_id(1, 1, 2)), Some(log_id(5, 1, 10))).with_id(5);
+    }
+
+    eng.output.clear_commands();
+    eng
+}
+
+#[test]
+fn test_update_log_purged() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.replication_handler().update_log_purged(3, RequestId::new_append_entries(5));
+
+    let want = Inflight::snapshot(Some(log_id(1, 1, 4))).with_id(6);
+    assert_eq!(
+        want,
+        eng.internal_server_state.leading().unwrap().progress.get(&3).inflight
+    );
+    assert_eq!(
+        vec![
+            //
+            Command::Replicate { target: 3, req: want },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_update_log_purged_stale_request() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.replication_handler().update_log_purged(3, RequestId::new_append_entries(4));
+
+    assert_eq!(
+        Inflight::logs(Some(log_id(1, 1, 2)), Some(log_id(5, 1, 10))).with_id(5),
+        eng.internal_server_state.leading().unwrap().progress.get(&3).inflight
+    );
+    assert!(eng.output.take_commands().is_empty());
+
+    Ok(())
+}
@@ -1,17 +1,25 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use maplit::btreeset;
 use pretty_assertions::assert_eq;
 
+use crate::core::ServerState;
 use crate::engine::testing::UTConfig;
 use crate::engine::Command;
 use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::entry::RaftEntry;
 use crate::progress::Inflight;
 use crate::progress::Progress;
+use crate::raft::quorum_policy::QuorumPolicyRef;
+use crate::raft::QuorumPolicy;
 use crate::raft_state::LogStateReader;
 use crate::testing::log_id;
 use crate::utime::UTime;
 use crate::EffectiveMembership;
+use crate::Entry;
+use crate::LogId;
 use crate::Membership;
 use crate::MembershipState;
 use crate::TokioInstant;
@@ -95,6 +103,7 @@ fn test_update_matching() -> anyhow::Result<()> {
         rh.output.clear_commands();
         rh.update_matching(3, inflight_id_3, Some(log_id(2, 1, 3)));
         assert_eq!(Some(&log_id(2, 1, 1)), rh.state.committed());
+        assert_eq!(btreeset! {2,3}, rh.leader.commit_quorum);
         assert_eq!(
             vec![
                 Command::ReplicateCommitted {
@@ -110,11 +119,12 @@ fn test_update_matching() -> anyhow::Result<()> {
         );
     }
 
-    // progress: (2,4), (2,1), (2,3); committed: (1,3)
+    // progress: (2,4), (2,1), (2,3); committed: (1,3); node 2 is not in the commit quorum
     {
         rh.output.clear_commands();
         rh.update_matching(1, inflight_id_1, Some(log_id(2, 1, 4)));
         assert_eq!(Some(&log_id(2, 1, 3)), rh.state.committed());
+        assert_eq!(btreeset! {1,3}, rh.leader.commit_quorum);
         assert_eq!(
             vec![
                 Command::ReplicateCommitted {
@@ -132,3 +142,227 @@ fn test_update_matching() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_update_matching_notify_removed_nodes() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.notify_removed_nodes = true;
+    eng.state.log_ids = LogIdList::new(vec![log_id(1, 1, 0), log_id(2, 1, 1), log_id(2, 1, 4)]);
+    eng.vote_handler().become_leading();
+
+    let mut rh = eng.replication_handler();
+    let inflight_id_1 = {
+        let prog_entry = rh.leader.progress.get_mut(&1).unwrap();
+        prog_entry.inflight = Inflight::logs(Some(log_id(1, 1, 1)), Some(log_id(2, 1, 4)));
+        prog_entry.inflight.get_id().unwrap()
+    };
+    let inflight_id_3 = {
+        let prog_entry = rh.leader.progress.get_mut(&3).unwrap();
+        prog_entry.inflight = Inflight::logs(Some(log_id(1, 1, 1)), Some(log_id(2, 1, 4)));
+        prog_entry.inflight.get_id().unwrap()
+    };
+
+    // progress: (2,3), None, (2,3); committed: (2,3), membership {0,1} is replaced by {1,2,3}
+    rh.update_matching(1, inflight_id_1, Some(log_id(2, 1, 3)));
+    rh.update_matching(3, inflight_id_3, Some(log_id(2, 1, 3)));
+    assert_eq!(Some(&log_id(2, 1, 3)), rh.state.committed());
+
+    assert_eq!(
+        vec![
+            Command::ReplicateCommitted {
+                committed: Some(log_id(2, 1, 3))
+            },
+            Command::NotifyRemoved {
+                target: 0,
+                node: (),
+                vote: Vote::new_committed(2, 1),
+                prev_log_id: Some(log_id(2, 1, 2)),
+                membership_entry: Entry::new_membership(log_id(2, 1, 3), m123()),
+                leader_commit: Some(log_id(2, 1, 3)),
+            },
+            Command::Commit {
+                seq: 1,
+                already_committed: None,
+                upto: log_id(2, 1, 3)
+            }
+        ],
+        rh.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_update_matching_coalesce_replicate_committed() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.vote_handler().become_leading();
+
+    let mut rh = eng.replication_handler();
+    let inflight_id_1 = {
+        let prog_entry = rh.leader.progress.get_mut(&1).unwrap();
+        prog_entry.inflight = Inflight::logs(Some(log_id(2, 1, 0)), Some(log_id(2, 1, 4)));
+        prog_entry.inflight.get_id().unwrap()
+    };
+    let inflight_id_2 = {
+        let prog_entry = rh.leader.progress.get_mut(&2).unwrap();
+        prog_entry.inflight = Inflight::logs(Some(log_id(2, 1, 0)), Some(log_id(2, 1, 4)));
+        prog_entry.inflight.get_id().unwrap()
+    };
+
+    // Two commits without running the queued commands in between.
+    rh.update_matching(1, inflight_id_1, Some(log_id(2, 1, 4)));
+    rh.update_matching(2, inflight_id_2, Some(log_id(2, 1, 1)));
+    assert_eq!(Some(&log_id(2, 1, 1)), rh.state.committed());
+
+    rh.update_matching(2, inflight_id_2, Some(log_id(2, 1, 3)));
+    assert_eq!(Some(&log_id(2, 1, 3)), rh.state.committed());
+
+    assert_eq!(
+        vec![
+            Command::Commit {
+                seq: 1,
+                already_committed: None,
+                upto: log_id(2, 1, 1)
+            },
+            Command::ReplicateCommitted {
+                committed: Some(log_id(2, 1, 3))
+            },
+            Command::Commit {
+                seq: 2,
+                already_committed: Some(log_id(2, 1, 1)),
+                upto: log_id(2, 1, 3)
+            }
+        ],
+        rh.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_update_matching_quorum_policy() -> anyhow::Result<()> {
+    /// Commit only when every voter accepts a log.
+    struct AllVoters;
+
+    impl QuorumPolicy<UTConfig> for AllVoters {
+        fn committed(
+            &self,
+            matching: &BTreeMap<u64, Option<LogId<u64>>>,
+            membership: &Membership<UTConfig>,
+        ) -> Option<LogId<u64>> {
+            membership.voter_ids().map(|id| matching.get(&id).copied().flatten()).min().flatten()
+        }
+    }
+
+    let mut eng = eng();
+    eng.config.quorum_policy = QuorumPolicyRef(Arc::new(AllVoters));
+    eng.vote_handler().become_leading();
+
+    let mut rh = eng.replication_handler();
+    let mut inflight_ids = BTreeMap::new();
+    for id in [1, 2, 3] {
+        let prog_entry = rh.leader.progress.get_mut(&id).unwrap();
+        prog_entry.inflight = Inflight::logs(None, Some(log_id(2, 1, 4)));
+        inflight_ids.insert(id, prog_entry.inflight.get_id().unwrap());
+    }
+
+    // progress: (2,3), None, (2,3); a majority is not enough
+    {
+        rh.update_matching(1, inflight_ids[&1], Some(log_id(2, 1, 3)));
+        rh.update_matching(3, inflight_ids[&3], Some(log_id(2, 1, 3)));
+        assert_eq!(None, rh.state.committed());
+        assert_eq!(0, rh.output.take_commands().len());
+    }
+
+    // progress: (2,3), (2,1), (2,3); committed: (2,1)
+    {
+        rh.update_matching(2, inflight_ids[&2], Some(log_id(2, 1, 1)));
+        assert_eq!(Some(&log_id(2, 1, 1)), rh.state.committed());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_update_matching_complete_inherited_joint() -> anyhow::Result<()> {
+    let m123_12 = Membership::<UTConfig>::new(vec![btreeset! {1,2,3}, btreeset! {1,2}], None);
+
+    let mut eng = eng();
+    eng.config.id = 2;
+    eng.state.server_state = ServerState::Leader;
+    eng.state.vote = UTime::new(TokioInstant::now(), Vote::new_committed(2, 2));
+    eng.state.log_ids = LogIdList::new(vec![log_id(1, 1, 0), log_id(2, 2, 4)]);
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m123())),
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 3)), m123_12)),
+    );
+    eng.vote_handler().become_leading();
+
+    let mut rh = eng.replication_handler();
+    let mut inflight_ids = BTreeMap::new();
+    for id in [1, 2] {
+        let prog_entry = rh.leader.progress.get_mut(&id).unwrap();
+        prog_entry.inflight = Inflight::logs(None, Some(log_id(2, 2, 4)));
+        inflight_ids.insert(id, prog_entry.inflight.get_id().unwrap());
+    }
+
+    // The joint config proposed by the previous leader is committed with the blank log.
+    rh.update_matching(1, inflight_ids[&1], Some(log_id(2, 2, 4)));
+    rh.update_matching(2, inflight_ids[&2], Some(log_id(2, 2, 4)));
+    assert_eq!(Some(&log_id(2, 2, 4)), rh.state.committed());
+
+    // The uniform config is proposed, the removed voter is retained as a learner.
+    let effective = rh.state.membership_state.effective().clone();
+    assert_eq!(&Some(log_id(2, 2, 5)), effective.log_id());
+    assert_eq!(
+        &Membership::<UTConfig>::new(vec![btreeset! {1,2}], Some(btreeset! {3})),
+        effective.membership()
+    );
+    assert!(rh.output.take_commands().iter().any(|c| matches!(c, Command::AppendInputEntries { .. })));
+
+    Ok(())
+}
+
+#[test]
+fn test_update_matching_joint_multiple_nodes() -> anyhow::Result<()> {
+    // Several voters are added and removed at once.
+    let m123_345 = Membership::<UTConfig>::new(vec![btreeset! {1,2,3}, btreeset! {3,4,5}], None);
+
+    let mut eng = eng();
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m01())),
+        Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 3)), m123_345)),
+    );
+    eng.vote_handler().become_leading();
+
+    let mut rh = eng.replication_handler();
+    let mut inflight_ids = BTreeMap::new();
+    for id in [1, 2, 3, 4, 5] {
+        let prog_entry = rh.leader.progress.get_mut(&id).unwrap();
+        prog_entry.inflight = Inflight::logs(None, Some(log_id(2, 1, 4)));
+        inflight_ids.insert(id, prog_entry.inflight.get_id().unwrap());
+    }
+
+    // progress: 4,5 at (2,3); a quorum of only the new config
+    {
+        rh.update_matching(4, inflight_ids[&4], Some(log_id(2, 1, 3)));
+        rh.update_matching(5, inflight_ids[&5], Some(log_id(2, 1, 3)));
+        assert_eq!(None, rh.state.committed());
+        assert_eq!(0, rh.output.take_commands().len());
+    }
+
+    // progress: 1,4,5 at (2,3); still not a quorum of the old config
+    {
+        rh.update_matching(1, inflight_ids[&1], Some(log_id(2, 1, 3)));
+        assert_eq!(None, rh.state.committed());
+        assert_eq!(0, rh.output.take_commands().len());
+    }
+
+    // progress: 1,2,4,5 at (2,3); a quorum of both configs
+    {
+        rh.update_matching(2, inflight_ids[&2], Some(log_id(2, 1, 3)));
+        assert_eq!(Some(&log_id(2, 1, 3)), rh.state.committed());
+    }
+
+    Ok(())
+}
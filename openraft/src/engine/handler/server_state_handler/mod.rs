@@ -48,6 +48,7 @@ where C: RaftTypeConfig
             // nothing to do
         }
 
+        self.output.push_server_state_transition(self.state.server_state, server_state);
         self.state.server_state = server_state;
     }
 }
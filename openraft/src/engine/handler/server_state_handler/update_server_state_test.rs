@@ -59,6 +59,10 @@ fn test_update_server_state_if_changed() -> anyhow::Result<()> {
             ],
             ssh.output.take_commands()
         );
+        assert_eq!(
+            vec![(ServerState::Leader, ServerState::Follower)],
+            ssh.output.take_server_state_transitions()
+        );
     }
 
     // TODO(3): add more test,
@@ -24,6 +24,7 @@ fn eng() -> Engine<UTConfig> {
         last_log_id: Some(log_id(2, 1, 2)),
         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
         snapshot_id: "1-2-3-4".to_string(),
+        checksum: None,
     };
     eng
 }
@@ -37,6 +38,7 @@ fn test_update_snapshot_no_update() -> anyhow::Result<()> {
         last_log_id: Some(log_id(2, 1, 2)),
         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
         snapshot_id: "1-2-3-4".to_string(),
+        checksum: None,
     });
 
     assert_eq!(false, got);
@@ -46,6 +48,7 @@ fn test_update_snapshot_no_update() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         eng.state.snapshot_meta
     );
@@ -64,6 +67,7 @@ fn test_update_snapshot_updated() -> anyhow::Result<()> {
         last_log_id: Some(log_id(2, 1, 3)),
         last_membership: StoredMembership::new(Some(log_id(2, 1, 2)), m1234()),
         snapshot_id: "1-2-3-4".to_string(),
+        checksum: None,
     });
 
     assert_eq!(true, got);
@@ -73,6 +77,7 @@ fn test_update_snapshot_updated() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 3)),
             last_membership: StoredMembership::new(Some(log_id(2, 1, 2)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         eng.state.snapshot_meta
     );
@@ -8,7 +8,7 @@ use crate::engine::testing::UTConfig;
 use crate::engine::Command;
 use crate::engine::Engine;
 use crate::engine::Respond;
-use crate::error::Infallible;
+use crate::error::Fatal;
 use crate::raft::VoteResponse;
 use crate::testing::log_id;
 use crate::type_config::alias::AsyncRuntimeOf;
@@ -24,8 +24,8 @@ fn m01() -> Membership<UTConfig> {
 }
 
 /// Make a sample VoteResponse
-fn mk_res() -> Result<VoteResponse<UTConfig>, Infallible> {
-    Ok::<VoteResponse<UTConfig>, Infallible>(VoteResponse {
+fn mk_res() -> Result<VoteResponse<UTConfig>, Fatal<UTConfig>> {
+    Ok::<VoteResponse<UTConfig>, Fatal<UTConfig>>(VoteResponse {
         vote: Vote::new(2, 1),
         vote_granted: false,
         last_log_id: None,
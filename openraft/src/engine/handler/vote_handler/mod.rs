@@ -101,6 +101,7 @@ where C: RaftTypeConfig
             tracing::info!("vote is changing from {} to {}", self.state.vote_ref(), vote);
 
             self.state.vote.update(InstantOf::<C>::now(), *vote);
+            self.state.yielded = false;
             self.output.push_command(Command::SaveVote { vote: *vote });
         } else {
             self.state.vote.touch(InstantOf::<C>::now());
@@ -170,8 +171,9 @@ where C: RaftTypeConfig
 
         debug_assert!(
             self.state.vote_ref().leader_id().voted_for() != Some(self.config.id)
-                || !self.state.membership_state.effective().membership().is_voter(&self.config.id),
-            "It must hold: vote is not mine, or I am not a voter(leader just left the cluster)"
+                || !self.state.membership_state.effective().membership().is_voter(&self.config.id)
+                || self.state.yielded,
+            "It must hold: vote is not mine, or I am not a voter(leader just left the cluster), or I yielded"
         );
 
         if self.internal_server_state.is_following() {
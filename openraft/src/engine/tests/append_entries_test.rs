@@ -1,3 +1,4 @@
+use std::io::Cursor;
 use std::sync::Arc;
 
 use maplit::btreeset;
@@ -9,6 +10,8 @@ use crate::engine::Command;
 use crate::engine::Engine;
 use crate::entry::RaftEntry;
 use crate::error::RejectAppendEntries;
+use crate::error::TermRegression;
+use crate::raft::AppendEntriesResponse;
 use crate::raft_state::LogStateReader;
 use crate::testing::blank_ent;
 use crate::testing::log_id;
@@ -17,6 +20,9 @@ use crate::EffectiveMembership;
 use crate::Entry;
 use crate::Membership;
 use crate::MembershipState;
+use crate::Snapshot;
+use crate::SnapshotMeta;
+use crate::StoredMembership;
 use crate::TokioInstant;
 use crate::Vote;
 
@@ -74,6 +80,7 @@ fn test_append_entries_vote_is_rejected() -> anyhow::Result<()> {
     );
     assert_eq!(ServerState::Follower, eng.state.server_state);
     assert_eq!(0, eng.output.take_commands().len());
+    assert_eq!(1, eng.stale_term_requests, "term 1 is stale");
 
     Ok(())
 }
@@ -115,6 +122,7 @@ fn test_append_entries_prev_log_id_is_applied() -> anyhow::Result<()> {
         },],
         eng.output.take_commands()
     );
+    assert_eq!(0, eng.stale_term_requests);
 
     Ok(())
 }
@@ -300,3 +308,143 @@ fn test_append_entries_conflict() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_append_entries_term_regression_between_entries() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    // A malformed payload: entry 2-5 is followed by 1-6.
+    let res = eng.append_entries(&Vote::new_committed(2, 1), Some(log_id(2, 1, 3)), vec![
+        blank_ent(2, 1, 4),
+        blank_ent(2, 1, 5),
+        blank_ent(1, 1, 6),
+    ]);
+
+    assert_eq!(
+        Err(RejectAppendEntries::ByTermRegression(TermRegression {
+            prev: log_id(2, 1, 5),
+            log_id: log_id(1, 1, 6),
+        })),
+        res
+    );
+    assert_eq!(
+        &[
+            log_id(1, 1, 1), //
+            log_id(2, 1, 3),
+        ],
+        eng.state.log_ids.key_log_ids(),
+        "nothing is appended"
+    );
+    assert_eq!(Some(&log_id(2, 1, 3)), eng.state.last_log_id());
+    assert_eq!(
+        vec![Command::SaveVote {
+            vote: Vote::new_committed(2, 1)
+        },],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_append_entries_term_regression_from_prev_log_id() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    // A malformed payload: the first entry has a smaller term than prev_log_id.
+    let res = eng.append_entries(&Vote::new_committed(2, 1), Some(log_id(2, 1, 3)), vec![blank_ent(
+        1, 1, 4,
+    )]);
+
+    assert_eq!(
+        Err(RejectAppendEntries::ByTermRegression(TermRegression {
+            prev: log_id(2, 1, 3),
+            log_id: log_id(1, 1, 4),
+        })),
+        res
+    );
+    assert_eq!(Some(&log_id(2, 1, 3)), eng.state.last_log_id());
+
+    let resp: AppendEntriesResponse<UTConfig> = res.into();
+    assert_eq!(
+        AppendEntriesResponse::TermRegression(TermRegression {
+            prev: log_id(2, 1, 3),
+            log_id: log_id(1, 1, 4),
+        }),
+        resp
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_append_entries_log_gap() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    // A malformed payload: entry 2-5 is missing.
+    let res = eng.append_entries(&Vote::new_committed(2, 1), Some(log_id(2, 1, 3)), vec![
+        blank_ent(2, 1, 4),
+        blank_ent(2, 1, 6),
+    ]);
+
+    assert_eq!(
+        Err(RejectAppendEntries::ByLogGap {
+            expect_index: 5,
+            log_id: log_id(2, 1, 6),
+        }),
+        res
+    );
+    assert_eq!(Some(&log_id(2, 1, 3)), eng.state.last_log_id(), "nothing is appended");
+    assert_eq!(
+        vec![Command::SaveVote {
+            vote: Vote::new_committed(2, 1)
+        },],
+        eng.output.take_commands()
+    );
+
+    let resp: AppendEntriesResponse<UTConfig> = res.into();
+    assert_eq!(AppendEntriesResponse::Conflict, resp);
+
+    Ok(())
+}
+
+#[test]
+fn test_append_entries_after_install_snapshot() -> anyhow::Result<()> {
+    // An AppendEntries sent before a snapshot is handled after the snapshot is installed:
+    // the entries covered by the snapshot are skipped.
+    let mut eng = eng();
+
+    eng.following_handler().install_full_snapshot(Snapshot {
+        meta: SnapshotMeta {
+            last_log_id: Some(log_id(2, 1, 6)),
+            last_membership: StoredMembership::new(Some(log_id(2, 1, 3)), m23()),
+            snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
+        },
+        snapshot: Box::new(Cursor::new(vec![0u8])),
+    });
+    eng.output.take_commands();
+
+    let res = eng.append_entries(&Vote::new_committed(2, 1), Some(log_id(2, 1, 3)), vec![
+        blank_ent(2, 1, 4),
+        blank_ent(2, 1, 5),
+        blank_ent(2, 1, 6),
+        blank_ent(2, 1, 7),
+    ]);
+
+    assert_eq!(Ok(()), res);
+    assert_eq!(Some(log_id(2, 1, 6)), eng.state.last_purged_log_id().copied());
+    assert_eq!(Some(&log_id(2, 1, 7)), eng.state.last_log_id());
+    assert_eq!(
+        vec![
+            Command::SaveVote {
+                vote: Vote::new_committed(2, 1)
+            },
+            Command::AppendInputEntries {
+                entries: vec![blank_ent(2, 1, 7)]
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    Ok(())
+}
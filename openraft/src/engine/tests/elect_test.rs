@@ -15,6 +15,7 @@ use crate::testing::log_id;
 use crate::utime::UTime;
 use crate::CommittedLeaderId;
 use crate::EffectiveMembership;
+use crate::ElectionOutcome;
 use crate::Entry;
 use crate::LogId;
 use crate::Membership;
@@ -49,6 +50,12 @@ fn test_elect() -> anyhow::Result<()> {
         eng.elect();
 
         assert_eq!(Vote::new_committed(1, 1), *eng.state.vote_ref());
+        assert_eq!(
+            Some(ElectionOutcome::Won {
+                vote: Vote::new_committed(1, 1)
+            }),
+            eng.last_election
+        );
         assert_eq!(
             Some(log_id(1, 1, 1)),
             eng.internal_server_state.leading().unwrap().noop_log_id
@@ -160,6 +167,10 @@ fn test_elect() -> anyhow::Result<()> {
         eng.elect();
 
         assert_eq!(Vote::new(1, 1), *eng.state.vote_ref());
+        assert_eq!(
+            Some(ElectionOutcome::InProgress { vote: Vote::new(1, 1) }),
+            eng.last_election
+        );
         assert_eq!(None, eng.internal_server_state.leading().unwrap().noop_log_id);
 
         assert_eq!(
@@ -178,3 +189,84 @@ fn test_elect() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_elect_max_term_jump() -> anyhow::Result<()> {
+    tracing::info!("--- term reaches the limit: re-elect with the current vote");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+        eng.config.max_term_jump = 2;
+        eng.state
+            .membership_state
+            .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(0, 1, 1)), m12())));
+        eng.state.log_ids = LogIdList::new(vec![log_id(1, 1, 1)]);
+
+        eng.confirmed_term = 1;
+        eng.state.vote = UTime::new(TokioInstant::now(), Vote::new(3, 1));
+
+        eng.elect();
+
+        assert_eq!(Vote::new(3, 1), *eng.state.vote_ref());
+        assert_eq!(
+            Some(ElectionOutcome::InProgress { vote: Vote::new(3, 1) }),
+            eng.last_election
+        );
+        assert_eq!(ServerState::Candidate, eng.state.server_state);
+
+        assert_eq!(
+            vec![Command::SendVote {
+                vote_req: VoteRequest::new(Vote::new(3, 1), Some(log_id(1, 1, 1)))
+            },],
+            eng.output.take_commands()
+        );
+    }
+
+    tracing::info!("--- term reaches the limit, voted for another node: skip election");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+        eng.config.max_term_jump = 2;
+        eng.state
+            .membership_state
+            .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(0, 1, 1)), m12())));
+
+        eng.confirmed_term = 1;
+        eng.state.vote = UTime::new(TokioInstant::now(), Vote::new(3, 2));
+
+        eng.elect();
+
+        assert_eq!(Vote::new(3, 2), *eng.state.vote_ref());
+        assert_eq!(None, eng.last_election);
+        assert_eq!(0, eng.output.take_commands().len());
+    }
+
+    tracing::info!("--- a greater term seen from others lifts the limit");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+        eng.config.max_term_jump = 2;
+        eng.state
+            .membership_state
+            .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(0, 1, 1)), m12())));
+        eng.state.log_ids = LogIdList::new(vec![log_id(1, 1, 1)]);
+
+        eng.confirmed_term = 1;
+        eng.state.vote = UTime::new(TokioInstant::now(), Vote::new(3, 1));
+
+        eng.handle_vote_req(VoteRequest::new(Vote::new(2, 2), Some(log_id(1, 1, 1))));
+        assert_eq!(2, eng.confirmed_term);
+        eng.output.take_commands();
+
+        eng.elect();
+
+        assert_eq!(Vote::new(4, 1), *eng.state.vote_ref());
+        assert_eq!(
+            vec![Command::SaveVote { vote: Vote::new(4, 1) }, Command::SendVote {
+                vote_req: VoteRequest::new(Vote::new(4, 1), Some(log_id(1, 1, 1)))
+            },],
+            eng.output.take_commands()
+        );
+    }
+    Ok(())
+}
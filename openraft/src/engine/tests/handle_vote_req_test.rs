@@ -88,6 +88,7 @@ fn test_handle_vote_req_reject_smaller_vote() -> anyhow::Result<()> {
 
     assert_eq!(ServerState::Candidate, eng.state.server_state);
     assert_eq!(0, eng.output.take_commands().len());
+    assert_eq!(1, eng.stale_term_requests, "term 1 is stale");
 
     Ok(())
 }
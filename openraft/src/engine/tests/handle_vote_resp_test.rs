@@ -1,6 +1,7 @@
 use std::collections::BTreeSet;
 use std::sync::Arc;
 
+use maplit::btreemap;
 use maplit::btreeset;
 use pretty_assertions::assert_eq;
 
@@ -10,6 +11,9 @@ use crate::engine::Command;
 use crate::engine::Engine;
 use crate::engine::LogIdList;
 use crate::entry::RaftEntry;
+use crate::metrics::ElectionRound;
+use crate::metrics::ElectionRoundOutcome;
+use crate::metrics::VoteReply;
 use crate::progress::entry::ProgressEntry;
 use crate::progress::Inflight;
 use crate::raft::VoteResponse;
@@ -18,6 +22,7 @@ use crate::testing::log_id;
 use crate::utime::UTime;
 use crate::CommittedLeaderId;
 use crate::EffectiveMembership;
+use crate::ElectionOutcome;
 use crate::Entry;
 use crate::LogId;
 use crate::Membership;
@@ -129,6 +134,15 @@ fn test_handle_vote_resp() -> anyhow::Result<()> {
 
         assert_eq!(Vote::new(3, 2), *eng.state.vote_ref());
         assert!(eng.internal_server_state.is_following());
+        assert_eq!(
+            Some(ElectionOutcome::Rejected {
+                vote: Vote::new(2, 1),
+                by: 2,
+                peer_vote: Vote::new(3, 2),
+                peer_last_log_id: Some(log_id(2, 1, 2)),
+            }),
+            eng.last_election
+        );
 
         assert_eq!(ServerState::Follower, eng.state.server_state);
 
@@ -272,3 +286,91 @@ fn test_handle_vote_resp() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_handle_vote_resp_election_round() -> anyhow::Result<()> {
+    let reply = |granted, vote, last_log_id| VoteReply::<UTConfig> {
+        granted,
+        vote,
+        last_log_id,
+    };
+
+    tracing::info!("--- rejected by a greater log and then by a greater vote: lost");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+        eng.state
+            .membership_state
+            .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m1234())));
+
+        eng.elect();
+        assert_eq!(Vote::new(1, 1), *eng.state.vote_ref());
+
+        eng.handle_vote_resp(2, VoteResponse {
+            vote: Vote::new(1, 1),
+            vote_granted: false,
+            last_log_id: Some(log_id(2, 1, 2)),
+        });
+        eng.handle_vote_resp(3, VoteResponse {
+            vote: Vote::new(1, 1),
+            vote_granted: true,
+            last_log_id: None,
+        });
+        assert!(
+            eng.output.take_election_rounds().is_empty(),
+            "the round is not finished"
+        );
+
+        eng.handle_vote_resp(4, VoteResponse {
+            vote: Vote::new(3, 4),
+            vote_granted: false,
+            last_log_id: None,
+        });
+        eng.finish_superseded_election_round();
+
+        assert_eq!(
+            vec![ElectionRound {
+                vote: Vote::new(1, 1),
+                last_log_id: Some(log_id(0, 0, 0)),
+                requested: btreeset! {2,3,4},
+                replies: btreemap! {
+                    2 => reply(false, Vote::new(1, 1), Some(log_id(2, 1, 2))),
+                    3 => reply(true, Vote::new(1, 1), None),
+                    4 => reply(false, Vote::new(3, 4), None),
+                },
+                outcome: ElectionRoundOutcome::Lost,
+            }],
+            eng.output.take_election_rounds()
+        );
+    }
+
+    tracing::info!("--- granted by a quorum: won");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+        eng.state
+            .membership_state
+            .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m12())));
+
+        eng.elect();
+
+        eng.handle_vote_resp(2, VoteResponse {
+            vote: Vote::new(1, 1),
+            vote_granted: true,
+            last_log_id: None,
+        });
+
+        assert_eq!(
+            vec![ElectionRound {
+                vote: Vote::new(1, 1),
+                last_log_id: Some(log_id(0, 0, 0)),
+                requested: btreeset! {2},
+                replies: btreemap! {2 => reply(true, Vote::new(1, 1), None)},
+                outcome: ElectionRoundOutcome::Won,
+            }],
+            eng.output.take_election_rounds()
+        );
+    }
+
+    Ok(())
+}
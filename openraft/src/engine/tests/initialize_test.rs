@@ -1,3 +1,4 @@
+use maplit::btreemap;
 use maplit::btreeset;
 use pretty_assertions::assert_eq;
 
@@ -8,6 +9,7 @@ use crate::engine::Engine;
 use crate::engine::LogIdList;
 use crate::entry::RaftEntry;
 use crate::error::InitializeError;
+use crate::error::InvalidMembership;
 use crate::error::NotAllowed;
 use crate::error::NotInMembers;
 use crate::raft::VoteRequest;
@@ -191,5 +193,24 @@ fn test_initialize() -> anyhow::Result<()> {
         );
     }
 
+    tracing::info!("--- invalid membership: no voter has a voting weight");
+    {
+        let mut eng = eng();
+        eng.config.id = 1;
+
+        let m = Membership::<UTConfig>::new(vec![btreeset! {1,2}], None).with_weights(btreemap! {1=>0,2=>0});
+        let entry = Entry::new_membership(LogId::default(), m.clone());
+
+        assert_eq!(
+            Err(InitializeError::InvalidMembership(InvalidMembership {
+                membership: m,
+                reason: "voter 1 has a weight of 0".to_string(),
+                committed: None,
+            })),
+            eng.initialize(entry)
+        );
+        assert_eq!(None, eng.state.last_log_id(), "state is not changed");
+    }
+
     Ok(())
 }
@@ -46,6 +46,7 @@ fn eng() -> Engine<UTConfig> {
         last_log_id: Some(log_id(2, 1, 2)),
         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
         snapshot_id: "1-2-3-4".to_string(),
+        checksum: None,
     };
     eng.state.server_state = eng.calc_server_state();
 
@@ -72,6 +73,7 @@ fn test_handle_install_full_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
                 last_log_id: Some(log_id(1, 1, 2)),
                 last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
                 snapshot_id: "1-2-3-4".to_string(),
+                checksum: None,
             },
             snapshot: Box::new(Cursor::new(vec![0u8])),
         },
@@ -83,6 +85,7 @@ fn test_handle_install_full_snapshot_lt_last_snapshot() -> anyhow::Result<()> {
             last_log_id: Some(log_id(2, 1, 2)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m12()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         eng.state.snapshot_meta
     );
@@ -120,6 +123,7 @@ fn test_handle_install_full_snapshot_no_conflict() -> anyhow::Result<()> {
                 last_log_id: Some(log_id(4, 1, 6)),
                 last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
                 snapshot_id: "1-2-3-4".to_string(),
+                checksum: None,
             },
             snapshot: Box::new(Cursor::new(vec![0u8])),
         },
@@ -131,6 +135,7 @@ fn test_handle_install_full_snapshot_no_conflict() -> anyhow::Result<()> {
             last_log_id: Some(log_id(4, 1, 6)),
             last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
             snapshot_id: "1-2-3-4".to_string(),
+            checksum: None,
         },
         eng.state.snapshot_meta
     );
@@ -145,6 +150,7 @@ fn test_handle_install_full_snapshot_no_conflict() -> anyhow::Result<()> {
                         last_log_id: Some(log_id(4, 1, 6)),
                         last_membership: StoredMembership::new(Some(log_id(1, 1, 1)), m1234()),
                         snapshot_id: "1-2-3-4".to_string(),
+                        checksum: None,
                     },
                     snapshot: Box::new(Cursor::new(vec![0u8])),
                 })
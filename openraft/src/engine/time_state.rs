@@ -20,6 +20,23 @@ pub(crate) struct Config {
     /// When a follower or learner perceives an active leader, such as by receiving an AppendEntries
     /// message, it should not grant another candidate to become the leader during this period.
     pub(crate) leader_lease: Duration,
+
+    /// The period after stepping down from leader, during which this node uses the shorter
+    /// `affinity_election_timeout`, so that it is likely to take back the leadership after a brief
+    /// disruption.
+    ///
+    /// `0` disables leader affinity.
+    pub(crate) leader_affinity_window: Duration,
+
+    /// The election timeout used during `leader_affinity_window`.
+    ///
+    /// It is shorter than the `election_timeout` of every node.
+    pub(crate) affinity_election_timeout: Duration,
+
+    /// The extra election timeout for every voter other than this node.
+    ///
+    /// `0` disables scaling election timeout by cluster size.
+    pub(crate) election_timeout_per_voter: Duration,
 }
 
 impl Default for Config {
@@ -28,6 +45,39 @@ impl Default for Config {
             election_timeout: Duration::from_millis(150),
             smaller_log_timeout: Duration::from_millis(200),
             leader_lease: Duration::from_millis(150),
+            leader_affinity_window: Duration::from_millis(0),
+            affinity_election_timeout: Duration::from_millis(75),
+            election_timeout_per_voter: Duration::from_millis(0),
         }
     }
 }
+
+impl Config {
+    /// Returns the extra election timeout for a cluster with `voter_count` voters.
+    pub(crate) fn voter_count_timeout(&self, voter_count: usize) -> Duration {
+        let others = voter_count.saturating_sub(1) as u32;
+        self.election_timeout_per_voter.saturating_mul(others)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Config;
+
+    #[test]
+    fn test_voter_count_timeout() {
+        let c = Config::default();
+        assert_eq!(Duration::from_millis(0), c.voter_count_timeout(5));
+
+        let c = Config {
+            election_timeout_per_voter: Duration::from_millis(10),
+            ..Default::default()
+        };
+        assert_eq!(Duration::from_millis(0), c.voter_count_timeout(0));
+        assert_eq!(Duration::from_millis(0), c.voter_count_timeout(1));
+        assert_eq!(Duration::from_millis(20), c.voter_count_timeout(3));
+        assert_eq!(Duration::from_millis(40), c.voter_count_timeout(5));
+    }
+}
@@ -9,9 +9,12 @@ use crate::Membership;
 use crate::RaftTypeConfig;
 
 pub mod payload;
+mod summary;
 mod traits;
 
 pub use payload::EntryPayload;
+pub use payload::EntryPayloadKind;
+pub use summary::LogEntrySummary;
 pub use traits::FromAppData;
 pub use traits::RaftEntry;
 pub use traits::RaftPayload;
@@ -25,6 +28,15 @@ where C: RaftTypeConfig
 
     /// This entry's payload.
     pub payload: EntryPayload<C>,
+
+    /// An opaque context attached by the application when writing this entry, e.g., a trace
+    /// context for distributed tracing.
+    ///
+    /// It is replicated along with the entry and is available when the entry is applied on every
+    /// node. Raft itself never reads it. Whether it is kept in a snapshot is up to the
+    /// application.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub context: Vec<u8>,
 }
 
 impl<C> Clone for Entry<C>
@@ -36,6 +48,7 @@ where
         Self {
             log_id: self.log_id,
             payload: self.payload.clone(),
+            context: self.context.clone(),
         }
     }
 }
@@ -44,7 +57,12 @@ impl<C> Debug for Entry<C>
 where C: RaftTypeConfig
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Entry").field("log_id", &self.log_id).field("payload", &self.payload).finish()
+        let mut s = f.debug_struct("Entry");
+        s.field("log_id", &self.log_id).field("payload", &self.payload);
+        if !self.context.is_empty() {
+            s.field("context", &self.context);
+        }
+        s.finish()
     }
 }
 
@@ -55,6 +73,7 @@ where C: RaftTypeConfig
         Self {
             log_id: LogId::default(),
             payload: EntryPayload::Blank,
+            context: vec![],
         }
     }
 }
@@ -65,7 +84,7 @@ where
     C: RaftTypeConfig,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.log_id == other.log_id && self.payload == other.payload
+        self.log_id == other.log_id && self.payload == other.payload && self.context == other.context
     }
 }
 
@@ -95,6 +114,10 @@ where C: RaftTypeConfig
     fn get_membership(&self) -> Option<&Membership<C>> {
         self.payload.get_membership()
     }
+
+    fn payload_kind(&self) -> EntryPayloadKind {
+        self.payload.kind()
+    }
 }
 
 impl<C> RaftLogId<C::NodeId> for Entry<C>
@@ -116,6 +139,7 @@ where C: RaftTypeConfig
         Self {
             log_id,
             payload: EntryPayload::Blank,
+            context: vec![],
         }
     }
 
@@ -123,8 +147,17 @@ where C: RaftTypeConfig
         Self {
             log_id,
             payload: EntryPayload::Membership(m),
+            context: vec![],
         }
     }
+
+    fn set_context(&mut self, context: Vec<u8>) {
+        self.context = context;
+    }
+
+    fn context(&self) -> &[u8] {
+        &self.context
+    }
 }
 
 impl<C> FromAppData<C::D> for Entry<C>
@@ -134,6 +167,7 @@ where C: RaftTypeConfig
         Entry {
             log_id: LogId::default(),
             payload: EntryPayload::Normal(d),
+            context: vec![],
         }
     }
 }
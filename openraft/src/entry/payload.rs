@@ -18,6 +18,48 @@ pub enum EntryPayload<C: RaftTypeConfig> {
     Membership(Membership<C>),
 }
 
+/// The kind of [`EntryPayload`], without the payload data.
+///
+/// It is useful to tell apart client entries, membership entries and blank entries when
+/// inspecting the log, e.g., with [`Raft::replay_committed()`].
+///
+/// [`Raft::replay_committed()`]: crate::Raft::replay_committed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum EntryPayloadKind {
+    /// An empty payload committed by a new cluster leader.
+    Blank,
+
+    /// Application data.
+    Normal,
+
+    /// A change-membership log entry.
+    Membership,
+}
+
+impl fmt::Display for EntryPayloadKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryPayloadKind::Blank => write!(f, "blank"),
+            EntryPayloadKind::Normal => write!(f, "normal"),
+            EntryPayloadKind::Membership => write!(f, "membership"),
+        }
+    }
+}
+
+impl<C> EntryPayload<C>
+where C: RaftTypeConfig
+{
+    /// Return the kind of this payload.
+    pub fn kind(&self) -> EntryPayloadKind {
+        match self {
+            EntryPayload::Blank => EntryPayloadKind::Blank,
+            EntryPayload::Normal(_) => EntryPayloadKind::Normal,
+            EntryPayload::Membership(_) => EntryPayloadKind::Membership,
+        }
+    }
+}
+
 impl<C> Clone for EntryPayload<C>
 where
     C: RaftTypeConfig,
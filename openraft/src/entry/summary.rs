@@ -0,0 +1,56 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::entry::EntryPayloadKind;
+use crate::entry::RaftPayload;
+use crate::log_id::RaftLogId;
+use crate::LogId;
+use crate::RaftTypeConfig;
+
+/// A brief description of a log entry, without its application data.
+///
+/// It is returned by [`Raft::uncommitted_entries()`].
+///
+/// [`Raft::uncommitted_entries()`]: crate::Raft::uncommitted_entries
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct LogEntrySummary<C>
+where C: RaftTypeConfig
+{
+    /// The log id of the entry.
+    pub log_id: LogId<C::NodeId>,
+
+    /// The kind of the payload: blank, normal or membership.
+    pub payload_kind: EntryPayloadKind,
+
+    /// The size in bytes of the application data, if it is known.
+    ///
+    /// See [`RaftPayload::app_data_size()`](crate::entry::RaftPayload::app_data_size).
+    pub app_data_size: Option<u64>,
+}
+
+impl<C> LogEntrySummary<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(entry: &C::Entry) -> Self {
+        Self {
+            log_id: *entry.get_log_id(),
+            payload_kind: entry.payload_kind(),
+            app_data_size: entry.app_data_size(),
+        }
+    }
+}
+
+impl<C> fmt::Display for LogEntrySummary<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}(size: {})",
+            self.log_id,
+            self.payload_kind,
+            self.app_data_size.display()
+        )
+    }
+}
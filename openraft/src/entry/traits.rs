@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 use std::fmt::Display;
 
+use crate::entry::payload::EntryPayloadKind;
 use crate::log_id::RaftLogId;
 use crate::LogId;
 use crate::Membership;
@@ -18,6 +19,31 @@ where C: RaftTypeConfig
 
     /// Return `Some(&Membership)` if the entry payload is a membership payload.
     fn get_membership(&self) -> Option<&Membership<C>>;
+
+    /// Return the kind of the payload: blank, normal or membership.
+    ///
+    /// The default implementation derives it from [`is_blank()`](Self::is_blank) and
+    /// [`get_membership()`](Self::get_membership).
+    fn payload_kind(&self) -> EntryPayloadKind {
+        if self.is_blank() {
+            EntryPayloadKind::Blank
+        } else if self.get_membership().is_some() {
+            EntryPayloadKind::Membership
+        } else {
+            EntryPayloadKind::Normal
+        }
+    }
+
+    /// Return the size in bytes of the serialized application data in this payload, if it is
+    /// known.
+    ///
+    /// The leader uses it to reject a client write larger than [`Config::max_entry_size_bytes`]
+    /// before appending it to the log. By default it returns `None` and the size is not checked.
+    ///
+    /// [`Config::max_entry_size_bytes`]: crate::Config::max_entry_size_bytes
+    fn app_data_size(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// Defines operations on an entry.
@@ -35,6 +61,23 @@ where
     ///
     /// The returned instance must return `Some()` for `Self::get_membership()`.
     fn new_membership(log_id: LogId<C::NodeId>, m: Membership<C>) -> Self;
+
+    /// Attach an opaque application context to this entry, such as a trace context.
+    ///
+    /// It is called by [`Raft::client_write_with_context()`] before the entry is appended to the
+    /// log. The context must be stored with the entry, so that it is replicated and available when
+    /// the entry is applied. It must not affect anything else of the entry.
+    ///
+    /// The default implementation discards the context.
+    ///
+    /// [`Raft::client_write_with_context()`]: crate::Raft::client_write_with_context
+    fn set_context(&mut self, _context: Vec<u8>) {}
+
+    /// Returns the application context attached to this entry, or an empty slice if there is
+    /// none.
+    fn context(&self) -> &[u8] {
+        &[]
+    }
 }
 
 /// Build a raft log entry from app data.
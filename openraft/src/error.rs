@@ -20,6 +20,7 @@ use crate::try_as_ref::TryAsRef;
 use crate::LogId;
 use crate::Membership;
 use crate::RaftTypeConfig;
+use crate::SnapshotId;
 use crate::StorageError;
 use crate::Vote;
 
@@ -115,6 +116,19 @@ where C: RaftTypeConfig
     }
 }
 
+impl<C> RaftError<C, Fatal<C>>
+where C: RaftTypeConfig
+{
+    /// Convert a [`Fatal`] returned as an API error, e.g., by a node in degraded mode, to
+    /// [`RaftError::Fatal`].
+    pub(crate) fn flatten_fatal(self) -> RaftError<C> {
+        match self {
+            RaftError::APIError(f) => RaftError::Fatal(f),
+            RaftError::Fatal(f) => RaftError::Fatal(f),
+        }
+    }
+}
+
 /// Fatal is unrecoverable and shuts down raft at once.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
@@ -139,6 +153,10 @@ where C: RaftTypeConfig
 pub enum InstallSnapshotError {
     #[error(transparent)]
     SnapshotMismatch(#[from] SnapshotMismatch),
+
+    /// The received snapshot data does not match the checksum computed by the sender.
+    #[error(transparent)]
+    ChecksumMismatch(#[from] SnapshotChecksumMismatch),
 }
 
 /// An error related to a is_leader request.
@@ -152,6 +170,10 @@ where C: RaftTypeConfig
 
     #[error(transparent)]
     QuorumNotEnough(#[from] QuorumNotEnough<C>),
+
+    /// A new leader is not yet ready to serve lease reads.
+    #[error(transparent)]
+    LeaderNotReady(#[from] LeaderNotReady<C>),
 }
 
 impl<C> TryAsRef<ForwardToLeader<C>> for CheckIsLeaderError<C>
@@ -165,6 +187,114 @@ where C: RaftTypeConfig
     }
 }
 
+/// An error when replaying committed log entries with [`Raft::replay_committed()`].
+///
+/// [`Raft::replay_committed()`]: crate::Raft::replay_committed
+#[derive(Debug, Clone, thiserror::Error, derive_more::TryInto)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum ReplayError<C>
+where C: RaftTypeConfig
+{
+    #[error(transparent)]
+    LogPurged(#[from] LogPurged<C>),
+
+    /// Failed to read log entries from the storage.
+    #[error(transparent)]
+    StorageError(#[from] StorageError<C::NodeId>),
+}
+
+/// An error when performing a linearizable read on a follower with
+/// [`Raft::follower_read_linearizable()`].
+///
+/// [`Raft::follower_read_linearizable()`]: crate::Raft::follower_read_linearizable
+#[derive(Debug, Clone, thiserror::Error, derive_more::TryInto)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum FollowerReadError<C>
+where C: RaftTypeConfig
+{
+    /// The leader is unknown, or the leader failed to confirm its leadership.
+    #[error(transparent)]
+    CheckIsLeaderError(#[from] CheckIsLeaderError<C>),
+
+    /// Failed to send the ReadIndex request to the leader.
+    #[error(transparent)]
+    RPCError(#[from] RPCError<C, RaftError<C, CheckIsLeaderError<C>>>),
+
+    /// The local state machine does not apply up to the read log id in time.
+    #[error(transparent)]
+    Timeout(#[from] FollowerReadTimeout<C>),
+}
+
+impl<C> From<ForwardToLeader<C>> for FollowerReadError<C>
+where C: RaftTypeConfig
+{
+    fn from(e: ForwardToLeader<C>) -> Self {
+        Self::CheckIsLeaderError(e.into())
+    }
+}
+
+impl<C> TryAsRef<ForwardToLeader<C>> for FollowerReadError<C>
+where C: RaftTypeConfig
+{
+    fn try_as_ref(&self) -> Option<&ForwardToLeader<C>> {
+        match self {
+            Self::CheckIsLeaderError(e) => e.try_as_ref(),
+            Self::RPCError(e) => e.forward_to_leader(),
+            Self::Timeout(_) => None,
+        }
+    }
+}
+
+/// An error when reading the uncommitted log entries with [`Raft::uncommitted_entries()`].
+///
+/// [`Raft::uncommitted_entries()`]: crate::Raft::uncommitted_entries
+#[derive(Debug, Clone, thiserror::Error, derive_more::TryInto)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum UncommittedEntriesError<C>
+where C: RaftTypeConfig
+{
+    #[error(transparent)]
+    ForwardToLeader(#[from] ForwardToLeader<C>),
+
+    /// Failed to read log entries from the storage.
+    #[error(transparent)]
+    StorageError(#[from] StorageError<C::NodeId>),
+}
+
+/// An error when waiting for a replication target to become line-rate with
+/// [`Raft::await_line_rate()`].
+///
+/// [`Raft::await_line_rate()`]: crate::Raft::await_line_rate
+#[derive(Debug, Clone, thiserror::Error, derive_more::TryInto)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum LineRateError<C>
+where C: RaftTypeConfig
+{
+    #[error(transparent)]
+    ForwardToLeader(#[from] ForwardToLeader<C>),
+
+    /// The target is not in the membership config, e.g., it has been removed.
+    #[error(transparent)]
+    NotInMembers(#[from] NotInMembers<C>),
+
+    #[error(transparent)]
+    Timeout(#[from] LineRateTimeout<C>),
+}
+
+impl<C> TryAsRef<ForwardToLeader<C>> for LineRateError<C>
+where C: RaftTypeConfig
+{
+    fn try_as_ref(&self) -> Option<&ForwardToLeader<C>> {
+        match self {
+            Self::ForwardToLeader(f) => Some(f),
+            _ => None,
+        }
+    }
+}
+
 /// An error related to a client write request.
 #[derive(Debug, Clone, thiserror::Error, derive_more::TryInto)]
 #[derive(PartialEq, Eq)]
@@ -178,6 +308,40 @@ where C: RaftTypeConfig
     /// When writing a change-membership entry.
     #[error(transparent)]
     ChangeMembershipError(#[from] ChangeMembershipError<C>),
+
+    /// The application data is larger than [`Config::max_entry_size_bytes`].
+    ///
+    /// [`Config::max_entry_size_bytes`]: crate::config::Config::max_entry_size_bytes
+    #[error(transparent)]
+    EntryTooLarge(#[from] EntryTooLarge),
+
+    /// The leader has not yet been acknowledged by a quorum in its term, and
+    /// [`Config::require_quorum_before_writes`] is enabled; or the leader is draining, see
+    /// [`Raft::drain_and_shutdown()`].
+    ///
+    /// [`Config::require_quorum_before_writes`]: crate::config::Config::require_quorum_before_writes
+    /// [`Raft::drain_and_shutdown()`]: crate::Raft::drain_and_shutdown
+    #[error(transparent)]
+    NotReady(#[from] NotReady<C>),
+
+    /// The leader accepts writes faster than [`Config::max_write_rate`].
+    ///
+    /// [`Config::max_write_rate`]: crate::config::Config::max_write_rate
+    #[error(transparent)]
+    RateLimited(#[from] RateLimited),
+
+    /// The log on the leader holds more entries than [`Config::max_log_entries`].
+    ///
+    /// [`Config::max_log_entries`]: crate::config::Config::max_log_entries
+    #[error(transparent)]
+    LogFull(#[from] LogFull),
+
+    /// A membership change is in progress and
+    /// [`Config::reject_writes_during_membership_change`] is enabled.
+    ///
+    /// [`Config::reject_writes_during_membership_change`]: crate::config::Config::reject_writes_during_membership_change
+    #[error(transparent)]
+    MembershipChanging(#[from] MembershipChanging<C>),
 }
 
 impl<C> TryAsRef<ForwardToLeader<C>> for ClientWriteError<C>
@@ -199,10 +363,61 @@ pub enum ChangeMembershipError<C: RaftTypeConfig> {
     InProgress(#[from] InProgress<C>),
 
     #[error(transparent)]
-    EmptyMembership(#[from] EmptyMembership),
+    EmptyMembership(#[from] EmptyMembership<C>),
 
     #[error(transparent)]
     LearnerNotFound(#[from] LearnerNotFound<C>),
+
+    #[error(transparent)]
+    LearnerNotReady(#[from] LearnerNotReady<C>),
+
+    #[error(transparent)]
+    NotLearner(#[from] NotLearner<C>),
+
+    #[error(transparent)]
+    InvalidMembership(#[from] InvalidMembership<C>),
+}
+
+impl<C: RaftTypeConfig> ChangeMembershipError<C> {
+    /// Returns the log id of the committed membership config that the rejected change is based on.
+    ///
+    /// A caller can compare it with the membership it knows, to find out whether its view of the
+    /// cluster is stale.
+    pub fn committed(&self) -> Option<&LogId<C::NodeId>> {
+        match self {
+            ChangeMembershipError::InProgress(e) => e.committed.as_ref(),
+            ChangeMembershipError::EmptyMembership(e) => e.committed.as_ref(),
+            ChangeMembershipError::LearnerNotFound(e) => e.committed.as_ref(),
+            ChangeMembershipError::LearnerNotReady(e) => e.committed.as_ref(),
+            ChangeMembershipError::NotLearner(e) => e.committed.as_ref(),
+            ChangeMembershipError::InvalidMembership(e) => e.committed.as_ref(),
+        }
+    }
+
+    /// Returns `true` if the change is rejected for a transient reason, and the same change may
+    /// succeed if it is retried later.
+    ///
+    /// A change is retriable if a previous membership change is not yet committed, or a learner
+    /// to become a voter has not yet caught up with the leader.
+    pub fn is_retriable(&self) -> bool {
+        matches!(
+            self,
+            ChangeMembershipError::InProgress(_) | ChangeMembershipError::LearnerNotReady(_)
+        )
+    }
+
+    /// Set the log id of the committed membership config for errors built without it.
+    pub(crate) fn with_committed(mut self, committed: Option<LogId<C::NodeId>>) -> Self {
+        match &mut self {
+            ChangeMembershipError::InProgress(_) => {}
+            ChangeMembershipError::EmptyMembership(e) => e.committed = committed,
+            ChangeMembershipError::LearnerNotFound(e) => e.committed = committed,
+            ChangeMembershipError::LearnerNotReady(e) => e.committed = committed,
+            ChangeMembershipError::NotLearner(e) => e.committed = committed,
+            ChangeMembershipError::InvalidMembership(e) => e.committed = committed,
+        }
+        self
+    }
 }
 
 /// The set of errors which may take place when initializing a pristine Raft node.
@@ -216,6 +431,9 @@ where C: RaftTypeConfig
 
     #[error(transparent)]
     NotInMembers(#[from] NotInMembers<C>),
+
+    #[error(transparent)]
+    InvalidMembership(#[from] InvalidMembership<C>),
 }
 
 /// Error variants related to the Replication.
@@ -237,6 +455,19 @@ where C: RaftTypeConfig
 
     #[error(transparent)]
     RPCError(#[from] RPCError<C, RaftError<C, Infallible>>),
+
+    /// The logs to replicate are found purged when reading them from the log store.
+    ///
+    /// The target has to be replicated with a snapshot instead.
+    #[error("log at index {needed_index} to replicate is purged, last purged index: {snapshot_index:?}")]
+    LogPurged {
+        /// The index of the first log to replicate.
+        needed_index: u64,
+
+        /// The index of the last log that is purged and is only included in a snapshot, if it is
+        /// known from the log store.
+        snapshot_index: Option<u64>,
+    },
 }
 
 /// Error occurs when invoking a remote raft API.
@@ -266,6 +497,10 @@ pub enum RPCError<C: RaftTypeConfig, E: Error> {
     #[error(transparent)]
     Network(#[from] NetworkError),
 
+    /// The response can not be decoded; should backoff before retrying.
+    #[error(transparent)]
+    Malformed(#[from] Malformed),
+
     #[error(transparent)]
     RemoteError(#[from] RemoteError<C, E>),
 }
@@ -283,6 +518,7 @@ where
             RPCError::Unreachable(_) => None,
             RPCError::PayloadTooLarge(_) => None,
             RPCError::Network(_) => None,
+            RPCError::Malformed(_) => None,
             RPCError::RemoteError(remote_err) => remote_err.source.forward_to_leader(),
         }
     }
@@ -386,6 +622,30 @@ impl Unreachable {
     }
 }
 
+/// Error indicating the response from a remote node can not be decoded.
+///
+/// Unlike [`NetworkError`] or [`Unreachable`], this is not a transient transport failure: it
+/// usually means the peers disagree about the wire format, e.g., they run incompatible versions.
+/// Openraft logs it at error level and will [`backoff()`] before retrying, since an immediate
+/// retry is unlikely to succeed.
+///
+/// [`backoff()`]: crate::network::RaftNetwork::backoff
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("Malformed response: {source}")]
+pub struct Malformed {
+    #[from]
+    source: AnyError,
+}
+
+impl Malformed {
+    pub fn new<E: Error + 'static>(e: &E) -> Self {
+        Self {
+            source: AnyError::new(e),
+        }
+    }
+}
+
 /// Error indicating that an RPC is too large and cannot be sent.
 ///
 /// This is a retryable error:
@@ -456,6 +716,9 @@ impl fmt::Display for PayloadTooLarge {
             RPCTypes::InstallSnapshot => {
                 write!(f, "bytes:{}", self.bytes_hint)?;
             }
+            RPCTypes::ReadIndex => {
+                unreachable!("read index rpc should not have payload")
+            }
         }
         write!(f, ")")?;
 
@@ -526,6 +789,35 @@ pub struct Timeout<C: RaftTypeConfig> {
     pub timeout: Duration,
 }
 
+/// The replication to `target` does not become line-rate within `timeout`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("timeout after {timeout:?} when waiting for node {target} to become line-rate, matching: {matching:?}")]
+pub struct LineRateTimeout<C: RaftTypeConfig> {
+    pub target: C::NodeId,
+    pub timeout: Duration,
+
+    /// The last known matching log id of the target.
+    pub matching: Option<LogId<C::NodeId>>,
+}
+
+/// The local state machine does not apply up to the read log id within `timeout`, when reading
+/// with [`Raft::follower_read_linearizable()`].
+///
+/// [`Raft::follower_read_linearizable()`]: crate::Raft::follower_read_linearizable
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("timeout after {timeout:?} when waiting to apply up to read log id {read_log_id:?}, applied: {applied:?}")]
+pub struct FollowerReadTimeout<C: RaftTypeConfig> {
+    pub timeout: Duration,
+
+    /// The log id the state machine has to apply up to before serving the read.
+    pub read_log_id: Option<LogId<C::NodeId>>,
+
+    /// The last applied log id when the timeout happens.
+    pub applied: Option<LogId<C::NodeId>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 #[error("has to forward request to: {leader_id:?}, {leader_node:?}")]
@@ -562,6 +854,30 @@ pub struct SnapshotMismatch {
     pub got: SnapshotSegmentId,
 }
 
+/// The state machine type passed to [`Raft::with_state_machine()`] is not the one the `Raft` is
+/// created with.
+///
+/// [`Raft::with_state_machine()`]: crate::Raft::with_state_machine
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid state machine type, expect: {expect}, got: {got}")]
+pub struct InvalidStateMachineType {
+    pub expect: &'static str,
+    pub got: &'static str,
+}
+
+/// The received snapshot data is corrupted or truncated: its checksum does not match the one in
+/// [`SnapshotMeta::checksum`].
+///
+/// [`SnapshotMeta::checksum`]: crate::SnapshotMeta::checksum
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("snapshot {snapshot_id} checksum mismatch, expect: {expect:#x}, got: {got:#x}")]
+pub struct SnapshotChecksumMismatch {
+    pub snapshot_id: SnapshotId,
+    pub expect: u64,
+    pub got: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 #[error("not enough for a quorum, cluster: {cluster}, got: {got:?}")]
@@ -570,6 +886,22 @@ pub struct QuorumNotEnough<C: RaftTypeConfig> {
     pub got: BTreeSet<C::NodeId>,
 }
 
+/// A new leader can not yet serve lease reads: it has not committed the blank log of its term, or
+/// it is still in [`Config::read_lease_warmup`].
+///
+/// [`Config::read_lease_warmup`]: crate::Config::read_lease_warmup
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("leader is not ready to serve lease reads: blank log: {noop_log_id:?}, committed: {committed:?}, elapsed: {elapsed:?}, warm-up: {warmup:?}")]
+pub struct LeaderNotReady<C: RaftTypeConfig> {
+    /// The blank log proposed by this leader when it is established.
+    pub noop_log_id: Option<LogId<C::NodeId>>,
+    pub committed: Option<LogId<C::NodeId>>,
+    /// The time elapsed since this node became leader.
+    pub elapsed: Duration,
+    pub warmup: Duration,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 #[error("the cluster is already undergoing a configuration change at log {membership_log_id:?}, last committed membership log id: {committed:?}")]
@@ -580,9 +912,46 @@ pub struct InProgress<C: RaftTypeConfig> {
 
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
-#[error("Learner {node_id} not found: add it as learner before adding it as a voter")]
+#[error("Learner {node_id} not found: add it as learner before adding it as a voter; committed membership log id: {committed:?}")]
 pub struct LearnerNotFound<C: RaftTypeConfig> {
     pub node_id: C::NodeId,
+    /// The log id of the committed membership config.
+    pub committed: Option<LogId<C::NodeId>>,
+}
+
+/// A learner to become a voter is not yet line-rate, i.e., its replication lags more than
+/// [`Config::replication_lag_threshold`] behind the leader.
+///
+/// It is returned only if [`Config::reject_lagging_new_voters`] is enabled. Counting such a node
+/// in the quorum stalls commits until it catches up. Wait for it with
+/// [`Raft::await_line_rate()`] before promoting it.
+///
+/// [`Config::replication_lag_threshold`]: crate::config::Config::replication_lag_threshold
+/// [`Config::reject_lagging_new_voters`]: crate::config::Config::reject_lagging_new_voters
+/// [`Raft::await_line_rate()`]: crate::Raft::await_line_rate
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("Learner {node_id} is not ready to become a voter: matching: {matching:?}, leader last log index: {last_log_index:?}; committed membership log id: {committed:?}")]
+pub struct LearnerNotReady<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+    /// The last log id known to be replicated to the learner.
+    pub matching: Option<LogId<C::NodeId>>,
+    /// The last log index on the leader.
+    pub last_log_index: Option<u64>,
+    /// The log id of the committed membership config.
+    pub committed: Option<LogId<C::NodeId>>,
+}
+
+/// The node to promote with [`Raft::promote_learner()`] is already a voter.
+///
+/// [`Raft::promote_learner()`]: crate::Raft::promote_learner
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("Node {node_id} is not a learner: it is already a voter; committed membership log id: {committed:?}")]
+pub struct NotLearner<C: RaftTypeConfig> {
+    pub node_id: C::NodeId,
+    /// The log id of the committed membership config.
+    pub committed: Option<LogId<C::NodeId>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
@@ -603,10 +972,136 @@ where C: RaftTypeConfig
     pub membership: Membership<C>,
 }
 
+/// A membership config is internally inconsistent, and would make the quorum undefined.
+///
+/// E.g., a joint config of more than two configs, or a config in which no voter has a voting
+/// weight.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("invalid membership: {reason}; membership: {membership}; committed membership log id: {committed:?}")]
+pub struct InvalidMembership<C: RaftTypeConfig> {
+    pub membership: Membership<C>,
+    pub reason: String,
+    /// The log id of the committed membership config.
+    pub committed: Option<LogId<C::NodeId>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("new membership can not be empty; committed membership log id: {committed:?}")]
+pub struct EmptyMembership<C: RaftTypeConfig> {
+    /// The log id of the committed membership config.
+    pub committed: Option<LogId<C::NodeId>>,
+}
+
+/// A client write is rejected by the leader before being appended to the log, because its
+/// application data is larger than [`Config::max_entry_size_bytes`].
+///
+/// [`Config::max_entry_size_bytes`]: crate::config::Config::max_entry_size_bytes
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("entry too large: size: {size} bytes, max: {max} bytes")]
+pub struct EntryTooLarge {
+    /// The size of the application data in bytes.
+    pub size: u64,
+
+    /// The max allowed size in bytes.
+    pub max: u64,
+}
+
+/// A client write is rejected because the leader accepts writes faster than
+/// [`Config::max_write_rate`].
+///
+/// The client may retry after a while.
+///
+/// [`Config::max_write_rate`]: crate::config::Config::max_write_rate
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[error("new membership can not be empty")]
-pub struct EmptyMembership {}
+#[error("write rate exceeds the limit: {max_rate} entries per second")]
+pub struct RateLimited {
+    /// The max allowed number of writes per second.
+    pub max_rate: u64,
+}
+
+/// A client write is rejected because the log on the leader holds more entries than
+/// [`Config::max_log_entries`].
+///
+/// A snapshot is triggered to compact the log; the client may retry after the log is purged.
+///
+/// [`Config::max_log_entries`]: crate::config::Config::max_log_entries
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("log is full: {entries} entries, max: {max} entries")]
+pub struct LogFull {
+    /// The number of entries in the log that are not yet purged.
+    pub entries: u64,
+
+    /// The max allowed number of entries.
+    pub max: u64,
+}
+
+/// A client write is rejected because a membership change is in progress, and
+/// [`Config::reject_writes_during_membership_change`] is enabled.
+///
+/// The client may retry after the membership change is committed.
+///
+/// [`Config::reject_writes_during_membership_change`]: crate::config::Config::reject_writes_during_membership_change
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("membership change in progress at log {membership_log_id:?}, last committed membership log id: {committed:?}")]
+pub struct MembershipChanging<C: RaftTypeConfig> {
+    /// The log id of the effective membership config that is not yet committed.
+    pub membership_log_id: Option<LogId<C::NodeId>>,
+
+    /// The log id of the last committed membership config.
+    pub committed: Option<LogId<C::NodeId>>,
+}
+
+/// The log entry to read is already purged, because it is included in a snapshot.
+///
+/// The caller should install the state from the snapshot and continue reading logs after
+/// `last_purged_log_id`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("log at index {index} is purged, last purged: {last_purged_log_id}; install from a snapshot instead")]
+pub struct LogPurged<C: RaftTypeConfig> {
+    /// The index of the log entry to read.
+    pub index: u64,
+
+    /// The last log id that is purged.
+    pub last_purged_log_id: LogId<C::NodeId>,
+}
+
+/// A client write is rejected because the leader is not ready for writes: it has not yet been
+/// acknowledged by a quorum since it is elected, or it is draining before shutdown, see
+/// [`Raft::drain_and_shutdown()`].
+///
+/// The client may retry after a while, or on another node.
+///
+/// [`Raft::drain_and_shutdown()`]: crate::Raft::drain_and_shutdown
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("leader is not ready for writes: vote {vote}")]
+pub struct NotReady<C: RaftTypeConfig> {
+    /// The vote of the leader.
+    pub vote: Vote<C::NodeId>,
+}
+
+/// The term of a log entry in an AppendEntries request is less than the term of the log id before
+/// it, which is either the previous entry or `prev_log_id`.
+///
+/// Terms of a leader's log never decrease, thus such a request is corrupted and is rejected by the
+/// follower without storing any entry in it.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("term regression in AppendEntries: log id {log_id} follows {prev}")]
+pub struct TermRegression<C: RaftTypeConfig> {
+    /// The log id immediately before `log_id`.
+    pub prev: LogId<C::NodeId>,
+
+    /// The log id whose term is less than the term of `prev`.
+    pub log_id: LogId<C::NodeId>,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -653,6 +1148,17 @@ pub(crate) enum RejectAppendEntries<C: RaftTypeConfig> {
         expect: LogId<C::NodeId>,
         local: Option<LogId<C::NodeId>>,
     },
+
+    #[error("reject AppendEntries because of {0}")]
+    ByTermRegression(#[from] TermRegression<C>),
+
+    /// The entries are not consecutive from `prev_log_id`, appending them would leave a gap in the
+    /// log.
+    #[error("reject AppendEntries because of a log gap: expect index {expect_index}, got: {log_id}")]
+    ByLogGap {
+        expect_index: u64,
+        log_id: LogId<C::NodeId>,
+    },
 }
 
 impl<C> From<RejectVoteRequest<C>> for RejectAppendEntries<C>
@@ -677,6 +1183,8 @@ where C: RaftTypeConfig
             Err(e) => match e {
                 RejectAppendEntries::ByVote(v) => AppendEntriesResponse::HigherVote(v),
                 RejectAppendEntries::ByConflictingLogId { expect: _, local: _ } => AppendEntriesResponse::Conflict,
+                RejectAppendEntries::ByTermRegression(e) => AppendEntriesResponse::TermRegression(e),
+                RejectAppendEntries::ByLogGap { .. } => AppendEntriesResponse::Conflict,
             },
         }
     }
@@ -1,5 +1,6 @@
 use std::error::Error;
 
+use crate::error::Malformed;
 use crate::error::NetworkError;
 use crate::error::RPCError;
 use crate::error::RaftError;
@@ -42,6 +43,10 @@ pub enum StreamingError<C: RaftTypeConfig, E: Error> {
     #[error(transparent)]
     Network(#[from] NetworkError),
 
+    /// The response can not be decoded; should backoff before retrying.
+    #[error(transparent)]
+    Malformed(#[from] Malformed),
+
     /// Remote node returns an error.
     #[error(transparent)]
     RemoteError(#[from] RemoteError<C, E>),
@@ -59,6 +64,7 @@ where
             StreamingError::Timeout(e) => ReplicationError::RPCError(RPCError::Timeout(e)),
             StreamingError::Unreachable(e) => ReplicationError::RPCError(RPCError::Unreachable(e)),
             StreamingError::Network(e) => ReplicationError::RPCError(RPCError::Network(e)),
+            StreamingError::Malformed(e) => ReplicationError::RPCError(RPCError::Malformed(e)),
             StreamingError::RemoteError(e) => {
                 let remote_err = RemoteError {
                     target: e.target,
@@ -1,10 +1,12 @@
+use std::collections::BTreeMap;
+
 use crate::leader::voting::Voting;
 use crate::leader::Leading;
 use crate::quorum::Joint;
 use crate::RaftTypeConfig;
 
 /// The quorum set type used by `Leader`.
-pub(crate) type LeaderQuorumSet<NID> = Joint<NID, Vec<NID>, Vec<Vec<NID>>>;
+pub(crate) type LeaderQuorumSet<NID> = Joint<NID, BTreeMap<NID, u64>, Vec<BTreeMap<NID, u64>>>;
 
 /// In openraft there are only two state for a server:
 /// Leading(raft leader or raft candidate) and following(raft follower or raft learner):
@@ -1,6 +1,8 @@
+use std::collections::BTreeSet;
 use std::fmt;
 
 use crate::leader::voting::Voting;
+use crate::leader::write_rate_limiter::WriteRateLimiter;
 use crate::progress::entry::ProgressEntry;
 use crate::progress::Progress;
 use crate::progress::VecProgress;
@@ -54,6 +56,22 @@ where C: RaftTypeConfig
     ///
     /// [`docs::leader_lease`]: `crate::docs::protocol::replication::leader_lease`
     pub(crate) clock_progress: VecProgress<C::NodeId, Option<InstantOf<C>>, Option<InstantOf<C>>, QS>,
+
+    /// Limits the rate of client writes, see [`Config::max_write_rate`].
+    ///
+    /// [`Config::max_write_rate`]: crate::Config::max_write_rate
+    pub(crate) write_rate_limiter: WriteRateLimiter<C>,
+
+    /// Targets whose replication progress is to be reset once the data in flight is acknowledged,
+    /// see [`Raft::resync_follower()`].
+    ///
+    /// [`Raft::resync_follower()`]: crate::Raft::resync_follower
+    pub(crate) pending_resyncs: BTreeSet<C::NodeId>,
+
+    /// The voters that had accepted the committed log id when it was last advanced by this leader.
+    ///
+    /// It contains the quorum that committed it, and may be larger if more voters had accepted it.
+    pub(crate) commit_quorum: BTreeSet<C::NodeId>,
 }
 
 impl<C, QS> Leading<C, QS>
@@ -80,6 +98,9 @@ where
                 ProgressEntry::empty(last_log_id.next_index()),
             ),
             clock_progress: VecProgress::new(quorum_set, learner_ids, None),
+            write_rate_limiter: WriteRateLimiter::new(),
+            pending_resyncs: BTreeSet::new(),
+            commit_quorum: BTreeSet::new(),
         }
     }
 
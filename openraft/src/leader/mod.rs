@@ -1,4 +1,5 @@
 #[allow(clippy::module_inception)] mod leader;
 pub(crate) mod voting;
+pub(crate) mod write_rate_limiter;
 
 pub(crate) use leader::Leading;
@@ -0,0 +1,115 @@
+use crate::type_config::alias::InstantOf;
+use crate::RaftTypeConfig;
+
+/// Number of token units that one entry costs.
+///
+/// Tokens are refilled at `rate` units per nanosecond, so that one entry is refilled every
+/// `1/rate` second without accumulating rounding errors.
+const UNITS_PER_ENTRY: u64 = 1_000_000_000;
+
+/// A token bucket that limits the number of client writes a leader accepts per second.
+///
+/// The bucket holds at most one second worth of entries. It starts full, and since it is part of
+/// the leader state, it is reset when a new leader is established.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+pub(crate) struct WriteRateLimiter<C>
+where C: RaftTypeConfig
+{
+    /// Available token units, `None` if no write has been made, i.e., the bucket is full.
+    tokens: Option<u64>,
+
+    /// The last time the tokens are refilled.
+    last_refill: Option<InstantOf<C>>,
+}
+
+impl<C> WriteRateLimiter<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            tokens: None,
+            last_refill: None,
+        }
+    }
+
+    /// Take a token for one entry, with at most `rate` entries allowed per second.
+    ///
+    /// It returns `false` if there is no token available and the write should be rejected.
+    pub(crate) fn try_acquire(&mut self, rate: u64, now: InstantOf<C>) -> bool {
+        self.try_acquire_n(rate, 1, now)
+    }
+
+    /// Take tokens for `n` entries at once, or none of them if there are not enough tokens.
+    pub(crate) fn try_acquire_n(&mut self, rate: u64, n: u64, now: InstantOf<C>) -> bool {
+        let cost = n.saturating_mul(UNITS_PER_ENTRY);
+        let capacity = rate.saturating_mul(UNITS_PER_ENTRY);
+
+        let mut tokens = self.tokens.unwrap_or(capacity);
+
+        if let Some(last) = self.last_refill {
+            if now > last {
+                let elapsed = (now - last).as_nanos();
+                let refill = u64::try_from(elapsed).unwrap_or(u64::MAX).saturating_mul(rate);
+                tokens = tokens.saturating_add(refill);
+            }
+        }
+
+        tokens = std::cmp::min(tokens, capacity);
+        self.last_refill = Some(now);
+
+        if tokens < cost {
+            self.tokens = Some(tokens);
+            return false;
+        }
+
+        self.tokens = Some(tokens - cost);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::engine::testing::UTConfig;
+    use crate::leader::write_rate_limiter::WriteRateLimiter;
+    use crate::type_config::alias::InstantOf;
+
+    #[test]
+    fn test_write_rate_limiter() {
+        let mut limiter = WriteRateLimiter::<UTConfig>::new();
+        let now = InstantOf::<UTConfig>::now();
+
+        assert!(limiter.try_acquire(2, now));
+        assert!(limiter.try_acquire(2, now));
+        assert!(!limiter.try_acquire(2, now), "bucket is empty");
+
+        let now = now + Duration::from_millis(499);
+        assert!(!limiter.try_acquire(2, now), "not yet refilled");
+
+        let now = now + Duration::from_millis(1);
+        assert!(limiter.try_acquire(2, now), "one entry is refilled in 500 ms");
+        assert!(!limiter.try_acquire(2, now));
+
+        let now = now + Duration::from_secs(10);
+        assert!(limiter.try_acquire(2, now));
+        assert!(limiter.try_acquire(2, now));
+        assert!(
+            !limiter.try_acquire(2, now),
+            "at most one second worth of entries are refilled"
+        );
+    }
+
+    #[test]
+    fn test_write_rate_limiter_acquire_n() {
+        let mut limiter = WriteRateLimiter::<UTConfig>::new();
+        let now = InstantOf::<UTConfig>::now();
+
+        assert!(!limiter.try_acquire_n(3, 4, now), "more than the capacity");
+        assert!(limiter.try_acquire_n(3, 2, now));
+        assert!(!limiter.try_acquire_n(3, 2, now), "not enough, nothing is taken");
+        assert!(limiter.try_acquire_n(3, 1, now));
+        assert!(!limiter.try_acquire(3, now), "bucket is empty");
+    }
+}
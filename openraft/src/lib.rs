@@ -75,10 +75,13 @@ pub use crate::async_runtime::TokioRuntime;
 pub use crate::change_members::ChangeMembers;
 pub use crate::config::Config;
 pub use crate::config::ConfigError;
+pub use crate::config::LogFlushMode;
 pub use crate::config::SnapshotPolicy;
 pub use crate::core::ServerState;
 pub use crate::entry::Entry;
 pub use crate::entry::EntryPayload;
+pub use crate::entry::EntryPayloadKind;
+pub use crate::entry::LogEntrySummary;
 pub use crate::instant::Instant;
 pub use crate::instant::TokioInstant;
 pub use crate::log_id::LogId;
@@ -97,8 +100,17 @@ pub use crate::node::EmptyNode;
 pub use crate::node::Node;
 pub use crate::node::NodeId;
 pub use crate::raft::Raft;
+pub use crate::raft_state::ClusterCommitView;
+pub use crate::raft_state::Electability;
+pub use crate::raft_state::ElectionOutcome;
+pub use crate::raft_state::LeadershipDiag;
 pub use crate::raft_state::MembershipState;
+pub use crate::raft_state::NodeCommitView;
+pub use crate::raft_state::NodeReplication;
+pub use crate::raft_state::NodeRole;
+pub use crate::raft_state::NodeTopology;
 pub use crate::raft_state::RaftState;
+pub use crate::raft_state::Topology;
 pub use crate::raft_types::SnapshotId;
 pub use crate::raft_types::SnapshotSegmentId;
 pub use crate::storage::LogState;
@@ -160,6 +172,14 @@ impl<T: Send + ?Sized> OptionalSend for T {}
 #[cfg(not(feature = "singlethreaded"))]
 impl<T: Sync + ?Sized> OptionalSync for T {}
 
+/// A pinned and boxed future, which is `Send` unless feature `singlethreaded` is enabled.
+#[cfg(not(feature = "singlethreaded"))]
+pub type BoxFuture<'a, T = ()> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + Send + 'a>>;
+
+/// A pinned and boxed future, which is `Send` unless feature `singlethreaded` is enabled.
+#[cfg(feature = "singlethreaded")]
+pub type BoxFuture<'a, T = ()> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + 'a>>;
+
 /// A trait defining application specific data.
 ///
 /// The intention of this trait is that applications which are using this crate will be able to
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::fmt;
 use std::fmt::Debug;
@@ -25,8 +26,11 @@ where C: RaftTypeConfig
 {
     stored_membership: Arc<StoredMembership<C>>,
 
-    /// The quorum set built from `membership`.
-    quorum_set: Joint<C::NodeId, Vec<C::NodeId>, Vec<Vec<C::NodeId>>>,
+    /// The weighted quorum set built from `membership`.
+    quorum_set: Joint<C::NodeId, BTreeMap<C::NodeId, u64>, Vec<BTreeMap<C::NodeId, u64>>>,
+
+    /// Cache of the joint config in `membership`.
+    joint_config: Vec<Vec<C::NodeId>>,
 
     /// Cache of union of all members
     voter_ids: BTreeSet<C::NodeId>,
@@ -73,16 +77,17 @@ where C: RaftTypeConfig
         let voter_ids = membership.voter_ids().collect();
 
         let configs = membership.get_joint_config();
-        let mut joint = vec![];
+        let mut joint_config = vec![];
         for c in configs {
-            joint.push(c.iter().copied().collect::<Vec<_>>());
+            joint_config.push(c.iter().copied().collect::<Vec<_>>());
         }
 
-        let quorum_set = Joint::from(joint);
+        let quorum_set = membership.to_quorum_set();
 
         Self {
             stored_membership: Arc::new(StoredMembership::new(log_id, membership)),
             quorum_set,
+            joint_config,
             voter_ids,
         }
     }
@@ -138,7 +143,7 @@ where C: RaftTypeConfig
     /// Membership is defined by a joint of multiple configs.
     /// Each config is a vec of node-id.
     pub fn get_joint_config(&self) -> &Vec<Vec<C::NodeId>> {
-        self.quorum_set.children()
+        &self.joint_config
     }
 }
 
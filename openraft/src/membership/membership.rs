@@ -4,9 +4,11 @@ use std::collections::BTreeSet;
 
 use crate::error::ChangeMembershipError;
 use crate::error::EmptyMembership;
+use crate::error::InvalidMembership;
 use crate::error::LearnerNotFound;
 use crate::membership::IntoNodes;
 use crate::quorum::AsJoint;
+use crate::quorum::Coherent;
 use crate::quorum::FindCoherent;
 use crate::quorum::Joint;
 use crate::quorum::QuorumSet;
@@ -31,6 +33,12 @@ where C: RaftTypeConfig
     ///
     /// A node-id key that is in `nodes` but is not in `configs` is a **learner**.
     nodes: BTreeMap<C::NodeId, C::Node>,
+
+    /// The voting weight of voters.
+    ///
+    /// A voter that is not in `weights` has a weight of `1`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    weights: BTreeMap<C::NodeId, u64>,
 }
 
 impl<C> From<BTreeMap<C::NodeId, C::Node>> for Membership<C>
@@ -87,7 +95,20 @@ where C: RaftTypeConfig
                 write!(f, "None")?;
             }
         }
-        write!(f, "]}}")?;
+        write!(f, "]")?;
+
+        if !self.weights.is_empty() {
+            write!(f, ", weights:[")?;
+            for (i, (node_id, weight)) in self.weights.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "{node_id}:{weight}")?;
+            }
+            write!(f, "]")?;
+        }
+
+        write!(f, "}}")?;
         Ok(())
     }
 }
@@ -113,7 +134,31 @@ where C: RaftTypeConfig
         let voter_ids = config.as_joint().ids().collect::<BTreeSet<_>>();
         let nodes = Self::extend_nodes(nodes.into_nodes(), &voter_ids.into_nodes());
 
-        Membership { configs: config, nodes }
+        Membership {
+            configs: config,
+            nodes,
+            weights: BTreeMap::new(),
+        }
+    }
+
+    /// Set the voting weight of voters and return the updated instance.
+    ///
+    /// A quorum of a weighted config is a set of voters whose weights sum up to more than half of
+    /// the total weight of the config. In a joint config, it has to be a quorum in every config.
+    /// A voter that is not in `weights` has a weight of `1`, thus by default every voter has the
+    /// same weight and a quorum is a simple majority.
+    ///
+    /// Weights are kept when the membership is changed, except that the weight of a voter is
+    /// dropped when it is removed from the voters. Every weight must be greater than 0 and belong
+    /// to a voter, otherwise the membership is rejected as invalid.
+    pub fn with_weights(mut self, weights: BTreeMap<C::NodeId, u64>) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Returns the voting weight of a node.
+    pub fn weight(&self, node_id: &C::NodeId) -> u64 {
+        self.weights.get(node_id).copied().unwrap_or(1)
     }
 
     /// Returns reference to the joint config.
@@ -127,6 +172,11 @@ where C: RaftTypeConfig
         &self.configs
     }
 
+    /// Returns true if it is a joint config, i.e., it consists of more than one config.
+    pub fn is_joint(&self) -> bool {
+        self.configs.len() > 1
+    }
+
     /// Returns an Iterator of all nodes(voters and learners).
     pub fn nodes(&self) -> impl Iterator<Item = (&C::NodeId, &C::Node)> {
         self.nodes.iter()
@@ -171,7 +221,11 @@ where C: RaftTypeConfig
     pub(crate) fn new_unchecked<T>(configs: Vec<BTreeSet<C::NodeId>>, nodes: T) -> Self
     where T: IntoNodes<C::NodeId, C::Node> {
         let nodes = nodes.into_nodes();
-        Membership { configs, nodes }
+        Membership {
+            configs,
+            nodes,
+            weights: BTreeMap::new(),
+        }
     }
 
     /// Extends nodes btreemap with another.
@@ -197,17 +251,76 @@ where C: RaftTypeConfig
     /// Ensure the membership config is valid:
     /// - No empty sub-config in it.
     /// - Every voter has a corresponding Node.
+    /// - It is consistent, see [`Self::ensure_consistent()`].
     pub(crate) fn ensure_valid(&self) -> Result<(), ChangeMembershipError<C>> {
         self.ensure_non_empty_config()?;
-        self.ensure_voter_nodes().map_err(|nid| LearnerNotFound { node_id: nid })?;
+        self.ensure_voter_nodes().map_err(|nid| LearnerNotFound {
+            node_id: nid,
+            committed: None,
+        })?;
+        self.ensure_consistent()?;
+        Ok(())
+    }
+
+    /// Ensures that the quorum of this membership is well defined:
+    /// - A joint config has at most two configs: the one being left and the one being entered.
+    /// - Every weight is greater than 0 and belongs to a voter. A config in which every voter has a
+    ///   weight of 0 never has a quorum.
+    pub(crate) fn ensure_consistent(&self) -> Result<(), InvalidMembership<C>> {
+        let invalid = |reason: String| InvalidMembership {
+            membership: self.clone(),
+            reason,
+            committed: None,
+        };
+
+        if self.configs.len() > 2 {
+            return Err(invalid(format!(
+                "a joint config has at most 2 configs, got {}",
+                self.configs.len()
+            )));
+        }
+
+        for (node_id, weight) in self.weights.iter() {
+            if *weight == 0 {
+                return Err(invalid(format!("voter {} has a weight of 0", node_id)));
+            }
+            if !self.is_voter(node_id) {
+                return Err(invalid(format!("node {} has a weight but is not a voter", node_id)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ensures that this membership can be switched to directly from `prev`.
+    ///
+    /// Two memberships are coherent if they share at least one config, so that any quorum of one
+    /// intersects with any quorum of the other. E.g., `{1,2,3}` can be switched to
+    /// `[{1,2,3}, {3,4,5}]`, but not to `{3,4,5}`, even though they share a voter: a quorum `{1,2}`
+    /// of the former does not intersect a quorum `{4,5}` of the latter.
+    pub(crate) fn ensure_coherent_with(&self, prev: &Self) -> Result<(), InvalidMembership<C>> {
+        let prev_joint = Joint::from(prev.configs.clone());
+        let joint = Joint::from(self.configs.clone());
+
+        if !joint.is_coherent_with(&prev_joint) {
+            return Err(InvalidMembership {
+                membership: self.clone(),
+                reason: format!(
+                    "config {:?} is not coherent with the previous config {:?}",
+                    self.configs, prev.configs
+                ),
+                committed: None,
+            });
+        }
+
         Ok(())
     }
 
     /// Ensures that none of the sub config in this joint config are empty.
-    pub(crate) fn ensure_non_empty_config(&self) -> Result<(), EmptyMembership> {
+    pub(crate) fn ensure_non_empty_config(&self) -> Result<(), EmptyMembership<C>> {
         for c in self.get_joint_config().iter() {
             if c.is_empty() {
-                return Err(EmptyMembership {});
+                return Err(EmptyMembership { committed: None });
             }
         }
 
@@ -265,7 +378,13 @@ where C: RaftTypeConfig
             }
         };
 
-        Membership::new_unchecked(config, nodes)
+        // The weight of a removed voter is dropped, so that it does not come back if the same
+        // node is added as a voter again.
+        let mut weights = self.weights.clone();
+        let new_voter_ids = config.as_joint().ids().collect::<BTreeSet<_>>();
+        weights.retain(|node_id, _| new_voter_ids.contains(node_id));
+
+        Membership::new_unchecked(config, nodes).with_weights(weights)
     }
 
     /// Apply a change-membership request and return a new instance.
@@ -282,6 +401,7 @@ where C: RaftTypeConfig
         tracing::debug!(change = debug(&change), "{}", func_name!());
 
         let last = self.get_joint_config().last().unwrap().clone();
+        let prev = self.clone();
 
         let new_membership = match change {
             ChangeMembers::AddVoterIds(add_voter_ids) => {
@@ -330,14 +450,18 @@ where C: RaftTypeConfig
 
         new_membership.ensure_valid()?;
 
+        // Adding or removing several voters at once is still safe only if the old and the new
+        // config are both required to form a quorum while the change is in progress.
+        new_membership.ensure_coherent_with(&prev)?;
+
         Ok(new_membership)
     }
 
-    /// Build a QuorumSet from current joint config
-    pub(crate) fn to_quorum_set(&self) -> Joint<C::NodeId, Vec<C::NodeId>, Vec<Vec<C::NodeId>>> {
+    /// Build a weighted QuorumSet from current joint config
+    pub(crate) fn to_quorum_set(&self) -> Joint<C::NodeId, BTreeMap<C::NodeId, u64>, Vec<BTreeMap<C::NodeId, u64>>> {
         let mut qs = vec![];
         for c in self.get_joint_config().iter() {
-            qs.push(c.iter().copied().collect::<Vec<_>>());
+            qs.push(c.iter().map(|id| (*id, self.weight(id))).collect::<BTreeMap<_, _>>());
         }
         Joint::new(qs)
     }
@@ -345,6 +469,8 @@ where C: RaftTypeConfig
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use maplit::btreemap;
     use maplit::btreeset;
 
@@ -352,6 +478,7 @@ mod tests {
     use crate::error::ChangeMembershipError;
     use crate::error::EmptyMembership;
     use crate::error::LearnerNotFound;
+    use crate::quorum::QuorumSet;
     use crate::ChangeMembers;
     use crate::Membership;
 
@@ -360,23 +487,65 @@ mod tests {
         let m = Membership::<UTConfig> {
             configs: vec![btreeset! {1,2}],
             nodes: btreemap! {1=>()},
+            weights: btreemap! {},
         };
         assert_eq!(Err(2), m.ensure_voter_nodes());
         Ok(())
     }
 
+    #[test]
+    fn test_membership_ensure_consistent() -> anyhow::Result<()> {
+        let m = Membership::<UTConfig>::new(vec![btreeset! {1,2}, btreeset! {2,3}], None);
+        assert!(m.ensure_consistent().is_ok());
+
+        // More than 2 configs
+        let m = Membership::<UTConfig>::new(vec![btreeset! {1}, btreeset! {2}, btreeset! {3}], None);
+        let err = m.ensure_consistent().unwrap_err();
+        assert_eq!("a joint config has at most 2 configs, got 3", err.reason);
+
+        // Positive weights of voters
+        let m = Membership::<UTConfig>::new(vec![btreeset! {1,2}], None).with_weights(btreemap! {1=>2});
+        assert!(m.ensure_consistent().is_ok());
+
+        // A voter has 0 weight
+        let m = Membership::<UTConfig>::new(vec![btreeset! {1,2}], None).with_weights(btreemap! {1=>0});
+        let err = m.ensure_consistent().unwrap_err();
+        assert_eq!("voter 1 has a weight of 0", err.reason);
+
+        // Every voter in a config has 0 weight
+        let m = Membership::<UTConfig>::new(vec![btreeset! {1,2}, btreeset! {3}], None).with_weights(btreemap! {3=>0});
+        let err = m.ensure_consistent().unwrap_err();
+        assert_eq!("voter 3 has a weight of 0", err.reason);
+        assert_eq!(Err(ChangeMembershipError::InvalidMembership(err)), m.ensure_valid());
+
+        // A learner or an unknown node has a weight
+        let m = Membership::<UTConfig>::new(vec![btreeset! {1,2}], Some(btreeset! {3})).with_weights(btreemap! {3=>2});
+        let err = m.ensure_consistent().unwrap_err();
+        assert_eq!("node 3 has a weight but is not a voter", err.reason);
+
+        let m = Membership::<UTConfig>::new(vec![btreeset! {1,2}], None).with_weights(btreemap! {5=>2});
+        let err = m.ensure_consistent().unwrap_err();
+        assert_eq!("node 5 has a weight but is not a voter", err.reason);
+
+        Ok(())
+    }
+
     #[test]
     fn test_membership_change() -> anyhow::Result<()> {
         let m = || Membership::<UTConfig> {
             configs: vec![btreeset! {1,2}],
             nodes: btreemap! {1=>(),2=>(),3=>()},
+            weights: btreemap! {},
         };
 
         // Add: no such learner
         {
             let res = m().change(ChangeMembers::AddVoterIds(btreeset! {4}), true);
             assert_eq!(
-                Err(ChangeMembershipError::LearnerNotFound(LearnerNotFound { node_id: 4 })),
+                Err(ChangeMembershipError::LearnerNotFound(LearnerNotFound {
+                    node_id: 4,
+                    committed: None
+                })),
                 res
             );
         }
@@ -387,7 +556,8 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}, btreeset! {1,2,3}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -399,7 +569,8 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}, btreeset! {1,2,5}],
-                    nodes: btreemap! {1=>(),2=>(),3=>(),5=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>(),5=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -411,7 +582,8 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -420,7 +592,12 @@ mod tests {
         // Remove: become empty
         {
             let res = m().change(ChangeMembers::RemoveVoters(btreeset! {1,2}), true);
-            assert_eq!(Err(ChangeMembershipError::EmptyMembership(EmptyMembership {})), res);
+            assert_eq!(
+                Err(ChangeMembershipError::EmptyMembership(EmptyMembership {
+                    committed: None
+                })),
+                res
+            );
         }
 
         // Remove: OK retain
@@ -429,7 +606,8 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}, btreeset! {2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -441,7 +619,8 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}, btreeset! {2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -452,12 +631,14 @@ mod tests {
             let mem = Membership::<UTConfig> {
                 configs: vec![btreeset! {1,2}, btreeset! {2}],
                 nodes: btreemap! {1=>(),2=>(),3=>()},
+                weights: btreemap! {},
             };
             let res = mem.change(ChangeMembers::RemoveVoters(btreeset! {1}), false);
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {2}],
-                    nodes: btreemap! {2=>(),3=>()}
+                    nodes: btreemap! {2=>(),3=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -469,7 +650,8 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}, btreeset! {2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -481,7 +663,8 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -493,7 +676,8 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -505,7 +689,8 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),3=>(), 4=>()}
+                    nodes: btreemap! {1=>(),2=>(),3=>(), 4=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -516,13 +701,15 @@ mod tests {
             let m = || Membership::<UTConfig<u64>> {
                 configs: vec![btreeset! {1,2}],
                 nodes: btreemap! {1=>1,2=>2,3=>3},
+                weights: btreemap! {},
             };
 
             let res = m().change(ChangeMembers::SetNodes(btreemap! {3=>30, 4=>40}), false);
             assert_eq!(
                 Ok(Membership::<UTConfig<u64>> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>1,2=>2,3=>30, 4=>40}
+                    nodes: btreemap! {1=>1,2=>2,3=>30, 4=>40},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -532,7 +719,10 @@ mod tests {
         {
             let res = m().change(ChangeMembers::RemoveNodes(btreeset! {2}), false);
             assert_eq!(
-                Err(ChangeMembershipError::LearnerNotFound(LearnerNotFound { node_id: 2 })),
+                Err(ChangeMembershipError::LearnerNotFound(LearnerNotFound {
+                    node_id: 2,
+                    committed: None
+                })),
                 res
             );
         }
@@ -543,7 +733,8 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>()}
+                    nodes: btreemap! {1=>(),2=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -555,7 +746,8 @@ mod tests {
             assert_eq!(
                 Ok(Membership::<UTConfig> {
                     configs: vec![btreeset! {1,2}],
-                    nodes: btreemap! {1=>(),2=>(),4=>()}
+                    nodes: btreemap! {1=>(),2=>(),4=>()},
+                    weights: btreemap! {},
                 }),
                 res
             );
@@ -563,4 +755,71 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_membership_change_multiple_voters() -> anyhow::Result<()> {
+        let m = |configs: Vec<BTreeSet<u64>>| Membership::<UTConfig> {
+            configs,
+            nodes: btreemap! {1=>(),2=>(),3=>(),4=>(),5=>(),6=>()},
+            weights: btreemap! {},
+        };
+        let joint = |res: Result<Membership<UTConfig>, _>| res.map(|x| x.get_joint_config().clone());
+
+        let m123 = || m(vec![btreeset! {1,2,3}]);
+
+        // Add several voters
+        {
+            let res = m123().change(ChangeMembers::AddVoterIds(btreeset! {4,5}), true);
+            assert_eq!(Ok(vec![btreeset! {1,2,3}, btreeset! {1,2,3,4,5}]), joint(res));
+        }
+
+        // Remove several voters
+        {
+            let res = m123().change(ChangeMembers::RemoveVoters(btreeset! {1,2}), true);
+            assert_eq!(Ok(vec![btreeset! {1,2,3}, btreeset! {3}]), joint(res));
+        }
+
+        // Add and remove several voters at once
+        {
+            let res = m123().change(ChangeMembers::ReplaceAllVoters(btreeset! {3,4,5}), true);
+            assert_eq!(Ok(vec![btreeset! {1,2,3}, btreeset! {3,4,5}]), joint(res));
+        }
+
+        // Replace every voter
+        {
+            let res = m123().change(ChangeMembers::ReplaceAllVoters(btreeset! {4,5,6}), true);
+            assert_eq!(Ok(vec![btreeset! {1,2,3}, btreeset! {4,5,6}]), joint(res));
+        }
+
+        // Change again during a joint config: leave the first config
+        {
+            let m = m(vec![btreeset! {1,2,3}, btreeset! {3,4,5}]);
+            let res = m.change(ChangeMembers::ReplaceAllVoters(btreeset! {5,6}), true);
+            assert_eq!(Ok(vec![btreeset! {3,4,5}, btreeset! {5,6}]), joint(res));
+        }
+
+        // Both the old and the new config must have a quorum
+        {
+            let m = m123().change(ChangeMembers::ReplaceAllVoters(btreeset! {4,5,6}), true)?;
+            let qs = m.to_quorum_set();
+
+            assert!(!qs.is_quorum([1, 2, 3].iter()), "only the old config");
+            assert!(!qs.is_quorum([4, 5, 6].iter()), "only the new config");
+            assert!(!qs.is_quorum([1, 2, 3, 4].iter()));
+            assert!(qs.is_quorum([1, 2, 4, 5].iter()));
+        }
+
+        // Switching directly between two incoherent configs is rejected
+        {
+            let m345 = m(vec![btreeset! {3,4,5}]);
+            let err = m345.ensure_coherent_with(&m123()).unwrap_err();
+            assert_eq!(
+                "config [{3, 4, 5}] is not coherent with the previous config [{1, 2, 3}]",
+                err.reason
+            );
+            assert!(m345.ensure_coherent_with(&m(vec![btreeset! {1,2,3}, btreeset! {3,4,5}])).is_ok());
+        }
+
+        Ok(())
+    }
 }
@@ -7,6 +7,7 @@ use maplit::btreeset;
 
 use crate::engine::testing::UTConfig;
 use crate::membership::IntoNodes;
+use crate::quorum::QuorumSet;
 use crate::ChangeMembers;
 use crate::Membership;
 
@@ -293,3 +294,50 @@ fn test_membership_next_coherent_with_nodes() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_membership_weights() -> anyhow::Result<()> {
+    let m =
+        Membership::<UTConfig>::new(vec![btreeset! {1,2,3}], Some(btreeset! {4})).with_weights(btreemap! {1=>3, 2=>2});
+
+    assert_eq!(3, m.weight(&1));
+    assert_eq!(2, m.weight(&2));
+    assert_eq!(1, m.weight(&3), "default weight is 1");
+    assert_eq!(
+        "{voters:[{1:(),2:(),3:()}], learners:[4:()], weights:[1:3,2:2]}",
+        m.to_string()
+    );
+
+    // Weighted quorum: total weight is 6, a quorum needs more than 3.
+    let qs = m.to_quorum_set();
+    assert!(qs.is_quorum([1, 3].iter()));
+    assert!(!qs.is_quorum([2, 3].iter()));
+    assert!(!qs.is_quorum([1].iter()));
+
+    // Weights are kept when changing membership.
+    let m2 = m.clone().change(ChangeMembers::AddVoterIds(btreeset! {4}), false)?;
+    assert_eq!(&vec![btreeset! {1,2,3}, btreeset! {1,2,3,4}], m2.get_joint_config());
+    assert_eq!(3, m2.weight(&1));
+
+    // Joint quorum: {1,2,3} has a total weight of 6, {1,2,3,4} has 7.
+    let qs = m2.to_quorum_set();
+    assert!(!qs.is_quorum([1, 4].iter()));
+    assert!(qs.is_quorum([1, 2].iter()));
+
+    // The weight of a removed voter is dropped, and it is not restored when it is added back.
+    let m3 = m.clone().change(ChangeMembers::RemoveVoters(btreeset! {1}), true)?;
+    assert_eq!(&vec![btreeset! {1,2,3}, btreeset! {2,3}], m3.get_joint_config());
+    assert_eq!(3, m3.weight(&1), "still a voter in the joint config");
+
+    let m4 = m3.change(ChangeMembers::RemoveVoters(btreeset! {1}), true)?;
+    assert_eq!(&vec![btreeset! {2,3}], m4.get_joint_config());
+    assert_eq!(1, m4.weight(&1), "weight is dropped with the voter");
+    assert!(m4.ensure_valid().is_ok());
+
+    let m5 = m4.change(ChangeMembers::AddVoterIds(btreeset! {1}), true)?;
+    assert_eq!(&vec![btreeset! {2,3}, btreeset! {1,2,3}], m5.get_joint_config());
+    assert_eq!(1, m5.weight(&1), "a re-added voter has the default weight");
+    assert_eq!(2, m5.weight(&2));
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::LogId;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+/// The reply of a peer to a vote request of an [`ElectionRound`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct VoteReply<C: RaftTypeConfig> {
+    /// Whether the peer granted the vote.
+    pub granted: bool,
+
+    /// The vote of the peer when it replied.
+    ///
+    /// If it is greater than the vote of the round, the peer has seen a greater term or has voted
+    /// for another candidate.
+    pub vote: Vote<C::NodeId>,
+
+    /// The last log id of the peer.
+    ///
+    /// If it is greater than the last log id of the candidate, the peer rejects the vote because
+    /// the candidate's log is not up to date.
+    pub last_log_id: Option<LogId<C::NodeId>>,
+}
+
+impl<C> fmt::Display for VoteReply<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{granted: {}, vote: {}, last_log_id: {}}}",
+            self.granted,
+            self.vote,
+            self.last_log_id.display()
+        )
+    }
+}
+
+/// How an [`ElectionRound`] ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ElectionRoundOutcome {
+    /// A quorum granted the vote and the candidate became the leader.
+    Won,
+
+    /// The candidate gave up the vote before a quorum granted it, e.g., it saw a greater vote, it
+    /// accepted another leader, or the election timed out and a new round started.
+    Lost,
+}
+
+/// The vote requests a candidate sent in one election round, and the replies it received.
+///
+/// It is delivered as [`RaftEvent::ElectionRoundFinished`] when the round ends. A peer that is in
+/// `requested` but not in `replies` did not reply in time, e.g., it is unreachable.
+///
+/// [`RaftEvent::ElectionRoundFinished`]: crate::metrics::RaftEvent::ElectionRoundFinished
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ElectionRound<C: RaftTypeConfig> {
+    /// The vote the candidate requested.
+    pub vote: Vote<C::NodeId>,
+
+    /// The last log id of the candidate, sent with the vote requests.
+    pub last_log_id: Option<LogId<C::NodeId>>,
+
+    /// The voters the vote requests are sent to, excluding the candidate itself.
+    pub requested: BTreeSet<C::NodeId>,
+
+    /// The replies received from peers.
+    pub replies: BTreeMap<C::NodeId, VoteReply<C>>,
+
+    /// How the round ended.
+    pub outcome: ElectionRoundOutcome,
+}
+
+impl<C> fmt::Display for ElectionRound<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ElectionRound{{vote: {}, last_log_id: {}, requested: {:?}, replies: [",
+            self.vote,
+            self.last_log_id.display(),
+            self.requested
+        )?;
+
+        for (i, (id, r)) in self.replies.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", id, r)?;
+        }
+
+        write!(f, "], outcome: {:?}}}", self.outcome)
+    }
+}
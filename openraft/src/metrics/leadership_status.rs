@@ -0,0 +1,32 @@
+use std::fmt;
+
+/// Whether a Raft node knows of an elected leader.
+///
+/// A cluster is briefly leaderless during a normal failover. To tell it apart from a genuine
+/// loss of leadership, a node reports [`ElectingNewLeader`](Self::ElectingNewLeader) at first, and
+/// escalates to [`Leaderless`](Self::Leaderless) only if no leader is elected within
+/// [`Config::leaderless_grace`](crate::Config::leaderless_grace).
+#[derive(Debug, Clone, Copy, Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LeadershipStatus {
+    /// This node knows of an elected leader.
+    HasLeader,
+
+    /// There is no known leader, for a period shorter than the grace period.
+    #[default]
+    ElectingNewLeader,
+
+    /// There is no known leader for longer than the grace period.
+    Leaderless,
+}
+
+impl fmt::Display for LeadershipStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeadershipStatus::HasLeader => write!(f, "HasLeader"),
+            LeadershipStatus::ElectingNewLeader => write!(f, "ElectingNewLeader"),
+            LeadershipStatus::Leaderless => write!(f, "Leaderless"),
+        }
+    }
+}
@@ -17,7 +17,9 @@
 //! - The current leader,
 //! - Last log and applied log.
 //! - Replication state, if this node is a Leader,
-//! - Snapshot state,
+//! - Why replication targets rejected AppendEntries,
+//! - Snapshot state, and the progress of receiving a snapshot,
+//! - Latency of storage operations,
 //! - etc.
 //!
 //! Metrics can be used as a trigger of application events, as a monitoring data
@@ -26,21 +28,46 @@
 //! Metrics is not a stream thus it only guarantees to provide the latest state but
 //! not every change of the state.
 //! Because internally, `watch::channel()` only stores one last state.
+//!
+//! ## [`RaftEvent`]
+//!
+//! Discrete events such as server state, term, leader and membership changes, and the detail of
+//! every election round, are also delivered
+//! without being coalesced by [`Raft::events()`](`crate::Raft::events`), via a bounded broadcast
+//! channel.
 
+mod election_round;
+mod leadership_status;
 mod metric;
+mod raft_event;
 mod raft_metrics;
+mod raft_status;
+mod replication_rejection;
+mod snapshot_install_progress;
+mod storage_metrics;
 mod wait;
 
 mod metric_display;
+#[cfg(test)] mod storage_metrics_test;
 mod wait_condition;
 #[cfg(test)] mod wait_test;
 
 use std::collections::BTreeMap;
 
+pub use election_round::ElectionRound;
+pub use election_round::ElectionRoundOutcome;
+pub use election_round::VoteReply;
+pub use leadership_status::LeadershipStatus;
 pub use metric::Metric;
+pub use raft_event::RaftEvent;
 pub use raft_metrics::RaftDataMetrics;
 pub use raft_metrics::RaftMetrics;
 pub use raft_metrics::RaftServerMetrics;
+pub use raft_status::RaftStatus;
+pub use replication_rejection::ReplicationRejection;
+pub use snapshot_install_progress::SnapshotInstallProgress;
+pub use storage_metrics::StorageMetrics;
+pub(crate) use storage_metrics::StorageOp;
 pub use wait::Wait;
 pub use wait::WaitError;
 pub(crate) use wait_condition::Condition;
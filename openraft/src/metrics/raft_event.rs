@@ -0,0 +1,65 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::core::ServerState;
+use crate::display_ext::DisplayOptionExt;
+use crate::metrics::ElectionRound;
+use crate::LogId;
+use crate::RaftTypeConfig;
+use crate::SnapshotMeta;
+use crate::StorageError;
+use crate::StoredMembership;
+
+/// A discrete event happened on a Raft node, delivered by [`Raft::events()`].
+///
+/// Unlike [`RaftMetrics`], which only keeps the latest state, every event is delivered to every
+/// subscriber in the order they happened, until the subscriber falls behind by more than
+/// [`Config::event_channel_capacity`] events.
+///
+/// [`Raft::events()`]: crate::Raft::events
+/// [`RaftMetrics`]: crate::RaftMetrics
+/// [`Config::event_channel_capacity`]: crate::Config::event_channel_capacity
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum RaftEvent<C: RaftTypeConfig> {
+    /// The server state of this node changed, e.g., a follower became a candidate.
+    ServerStateChanged { from: ServerState, to: ServerState },
+
+    /// A new term is persisted by this node.
+    TermChanged { term: u64 },
+
+    /// The leader known by this node changed. `None` means no leader is known.
+    LeaderChanged { leader: Option<C::NodeId> },
+
+    /// The effective membership of this node changed.
+    MembershipChanged { membership: Arc<StoredMembership<C>> },
+
+    /// An election round started by this node ended, with the vote requests it sent and the
+    /// replies it received.
+    ElectionRoundFinished { round: ElectionRound<C> },
+
+    /// A snapshot received from the leader is installed into the state machine.
+    SnapshotInstalled { meta: SnapshotMeta<C> },
+
+    /// The state machine failed to apply the log entry `log_id`.
+    ApplyFailed {
+        log_id: LogId<C::NodeId>,
+        error: StorageError<C::NodeId>,
+    },
+}
+
+impl<C> fmt::Display for RaftEvent<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RaftEvent::ServerStateChanged { from, to } => write!(f, "ServerStateChanged: {:?} -> {:?}", from, to),
+            RaftEvent::TermChanged { term } => write!(f, "TermChanged: {}", term),
+            RaftEvent::LeaderChanged { leader } => write!(f, "LeaderChanged: {}", leader.display()),
+            RaftEvent::MembershipChanged { membership } => write!(f, "MembershipChanged: {}", membership),
+            RaftEvent::ElectionRoundFinished { round } => write!(f, "ElectionRoundFinished: {}", round),
+            RaftEvent::SnapshotInstalled { meta } => write!(f, "SnapshotInstalled: {}", meta),
+            RaftEvent::ApplyFailed { log_id, error } => write!(f, "ApplyFailed: {}: {}", log_id, error),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fmt;
 use std::sync::Arc;
 
@@ -5,9 +7,14 @@ use crate::core::ServerState;
 use crate::display_ext::DisplayOption;
 use crate::display_ext::DisplayOptionExt;
 use crate::error::Fatal;
+use crate::metrics::LeadershipStatus;
 use crate::metrics::ReplicationMetrics;
+use crate::metrics::ReplicationRejection;
+use crate::metrics::SnapshotInstallProgress;
+use crate::metrics::StorageMetrics;
 use crate::LogId;
 use crate::RaftTypeConfig;
+use crate::StorageError;
 use crate::StoredMembership;
 use crate::Vote;
 
@@ -17,6 +24,12 @@ use crate::Vote;
 pub struct RaftMetrics<C: RaftTypeConfig> {
     pub running_state: Result<(), Fatal<C>>,
 
+    /// The storage error that made this node enter read-only degraded mode.
+    ///
+    /// It is `None` if the node is running normally.
+    /// See [`Config::degrade_on_storage_error`](crate::Config::degrade_on_storage_error).
+    pub storage_degraded: Option<StorageError<C::NodeId>>,
+
     /// The ID of the Raft node.
     pub id: C::NodeId,
 
@@ -32,6 +45,9 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// The last log index has been appended to this Raft node's log.
     pub last_log_index: Option<u64>,
 
+    /// The last log id this Raft node knows to be committed.
+    pub committed: Option<LogId<C::NodeId>>,
+
     /// The last log index has been applied to this Raft node's state machine.
     pub last_applied: Option<LogId<C::NodeId>>,
 
@@ -39,12 +55,20 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// If there is no snapshot, it is (0,0).
     pub snapshot: Option<LogId<C::NodeId>>,
 
+    /// The progress of the snapshot this node is receiving from the leader.
+    ///
+    /// It is `None` if no snapshot is being received.
+    pub installing_snapshot: Option<SnapshotInstallProgress<C::NodeId>>,
+
     /// The last log id that has purged from storage, inclusive.
     ///
     /// `purged` is also the first log id Openraft knows, although the corresponding log entry has
     /// already been deleted.
     pub purged: Option<LogId<C::NodeId>>,
 
+    /// Latency of storage operations performed by this node.
+    pub storage: StorageMetrics,
+
     // ---
     // --- cluster ---
     // ---
@@ -54,6 +78,10 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// The current cluster leader.
     pub current_leader: Option<C::NodeId>,
 
+    /// Whether there is a known leader, and if not, whether it has been missing for longer than
+    /// [`Config::leaderless_grace`](crate::Config::leaderless_grace).
+    pub leadership_status: LeadershipStatus,
+
     /// For a leader, it is the elapsed time in milliseconds since the most recently acknowledged
     /// timestamp by a quorum.
     ///
@@ -72,11 +100,81 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// The current membership config of the cluster.
     pub membership_config: Arc<StoredMembership<C>>,
 
+    /// For a follower or learner, the committed log id of the leader in the last accepted
+    /// AppendEntries request.
+    ///
+    /// Comparing it with `last_applied` tells how far this node falls behind the leader. It is
+    /// `None` if this node is a leader or has not yet received any AppendEntries.
+    pub leader_commit_seen: Option<LogId<C::NodeId>>,
+
+    /// The elapsed time in milliseconds since `leader_commit_seen` is received.
+    pub millis_since_leader_commit_seen: Option<u64>,
+
     // ---
     // --- replication ---
     // ---
     /// The replication states. It is Some() only when this node is leader.
     pub replication: Option<ReplicationMetrics<C::NodeId>>,
+
+    /// For a leader, the elapsed time in milliseconds since the last acknowledged request to each
+    /// replication target was sent, or `None` for a target that has not yet acknowledged any.
+    ///
+    /// It is updated by every successful AppendEntries reply, including heartbeats. Combined with
+    /// [`Self::replication`], it tells a target that is caught up but has gone silent from one
+    /// that is alive. It is `None` if this node is not a leader.
+    pub millis_since_last_ack: Option<BTreeMap<C::NodeId, Option<u64>>>,
+
+    /// The greatest log index replicated to every voter, not only to a quorum.
+    ///
+    /// It is `None` if this node is not a leader, or if any voter has not yet accepted any log.
+    /// Unlike the committed index, it only advances when the slowest voter catches up, and it may
+    /// go backward when the membership changes.
+    pub fully_replicated_index: Option<u64>,
+
+    /// The voters that had accepted the committed log id when this leader last advanced it.
+    ///
+    /// It contains a quorum of the effective membership, and may contain more voters if they had
+    /// accepted it too. It tells which voters the commits depend on, e.g., to verify that a
+    /// witness node is not unexpectedly required.
+    ///
+    /// It is `None` if this node is not a leader, or if it has not yet committed any log.
+    pub commit_quorum: Option<BTreeSet<C::NodeId>>,
+
+    /// The number of times this node, as a leader, found the logs to replicate already purged
+    /// from the log store, and fell back to replicating a snapshot.
+    pub replication_log_purged: u64,
+
+    /// Why each replication target rejected the last AppendEntries request from this node, when
+    /// it was the leader.
+    ///
+    /// A target is removed once it accepts logs again. It is reset when this node becomes a
+    /// leader, and is kept after it steps down, so that the reason of losing leadership, e.g., a
+    /// [`ReplicationRejection::HigherVote`], is visible.
+    pub replication_rejections: BTreeMap<C::NodeId, ReplicationRejection<C::NodeId>>,
+
+    /// The number of client write requests on this leader that are waiting for their entries to
+    /// be committed and applied.
+    ///
+    /// A steadily growing value indicates the write pipeline can not keep up with the load.
+    /// It is always `0` if this node is not a leader.
+    pub pending_client_writes: u64,
+
+    /// The number of AppendEntries, Vote and InstallSnapshot requests received with a term lower
+    /// than the current term of this node. These requests are rejected.
+    ///
+    /// A nonzero and growing value usually indicates a network issue, or a partitioned old leader
+    /// that is still trying to replicate logs.
+    pub stale_term_requests: u64,
+
+    /// The number of times this node has become leader since it started.
+    ///
+    /// Unlike the term, which may increase several times during failed elections, it increases by
+    /// exactly one every time this node becomes leader, and never decreases while the node is
+    /// running. It can be used as a fencing token by an external system to reject operations from
+    /// a deposed leader on this node. It is not persisted and restarts from `0` after a restart,
+    /// thus a fencing token that outlives a restart should be combined with
+    /// [`current_term`](Self::current_term).
+    pub leadership_epoch: u64,
 }
 
 impl<C> fmt::Display for RaftMetrics<C>
@@ -101,10 +199,11 @@ where C: RaftTypeConfig
         write!(f, ", ")?;
         write!(
             f,
-            "membership:{}, snapshot:{}, purged:{}, replication:{{{}}}",
+            "membership:{}, snapshot:{}, purged:{}, storage:{}, replication:{{{}}}",
             self.membership_config,
             DisplayOption(&self.snapshot),
             DisplayOption(&self.purged),
+            self.storage,
             self.replication
                 .as_ref()
                 .map(|x| { x.iter().map(|(k, v)| format!("{}:{}", k, DisplayOption(v))).collect::<Vec<_>>().join(",") })
@@ -122,20 +221,35 @@ where C: RaftTypeConfig
     pub fn new_initial(id: C::NodeId) -> Self {
         Self {
             running_state: Ok(()),
+            storage_degraded: None,
             id,
 
             current_term: 0,
             vote: Vote::default(),
             last_log_index: None,
+            committed: None,
             last_applied: None,
             snapshot: None,
+            installing_snapshot: None,
             purged: None,
+            storage: StorageMetrics::default(),
 
             state: ServerState::Follower,
             current_leader: None,
+            leadership_status: LeadershipStatus::default(),
             millis_since_quorum_ack: None,
             membership_config: Arc::new(StoredMembership::default()),
+            leader_commit_seen: None,
+            millis_since_leader_commit_seen: None,
             replication: None,
+            millis_since_last_ack: None,
+            fully_replicated_index: None,
+            commit_quorum: None,
+            replication_log_purged: 0,
+            replication_rejections: BTreeMap::new(),
+            pending_client_writes: 0,
+            stale_term_requests: 0,
+            leadership_epoch: 0,
         }
     }
 }
@@ -147,8 +261,15 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     pub last_log: Option<LogId<C::NodeId>>,
     pub last_applied: Option<LogId<C::NodeId>>,
     pub snapshot: Option<LogId<C::NodeId>>,
+
+    /// See [`RaftMetrics::installing_snapshot`].
+    pub installing_snapshot: Option<SnapshotInstallProgress<C::NodeId>>,
+
     pub purged: Option<LogId<C::NodeId>>,
 
+    /// Latency of storage operations performed by this node.
+    pub storage: StorageMetrics,
+
     /// For a leader, it is the elapsed time in milliseconds since the most recently acknowledged
     /// timestamp by a quorum.
     ///
@@ -164,7 +285,32 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     /// being partitioned from the cluster.
     pub millis_since_quorum_ack: Option<u64>,
 
+    /// See [`RaftMetrics::leader_commit_seen`].
+    pub leader_commit_seen: Option<LogId<C::NodeId>>,
+
+    /// See [`RaftMetrics::millis_since_leader_commit_seen`].
+    pub millis_since_leader_commit_seen: Option<u64>,
+
     pub replication: Option<ReplicationMetrics<C::NodeId>>,
+
+    /// See [`RaftMetrics::millis_since_last_ack`].
+    pub millis_since_last_ack: Option<BTreeMap<C::NodeId, Option<u64>>>,
+
+    /// See [`RaftMetrics::fully_replicated_index`].
+    pub fully_replicated_index: Option<u64>,
+
+    /// See [`RaftMetrics::commit_quorum`].
+    pub commit_quorum: Option<BTreeSet<C::NodeId>>,
+
+    /// The number of times the logs to replicate are found purged, see
+    /// [`RaftMetrics::replication_log_purged`].
+    pub replication_log_purged: u64,
+
+    /// See [`RaftMetrics::replication_rejections`].
+    pub replication_rejections: BTreeMap<C::NodeId, ReplicationRejection<C::NodeId>>,
+
+    /// See [`RaftMetrics::pending_client_writes`].
+    pub pending_client_writes: u64,
 }
 
 impl<C> fmt::Display for RaftDataMetrics<C>
@@ -175,11 +321,12 @@ where C: RaftTypeConfig
 
         write!(
             f,
-            "last_log:{}, last_applied:{}, snapshot:{}, purged:{}, quorum_acked(leader):{} ms before, replication:{{{}}}",
+            "last_log:{}, last_applied:{}, snapshot:{}, purged:{}, storage:{}, quorum_acked(leader):{} ms before, replication:{{{}}}",
             DisplayOption(&self.last_log),
             DisplayOption(&self.last_applied),
             DisplayOption(&self.snapshot),
             DisplayOption(&self.purged),
+            self.storage,
             self.millis_since_quorum_ack.display(),
             self.replication
                 .as_ref()
@@ -201,6 +348,9 @@ pub struct RaftServerMetrics<C: RaftTypeConfig> {
     pub state: ServerState,
     pub current_leader: Option<C::NodeId>,
 
+    /// See [`RaftMetrics::leadership_status`].
+    pub leadership_status: LeadershipStatus,
+
     pub membership_config: Arc<StoredMembership<C>>,
 }
 
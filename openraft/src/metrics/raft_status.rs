@@ -0,0 +1,63 @@
+use std::fmt;
+
+use crate::core::ServerState;
+use crate::display_ext::DisplayOptionExt;
+use crate::metrics::RaftMetrics;
+use crate::LogIdOptionExt;
+use crate::NodeId;
+use crate::RaftTypeConfig;
+
+/// The role, term and log position of a Raft node, returned by [`Raft::status()`].
+///
+/// All fields are taken from the same [`RaftMetrics`] value, thus they are consistent with each
+/// other, e.g., `leader_id` is the leader of `current_term`. Reading the fields one by one from
+/// the metrics channel may instead mix up values from different updates.
+///
+/// [`Raft::status()`]: crate::Raft::status
+#[derive(Debug, Clone, Copy)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct RaftStatus<NID: NodeId> {
+    /// The server state of this node.
+    pub state: ServerState,
+
+    /// The current term of this node.
+    pub current_term: u64,
+
+    /// The current leader known to this node.
+    pub leader_id: Option<NID>,
+
+    /// The index of the last log this node knows to be committed.
+    pub commit_index: Option<u64>,
+
+    /// The index of the last log appended to this node.
+    pub last_log_index: Option<u64>,
+}
+
+impl<C> From<&RaftMetrics<C>> for RaftStatus<C::NodeId>
+where C: RaftTypeConfig
+{
+    fn from(m: &RaftMetrics<C>) -> Self {
+        Self {
+            state: m.state,
+            current_term: m.current_term,
+            leader_id: m.current_leader,
+            commit_index: m.committed.index(),
+            last_log_index: m.last_log_index,
+        }
+    }
+}
+
+impl<NID: NodeId> fmt::Display for RaftStatus<NID> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{state:{:?}, term:{}, leader:{}, commit_index:{}, last_log_index:{}}}",
+            self.state,
+            self.current_term,
+            self.leader_id.display(),
+            self.commit_index.display(),
+            self.last_log_index.display()
+        )
+    }
+}
@@ -0,0 +1,33 @@
+use std::fmt;
+
+use crate::LogId;
+use crate::NodeId;
+use crate::Vote;
+
+/// Why a replication target rejected the last AppendEntries request from the leader.
+///
+/// A rejected target is not necessarily unhealthy: a [`LogMismatch`](Self::LogMismatch) is
+/// expected while the leader searches for the last matching log of a follower whose log diverges,
+/// and it is cleared once the follower accepts logs again.
+#[derive(Debug, Clone)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum ReplicationRejection<NID: NodeId> {
+    /// The target has seen a greater vote than the leader's, i.e., the leader's term is too low.
+    ///
+    /// The leader steps down upon this rejection.
+    HigherVote(Vote<NID>),
+
+    /// The log at `prev_log_id` of the request does not exist on the target or has a different
+    /// term, i.e., the target's log diverges from the leader's or falls behind it.
+    LogMismatch(LogId<NID>),
+}
+
+impl<NID: NodeId> fmt::Display for ReplicationRejection<NID> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplicationRejection::HigherVote(vote) => write!(f, "HigherVote({})", vote),
+            ReplicationRejection::LogMismatch(log_id) => write!(f, "LogMismatch(prev_log_id: {})", log_id),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::LogId;
+use crate::NodeId;
+use crate::SnapshotId;
+
+/// The progress of a snapshot that a follower or learner is receiving from the leader.
+///
+/// It is updated every time a chunk of the snapshot is written, and is gone once the last chunk is
+/// received and the snapshot is handed over to be installed, or the transfer fails.
+#[derive(Debug, Clone)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct SnapshotInstallProgress<NID: NodeId> {
+    /// The ID of the snapshot being received.
+    pub snapshot_id: SnapshotId,
+
+    /// The last log id included in the snapshot being received.
+    pub last_log_id: Option<LogId<NID>>,
+
+    /// The number of bytes of the snapshot data written so far.
+    pub bytes_received: u64,
+}
+
+impl<NID: NodeId> fmt::Display for SnapshotInstallProgress<NID> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{snapshot_id:{}, last_log_id:{}, bytes_received:{}}}",
+            self.snapshot_id,
+            self.last_log_id.display(),
+            self.bytes_received
+        )
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+use std::time::Duration;
+
+/// The kind of storage operation whose latency is tracked by [`StorageMetrics`].
+#[derive(Debug, Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub(crate) enum StorageOp {
+    Append,
+    SaveVote,
+    GetLogEntries,
+    Apply,
+    BuildSnapshot,
+}
+
+/// Latency of storage operations performed by Raft, in microseconds.
+///
+/// Every field is an exponentially weighted moving average(EWMA) of the latency of one kind of
+/// operation, in which the latest sample weighs `1/8`. A field is `0` until the first operation of
+/// this kind is done.
+///
+/// Compared with the replication metrics, it tells whether a slow commit is caused by a slow disk
+/// or by a slow network.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct StorageMetrics {
+    /// Appending log entries until they are flushed, i.e., from calling
+    /// [`RaftLogStorage::append()`] until the callback is called.
+    ///
+    /// [`RaftLogStorage::append()`]: crate::storage::RaftLogStorage::append
+    pub append_micros: u64,
+
+    /// Saving a vote with [`RaftLogStorage::save_vote()`].
+    ///
+    /// [`RaftLogStorage::save_vote()`]: crate::storage::RaftLogStorage::save_vote
+    pub save_vote_micros: u64,
+
+    /// Reading committed log entries to apply with [`RaftLogReaderExt::get_log_entries()`].
+    ///
+    /// [`RaftLogReaderExt::get_log_entries()`]: crate::storage::RaftLogReaderExt::get_log_entries
+    pub get_log_entries_micros: u64,
+
+    /// Applying log entries with [`RaftStateMachine::apply()`].
+    ///
+    /// [`RaftStateMachine::apply()`]: crate::storage::RaftStateMachine::apply
+    pub apply_micros: u64,
+
+    /// Building a snapshot with [`RaftSnapshotBuilder::build_snapshot()`].
+    ///
+    /// [`RaftSnapshotBuilder::build_snapshot()`]: crate::RaftSnapshotBuilder::build_snapshot
+    pub build_snapshot_micros: u64,
+}
+
+impl StorageMetrics {
+    /// Add a latency sample of a storage operation.
+    pub(crate) fn record(&mut self, op: StorageOp, elapsed: Duration) {
+        let sample = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+
+        let avg = match op {
+            StorageOp::Append => &mut self.append_micros,
+            StorageOp::SaveVote => &mut self.save_vote_micros,
+            StorageOp::GetLogEntries => &mut self.get_log_entries_micros,
+            StorageOp::Apply => &mut self.apply_micros,
+            StorageOp::BuildSnapshot => &mut self.build_snapshot_micros,
+        };
+
+        *avg = if *avg == 0 {
+            sample
+        } else {
+            ((*avg as u128 * 7 + sample as u128) / 8) as u64
+        };
+    }
+}
+
+impl fmt::Display for StorageMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{append:{}us, save_vote:{}us, get_log_entries:{}us, apply:{}us, build_snapshot:{}us}}",
+            self.append_micros,
+            self.save_vote_micros,
+            self.get_log_entries_micros,
+            self.apply_micros,
+            self.build_snapshot_micros,
+        )
+    }
+}
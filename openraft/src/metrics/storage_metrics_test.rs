@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use crate::metrics::storage_metrics::StorageOp;
+use crate::metrics::StorageMetrics;
+
+#[test]
+fn test_storage_metrics_record() -> anyhow::Result<()> {
+    let mut m = StorageMetrics::default();
+
+    // The first sample is used as is.
+    m.record(StorageOp::Append, Duration::from_micros(800));
+    assert_eq!(800, m.append_micros);
+
+    // Later samples are averaged.
+    m.record(StorageOp::Append, Duration::from_micros(1600));
+    assert_eq!(900, m.append_micros);
+
+    // Other kinds of operation are not affected.
+    m.record(StorageOp::Apply, Duration::from_micros(8));
+    assert_eq!(
+        StorageMetrics {
+            append_micros: 900,
+            save_vote_micros: 0,
+            get_log_entries_micros: 0,
+            apply_micros: 8,
+            build_snapshot_micros: 0,
+        },
+        m
+    );
+
+    Ok(())
+}
@@ -9,6 +9,7 @@ use tokio::time::sleep;
 use crate::core::ServerState;
 use crate::engine::testing::UTConfig;
 use crate::log_id::LogIdOptionExt;
+use crate::metrics::LeadershipStatus;
 use crate::metrics::Wait;
 use crate::metrics::WaitError;
 use crate::testing::log_id;
@@ -255,15 +256,30 @@ where C: RaftTypeConfig {
         current_term: 0,
         vote: Vote::default(),
         last_log_index: None,
+        committed: None,
         last_applied: None,
         purged: None,
+        storage: Default::default(),
 
         current_leader: None,
+        leadership_status: LeadershipStatus::default(),
         millis_since_quorum_ack: None,
         membership_config: Arc::new(StoredMembership::new(None, Membership::new(vec![btreeset! {}], None))),
 
+        leader_commit_seen: None,
+        millis_since_leader_commit_seen: None,
+
         snapshot: None,
+        installing_snapshot: None,
         replication: None,
+        millis_since_last_ack: None,
+        fully_replicated_index: None,
+        commit_quorum: None,
+        replication_log_purged: 0,
+        replication_rejections: Default::default(),
+        pending_client_writes: 0,
+        stale_term_requests: 0,
+        leadership_epoch: 0,
     };
     let (tx, rx) = watch::channel(init.clone());
     let w = Wait {
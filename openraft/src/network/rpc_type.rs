@@ -8,6 +8,7 @@ pub enum RPCTypes {
     Vote,
     AppendEntries,
     InstallSnapshot,
+    ReadIndex,
 }
 
 impl fmt::Display for RPCTypes {
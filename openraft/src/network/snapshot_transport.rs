@@ -5,6 +5,7 @@ use std::future::Future;
 use std::io::SeekFrom;
 use std::time::Duration;
 
+use futures::future::Either;
 use futures::FutureExt;
 use openraft_macros::add_async_trait;
 use tokio::io::AsyncReadExt;
@@ -16,6 +17,7 @@ use crate::error::InstallSnapshotError;
 use crate::error::RPCError;
 use crate::error::RaftError;
 use crate::error::ReplicationClosed;
+use crate::error::SnapshotChecksumMismatch;
 use crate::error::StreamingError;
 use crate::network::RPCOption;
 use crate::raft::InstallSnapshotRequest;
@@ -114,6 +116,9 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
         let mut offset = 0;
         let end = snapshot.snapshot.seek(SeekFrom::End(0)).await.sto_res(subject_verb)?;
 
+        // Checksum of the data before `offset` that has been sent.
+        let mut checksum = Checksum::new();
+
         let mut c = std::pin::pin!(cancel);
         loop {
             // If canceled, return at once
@@ -142,9 +147,17 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
             let n_read = buf.len();
 
             let done = (offset + n_read as u64) == end;
+
+            let mut chunk_checksum = checksum;
+            chunk_checksum.update(&buf);
+
+            // The checksum is known only when all data is read, and is sent with the last chunk.
+            let mut meta = snapshot.meta.clone();
+            meta.checksum = if done { Some(chunk_checksum.finish()) } else { None };
+
             let req = InstallSnapshotRequest {
                 vote,
-                meta: snapshot.meta.clone(),
+                meta,
                 offset,
                 data: buf,
                 done,
@@ -160,7 +173,13 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
             );
 
             #[allow(deprecated)]
-            let res = AsyncRuntimeOf::<C>::timeout(option.hard_ttl(), net.install_snapshot(req, option.clone())).await;
+            let rpc = AsyncRuntimeOf::<C>::timeout(option.hard_ttl(), net.install_snapshot(req, option.clone()));
+
+            // Do not wait for the response if canceled while sending a chunk.
+            let res = match futures::future::select(c.as_mut(), std::pin::pin!(rpc)).await {
+                Either::Left((err, _)) => return Err(err.into()),
+                Either::Right((res, _)) => res,
+            };
 
             let resp = match res {
                 Ok(outer_res) => match outer_res {
@@ -175,6 +194,7 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
                             RPCError::Unreachable(_) => {}
                             RPCError::PayloadTooLarge(_) => {}
                             RPCError::Network(_) => {}
+                            RPCError::Malformed(_) => {}
                             RPCError::RemoteError(remote_err) => {
                                 //
                                 match remote_err.source {
@@ -189,6 +209,15 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
                                                     "snapshot mismatch, reset offset and retry"
                                                 );
                                                 offset = 0;
+                                                checksum = Checksum::new();
+                                            }
+                                            InstallSnapshotError::ChecksumMismatch(mismatch) => {
+                                                tracing::warn!(
+                                                    mismatch = display(&mismatch),
+                                                    "snapshot checksum mismatch, re-send all data"
+                                                );
+                                                offset = 0;
+                                                checksum = Checksum::new();
                                             }
                                         }
                                     }
@@ -215,6 +244,7 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
             }
 
             offset += n_read as u64;
+            checksum = chunk_checksum;
         }
     }
 
@@ -266,6 +296,23 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
             let streaming = streaming.take().unwrap();
             let mut data = streaming.into_snapshot_data();
 
+            if let Some(expect) = snapshot_meta.checksum {
+                let got = Checksum::of_data(data.as_mut()).await.map_err(|e| {
+                    let io_err = StorageIOError::read_snapshot(Some(snapshot_meta.signature()), &e);
+                    StorageError::from(io_err)
+                })?;
+
+                if got != expect {
+                    let mismatch = SnapshotChecksumMismatch {
+                        snapshot_id: snapshot_meta.snapshot_id.clone(),
+                        expect,
+                        got,
+                    };
+                    tracing::warn!(mismatch = display(&mismatch), "reject received snapshot");
+                    return Err(RaftError::APIError(InstallSnapshotError::ChecksumMismatch(mismatch)));
+                }
+            }
+
             data.as_mut().shutdown().await.map_err(|e| {
                 let io_err = StorageIOError::write_snapshot(Some(snapshot_meta.signature()), &e);
                 StorageError::from(io_err)
@@ -279,6 +326,57 @@ where C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io:
     }
 }
 
+/// A 64-bit FNV-1a checksum of snapshot data.
+///
+/// FNV-1a is used instead of the std `DefaultHasher`, because its output must be the same on the
+/// sender and the receiver, which may be built with different Rust versions.
+#[derive(Debug, Clone, Copy)]
+struct Checksum {
+    state: u64,
+}
+
+impl Checksum {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    fn new() -> Self {
+        Self {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        for b in data {
+            self.state ^= *b as u64;
+            self.state = self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    /// Compute the checksum of all data from the start, and seek back to the start.
+    async fn of_data<D>(data: &mut D) -> Result<u64, std::io::Error>
+    where D: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io::AsyncSeek + Unpin + ?Sized {
+        data.flush().await?;
+        data.seek(SeekFrom::Start(0)).await?;
+
+        let mut checksum = Self::new();
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let n = data.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            checksum.update(&buf[..n]);
+        }
+
+        data.seek(SeekFrom::Start(0)).await?;
+        Ok(checksum.finish())
+    }
+}
+
 /// The Raft node is streaming in a snapshot from the leader.
 pub struct Streaming<C>
 where C: RaftTypeConfig
@@ -308,6 +406,11 @@ where C: RaftTypeConfig
         &self.snapshot_id
     }
 
+    /// The number of bytes written to the snapshot so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
     /// Consumes the `Streaming` and returns the snapshot data.
     pub fn into_snapshot_data(self) -> Box<C::SnapshotData> {
         self.snapshot_data
@@ -359,6 +462,7 @@ mod tests {
     use crate::error::RPCError;
     use crate::error::RaftError;
     use crate::error::SnapshotMismatch;
+    use crate::network::snapshot_transport::Checksum;
     use crate::network::snapshot_transport::Chunked;
     use crate::network::snapshot_transport::SnapshotTransport;
     use crate::network::RPCOption;
@@ -377,6 +481,7 @@ mod tests {
 
     struct Network {
         received_offset: Vec<u64>,
+        received_checksum: Vec<Option<u64>>,
         match_cnt: u64,
     }
 
@@ -407,6 +512,7 @@ mod tests {
             // A fake implementation to test the Chunked::send_snapshot.
 
             self.received_offset.push(rpc.offset);
+            self.received_checksum.push(rpc.meta.checksum);
 
             // For the second last time, return a mismatch error.
             // Then return Ok for the reset of the time.
@@ -436,6 +542,7 @@ mod tests {
     async fn test_chunked_reset_offset_if_snapshot_id_mismatch() {
         let mut net = Network {
             received_offset: vec![],
+            received_checksum: vec![],
             // When match_cnt == 1, return a mismatch error.
             // For other times, return Ok.
             match_cnt: 4,
@@ -453,6 +560,7 @@ mod tests {
                     last_log_id: None,
                     last_membership: StoredMembership::default(),
                     snapshot_id: "1-1-1-1".to_string(),
+                    checksum: None,
                 },
                 Box::new(Cursor::new(vec![1, 2, 3])),
             ),
@@ -464,4 +572,59 @@ mod tests {
 
         assert_eq!(net.received_offset, vec![0, 1, 2, 0, 1, 2]);
     }
+
+    /// The checksum of all data is sent only with the last chunk.
+    #[tokio::test]
+    async fn test_chunked_send_checksum_with_last_chunk() {
+        let mut net = Network {
+            received_offset: vec![],
+            received_checksum: vec![],
+            match_cnt: 0,
+        };
+
+        let mut opt = RPCOption::new(Duration::from_millis(100));
+        opt.snapshot_chunk_size = Some(2);
+        let cancel = futures::future::pending();
+
+        Chunked::send_snapshot(
+            &mut net,
+            Vote::new(1, 0),
+            Snapshot::<UTConfig>::new(
+                SnapshotMeta {
+                    last_log_id: None,
+                    last_membership: StoredMembership::default(),
+                    snapshot_id: "1-1-1-1".to_string(),
+                    checksum: Some(1),
+                },
+                Box::new(Cursor::new(vec![1, 2, 3])),
+            ),
+            cancel,
+            opt,
+        )
+        .await
+        .unwrap();
+
+        let mut checksum = Checksum::new();
+        checksum.update(&[1, 2, 3]);
+
+        assert_eq!(net.received_offset, vec![0, 2]);
+        assert_eq!(net.received_checksum, vec![None, Some(checksum.finish())]);
+    }
+
+    #[tokio::test]
+    async fn test_checksum_of_data() {
+        let mut checksum = Checksum::new();
+        checksum.update(b"a");
+        assert_eq!(0xaf63dc4c8601ec8c, checksum.finish(), "FNV-1a of 'a'");
+
+        let mut data = Cursor::new(b"foobar".to_vec());
+        data.set_position(3);
+
+        let mut checksum = Checksum::new();
+        checksum.update(b"foo");
+        checksum.update(b"bar");
+
+        assert_eq!(checksum.finish(), Checksum::of_data(&mut data).await.unwrap());
+        assert_eq!(0, data.position(), "seek back to start");
+    }
 }
@@ -1,13 +1,18 @@
 use std::time::Duration;
 
+use anyerror::AnyError;
 use openraft_macros::add_async_trait;
 
+use crate::error::CheckIsLeaderError;
+use crate::error::NetworkError;
 use crate::error::RPCError;
 use crate::error::RaftError;
 use crate::network::rpc_option::RPCOption;
 use crate::network::Backoff;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::ReadIndexRequest;
+use crate::raft::ReadIndexResponse;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::OptionalSend;
@@ -46,6 +51,26 @@ where C: RaftTypeConfig
         option: RPCOption,
     ) -> Result<VoteResponse<C>, RPCError<C, RaftError<C>>>;
 
+    /// Send a ReadIndex RPC to the leader, to get the log id a linearizable read on this node has
+    /// to wait for.
+    ///
+    /// It is only used by [`Raft::follower_read_linearizable()`]. The receiving node should
+    /// handle it with [`Raft::read_index()`].
+    ///
+    /// The default implementation returns a [`NetworkError`] saying it is not supported.
+    ///
+    /// [`Raft::follower_read_linearizable()`]: crate::Raft::follower_read_linearizable
+    /// [`Raft::read_index()`]: crate::Raft::read_index
+    /// [`NetworkError`]: crate::error::NetworkError
+    async fn read_index(
+        &mut self,
+        _rpc: ReadIndexRequest<C>,
+        _option: RPCOption,
+    ) -> Result<ReadIndexResponse<C>, RPCError<C, RaftError<C, CheckIsLeaderError<C>>>> {
+        let err = AnyError::error("ReadIndex RPC is not supported by this network");
+        Err(RPCError::Network(NetworkError::new(&err)))
+    }
+
     /// Build a backoff instance if the target node is temporarily(or permanently) unreachable.
     ///
     /// When a [`Unreachable`](`crate::error::Unreachable`) error is returned from the `Network`
@@ -1,5 +1,6 @@
 use std::future::Future;
 
+use crate::error::CheckIsLeaderError;
 use crate::error::Fatal;
 use crate::error::RPCError;
 use crate::error::RaftError;
@@ -10,6 +11,8 @@ use crate::network::Backoff;
 use crate::network::RPCOption;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::ReadIndexRequest;
+use crate::raft::ReadIndexResponse;
 use crate::raft::SnapshotResponse;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
@@ -41,6 +44,14 @@ where
         RaftNetwork::<C>::vote(self, rpc, option).await
     }
 
+    async fn read_index(
+        &mut self,
+        rpc: ReadIndexRequest<C>,
+        option: RPCOption,
+    ) -> Result<ReadIndexResponse<C>, RPCError<C, RaftError<C, CheckIsLeaderError<C>>>> {
+        RaftNetwork::<C>::read_index(self, rpc, option).await
+    }
+
     async fn full_snapshot(
         &mut self,
         vote: Vote<C::NodeId>,
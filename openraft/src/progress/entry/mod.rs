@@ -55,8 +55,6 @@ impl<NID: NodeId> ProgressEntry<NID> {
         }
     }
 
-    // This method is only used by tests.
-    #[allow(dead_code)]
     pub(crate) fn with_curr_inflight_id(mut self, v: u64) -> Self {
         self.curr_inflight_id = v;
         self
@@ -218,6 +218,26 @@ where
         0
     }
 
+    /// Reset the value of `id`, which may be smaller than the current value.
+    ///
+    /// Voters are re-sorted so that the values greater than the granted value are still in a
+    /// descending order. The granted value is not decreased.
+    ///
+    /// It returns `false` if the `id` is not found.
+    pub(crate) fn reset(&mut self, id: &ID, value: V) -> bool {
+        let Some(index) = self.index(id) else {
+            return false;
+        };
+
+        self.vector[index].1 = value;
+
+        if index < self.voter_count {
+            self.vector[..self.voter_count].sort_by(|a, b| b.1.borrow().cmp(a.1.borrow()));
+        }
+
+        true
+    }
+
     pub(crate) fn iter_mut(&mut self) -> IterMut<(ID, V)> {
         self.vector.iter_mut()
     }
@@ -530,6 +550,32 @@ mod t {
         }
     }
 
+    #[test]
+    fn vec_progress_reset() -> anyhow::Result<()> {
+        let quorum_set: Vec<u64> = vec![0, 1, 2];
+        let mut progress = VecProgress::<u64, u64, u64, _>::new(quorum_set, [6], 0);
+
+        let _ = progress.update(&0, 5);
+        let _ = progress.update(&1, 4);
+        let _ = progress.update(&2, 3);
+        let _ = progress.update(&6, 7);
+        assert_eq!(&4, progress.granted());
+
+        assert!(progress.reset(&1, 0));
+        assert_eq!(vec![(0, 5), (2, 3), (1, 0), (6, 7)], progress.vector);
+        assert_eq!(&4, progress.granted(), "granted value is not decreased");
+
+        assert!(progress.reset(&6, 1));
+        assert_eq!(&1, progress.get(&6));
+
+        assert!(!progress.reset(&9, 1), "nonexistent id");
+
+        // Update after reset still finds the granted value.
+        assert_eq!(Ok(&5), progress.update(&2, 6));
+
+        Ok(())
+    }
+
     #[test]
     fn vec_progress_update_struct_value() -> anyhow::Result<()> {
         let pv = |p, user_data| ProgressEntry { progress: p, user_data };
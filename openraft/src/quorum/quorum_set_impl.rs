@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 
 use crate::quorum::quorum_set::QuorumSet;
@@ -76,3 +77,30 @@ where ID: PartialOrd + Ord + Copy + 'static
         BTreeSet::from_iter(self.iter().copied()).into_iter()
     }
 }
+
+/// Impl a weighted majority quorum set: a map of id to its weight.
+///
+/// A set of ids is a quorum if the sum of their weights is greater than half of the total weight.
+impl<ID> QuorumSet<ID> for BTreeMap<ID, u64>
+where ID: PartialOrd + Ord + Copy + 'static
+{
+    type Iter = std::collections::btree_map::IntoKeys<ID, u64>;
+
+    fn is_quorum<'a, I: Iterator<Item = &'a ID> + Clone>(&self, ids: I) -> bool {
+        let mut weight = 0u128;
+        let limit = self.values().map(|w| *w as u128).sum::<u128>();
+        for id in ids {
+            if let Some(w) = self.get(id) {
+                weight += 2 * (*w as u128);
+                if weight > limit {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn ids(&self) -> Self::Iter {
+        self.clone().into_keys()
+    }
+}
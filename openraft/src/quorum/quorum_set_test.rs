@@ -1,3 +1,4 @@
+use maplit::btreemap;
 use maplit::btreeset;
 
 use crate::quorum::AsJoint;
@@ -30,6 +31,28 @@ fn test_simple_quorum_set_impl() -> anyhow::Result<()> {
         assert!(m12345.is_quorum([1, 3, 4, 5].iter()));
     }
 
+    // BTreeMap as weighted majority quorum set
+    {
+        let m1_2_3_4 = btreemap! {1=>1,2=>2,3=>3,4=>4};
+
+        assert!(!m1_2_3_4.is_quorum([0].iter()));
+        assert!(!m1_2_3_4.is_quorum([4].iter()));
+        assert!(!m1_2_3_4.is_quorum([1, 4].iter()));
+        assert!(!m1_2_3_4.is_quorum([2, 3].iter()));
+        assert!(m1_2_3_4.is_quorum([2, 4].iter()));
+        assert!(m1_2_3_4.is_quorum([1, 2, 3].iter()));
+        assert!(m1_2_3_4.is_quorum([0, 3, 4].iter()));
+    }
+
+    // BTreeMap with all weights being 1 is the same as majority quorum set
+    {
+        let m12345 = btreemap! {1=>1,2=>1,3=>1,4=>1,5=>1};
+
+        assert!(!m12345.is_quorum([0, 1, 2].iter()));
+        assert!(m12345.is_quorum([1, 2, 3].iter()));
+        assert!(m12345.is_quorum([3, 4, 5].iter()));
+    }
+
     Ok(())
 }
 
@@ -87,6 +110,17 @@ fn test_joint_quorum_set_impl() -> anyhow::Result<()> {
         assert!(qs.is_quorum([1, 2, 3, 4, 7, 8].iter()));
     }
 
+    // Vec<BTreeMap, BTreeMap> into Joint, as joint-of-weighted-majority quorum set
+    {
+        let m123_45 = vec![btreemap! {1=>3,2=>1,3=>1}, btreemap! {4=>1,5=>2}];
+        let qs = Joint::from(m123_45);
+
+        assert!(!qs.is_quorum([1].iter()));
+        assert!(!qs.is_quorum([2, 3, 5].iter()));
+        assert!(!qs.is_quorum([1, 4].iter()));
+        assert!(qs.is_quorum([1, 5].iter()));
+    }
+
     // Vec<Vec, Vec> into Joint
     {
         let m12345_678 = vec![vec![1, 2, 3, 4, 5], vec![6, 7, 8]];
@@ -119,6 +153,11 @@ fn test_ids() -> anyhow::Result<()> {
         assert_eq!(btreeset! {1,2,3,4,5}, m12345.ids().collect());
     }
 
+    {
+        let m12345 = btreemap! {1=>1,2=>2,3=>1,4=>1,5=>3};
+        assert_eq!(btreeset! {1,2,3,4,5}, m12345.ids().collect());
+    }
+
     {
         let m12345_678 = vec![btreeset! {1,2,3,4,5}, btreeset! {4,5,6,7,8}];
         let qs = m12345_678.as_joint();
@@ -2,10 +2,15 @@
 //! Blocking mode write API blocks until the write operation is completed,
 //! where [`RaftTypeConfig::Responder`] is a [`OneshotResponder`].
 
+use std::time::Duration;
+
 use maplit::btreemap;
+use maplit::btreeset;
 
 use crate::core::raft_msg::RaftMsg;
+use crate::error::ChangeMembershipError;
 use crate::error::ClientWriteError;
+use crate::error::NotLearner;
 use crate::error::RaftError;
 use crate::raft::message::ClientWriteResult;
 use crate::raft::responder::OneshotResponder;
@@ -13,6 +18,7 @@ use crate::raft::ClientWriteResponse;
 use crate::type_config::alias::OneshotReceiverOf;
 use crate::AsyncRuntime;
 use crate::ChangeMembers;
+use crate::LogId;
 use crate::Raft;
 use crate::RaftTypeConfig;
 
@@ -24,7 +30,12 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
     /// Propose a cluster configuration change.
     ///
     /// A node in the proposed config has to be a learner, otherwise it fails with LearnerNotFound
-    /// error.
+    /// error. If [`Config::reject_lagging_new_voters`] is enabled, a learner to become a voter has
+    /// to be line-rate, i.e., its replication lag is within [`Config::replication_lag_threshold`],
+    /// otherwise it fails with LearnerNotReady error.
+    ///
+    /// [`Config::reject_lagging_new_voters`]: crate::Config::reject_lagging_new_voters
+    /// [`Config::replication_lag_threshold`]: crate::Config::replication_lag_threshold
     ///
     /// Internally:
     /// - It proposes a **joint** config.
@@ -44,6 +55,14 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
     ///
     /// If it loses leadership or crashed before committing the second **uniform** config log, the
     /// cluster is left in the **joint** config.
+    ///
+    /// A change rejected for a transient reason is retried up to
+    /// [`Config::change_membership_retries`] times, see
+    /// [`ChangeMembershipError::is_retriable()`]. Retrying is idempotent: the change is applied to
+    /// the effective config, thus a change left in the **joint** config continues to the
+    /// **uniform** one, and a change already in effect just commits the same config again.
+    ///
+    /// [`Config::change_membership_retries`]: crate::Config::change_membership_retries
     #[tracing::instrument(level = "info", skip_all)]
     pub async fn change_membership(
         &self,
@@ -52,6 +71,39 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
     ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
         let changes: ChangeMembers<C::NodeId, C::Node> = members.into();
 
+        let mut retries = self.inner.config.change_membership_retries;
+        let mut backoff = Duration::from_millis(self.inner.config.heartbeat_interval);
+        // The wait between retries does not grow beyond an election timeout.
+        let max_backoff = Duration::from_millis(self.inner.config.election_timeout_max);
+
+        loop {
+            let res = self.do_change_membership(changes.clone(), retain).await;
+
+            match &res {
+                Err(RaftError::APIError(ClientWriteError::ChangeMembershipError(e)))
+                    if retries > 0 && e.is_retriable() =>
+                {
+                    tracing::info!(
+                        error = display(e),
+                        retries = display(retries),
+                        backoff = debug(backoff),
+                        "change_membership: retry later"
+                    );
+                }
+                _ => return res,
+            }
+
+            retries -= 1;
+            C::AsyncRuntime::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2).min(max_backoff);
+        }
+    }
+
+    async fn do_change_membership(
+        &self,
+        changes: ChangeMembers<C::NodeId, C::Node>,
+        retain: bool,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
         tracing::info!(
             changes = debug(&changes),
             retain = display(retain),
@@ -104,6 +156,34 @@ where C: RaftTypeConfig<Responder = OneshotResponder<C>>
         Ok(res)
     }
 
+    /// Promote a learner to a voter and return the log id of the committed final config.
+    ///
+    /// It is a shortcut of [`Raft::change_membership()`] with
+    /// [`ChangeMembers::AddVoterIds`], which in addition rejects a node that is already a voter
+    /// with a `NotLearner` error. A node that is not a learner fails with `LearnerNotFound`, and if
+    /// [`Config::reject_lagging_new_voters`] is enabled, a learner that is not yet line-rate fails
+    /// with `LearnerNotReady`, see [`Raft::await_line_rate()`].
+    ///
+    /// [`Config::reject_lagging_new_voters`]: crate::Config::reject_lagging_new_voters
+    #[tracing::instrument(level = "info", skip(self, id), fields(target=display(id)))]
+    pub async fn promote_learner(&self, id: C::NodeId) -> Result<LogId<C::NodeId>, RaftError<C, ClientWriteError<C>>> {
+        let effective =
+            self.with_raft_state(|st| st.membership_state.effective().clone()).await.map_err(RaftError::Fatal)?;
+
+        if effective.is_voter(&id) {
+            let err = NotLearner {
+                node_id: id,
+                committed: *effective.log_id(),
+            };
+            return Err(RaftError::APIError(ClientWriteError::ChangeMembershipError(
+                ChangeMembershipError::NotLearner(err),
+            )));
+        }
+
+        let resp = self.change_membership(ChangeMembers::AddVoterIds(btreeset! {id}), false).await?;
+        Ok(resp.log_id)
+    }
+
     /// Add a new learner raft node, optionally, blocking until up-to-speed.
     ///
     /// - Add a node as learner into the cluster.
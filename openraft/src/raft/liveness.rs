@@ -0,0 +1,110 @@
+//! A cheap snapshot of whether a Raft node is alive and connected to a leader.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::type_config::alias::InstantOf;
+use crate::Instant;
+use crate::RaftTypeConfig;
+use crate::ServerState;
+
+/// A small liveness report of a Raft node, returned by [`Raft::liveness()`].
+///
+/// Unlike [`RaftMetrics`], it is read from a few atomic values shared with `RaftCore`, without
+/// allocating, without touching storage and without borrowing the metrics watch channel. It is
+/// meant to be polled frequently, such as by a health-check endpoint.
+///
+/// [`Raft::liveness()`]: crate::Raft::liveness
+/// [`RaftMetrics`]: crate::RaftMetrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Liveness {
+    /// The server state of this node.
+    pub state: ServerState,
+
+    /// Whether this node knows of a leader, which may be itself.
+    pub has_leader: bool,
+
+    /// How long ago the leader was last heard from.
+    ///
+    /// On a leader, it is the time since a quorum last acknowledged it. On other nodes, it is the
+    /// time since the vote was last granted or refreshed by a leader. `None` if it never happened.
+    pub last_heartbeat_age: Option<Duration>,
+}
+
+/// The values of [`Liveness`], written by `RaftCore` and read by [`Raft::liveness()`].
+///
+/// [`Raft::liveness()`]: crate::Raft::liveness
+pub(crate) struct LivenessState<C>
+where C: RaftTypeConfig
+{
+    state: AtomicU8,
+    has_leader: AtomicBool,
+
+    /// The instant from which `last_heartbeat` is measured.
+    base: InstantOf<C>,
+
+    /// Nanoseconds from `base` to the last heartbeat, plus 1. `0` means no heartbeat.
+    last_heartbeat: AtomicU64,
+}
+
+impl<C> LivenessState<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            state: AtomicU8::new(encode_state(ServerState::default())),
+            has_leader: AtomicBool::new(false),
+            base: InstantOf::<C>::now(),
+            last_heartbeat: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn update(&self, state: ServerState, has_leader: bool, last_heartbeat: Option<InstantOf<C>>) {
+        let last_heartbeat = match last_heartbeat {
+            Some(t) if t >= self.base => (t - self.base).as_nanos() as u64 + 1,
+            Some(_) => 1,
+            None => 0,
+        };
+
+        self.state.store(encode_state(state), Ordering::Relaxed);
+        self.has_leader.store(has_leader, Ordering::Relaxed);
+        self.last_heartbeat.store(last_heartbeat, Ordering::Relaxed);
+    }
+
+    pub(crate) fn load(&self) -> Liveness {
+        let last_heartbeat = self.last_heartbeat.load(Ordering::Relaxed);
+        let last_heartbeat_age = match last_heartbeat {
+            0 => None,
+            n => Some((self.base + Duration::from_nanos(n - 1)).elapsed()),
+        };
+
+        Liveness {
+            state: decode_state(self.state.load(Ordering::Relaxed)),
+            has_leader: self.has_leader.load(Ordering::Relaxed),
+            last_heartbeat_age,
+        }
+    }
+}
+
+fn encode_state(state: ServerState) -> u8 {
+    match state {
+        ServerState::Learner => 0,
+        ServerState::Follower => 1,
+        ServerState::Candidate => 2,
+        ServerState::Leader => 3,
+        ServerState::Shutdown => 4,
+    }
+}
+
+fn decode_state(v: u8) -> ServerState {
+    match v {
+        0 => ServerState::Learner,
+        1 => ServerState::Follower,
+        2 => ServerState::Candidate,
+        3 => ServerState::Leader,
+        _ => ServerState::Shutdown,
+    }
+}
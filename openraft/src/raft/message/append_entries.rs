@@ -2,6 +2,7 @@ use std::fmt;
 
 use crate::display_ext::DisplayOptionExt;
 use crate::display_ext::DisplaySlice;
+use crate::error::TermRegression;
 use crate::LogId;
 use crate::RaftTypeConfig;
 use crate::Vote;
@@ -92,6 +93,12 @@ pub enum AppendEntriesResponse<C: RaftTypeConfig> {
     /// And a leader's vote(committed vote) must be total order with other vote.
     /// Therefore it has to be a higher vote: `mine_vote < v`
     HigherVote(Vote<C::NodeId>),
+
+    /// The log ids of the entries to send are not in order: the term of an entry is less than the
+    /// term of the log id before it.
+    ///
+    /// The remote target node did not store any of the entries.
+    TermRegression(TermRegression<C>),
 }
 
 impl<C> AppendEntriesResponse<C>
@@ -117,6 +124,7 @@ where C: RaftTypeConfig
             }
             AppendEntriesResponse::HigherVote(vote) => write!(f, "Higher vote, {}", vote),
             AppendEntriesResponse::Conflict => write!(f, "Conflict"),
+            AppendEntriesResponse::TermRegression(e) => write!(f, "TermRegression: {}", e),
         }
     }
 }
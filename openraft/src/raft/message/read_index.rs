@@ -0,0 +1,49 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::LogId;
+use crate::RaftTypeConfig;
+
+/// An RPC sent by a follower to the leader, to get the log id up to which the follower has to
+/// apply before serving a linearizable read.
+///
+/// See [`Raft::follower_read_linearizable()`](crate::Raft::follower_read_linearizable).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ReadIndexRequest<C: RaftTypeConfig> {
+    /// The id of the node that sends this request.
+    pub from: C::NodeId,
+}
+
+impl<C> fmt::Display for ReadIndexRequest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{from:{}}}", self.from)
+    }
+}
+
+impl<C> ReadIndexRequest<C>
+where C: RaftTypeConfig
+{
+    pub fn new(from: C::NodeId) -> Self {
+        Self { from }
+    }
+}
+
+/// The response to a [`ReadIndexRequest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ReadIndexResponse<C: RaftTypeConfig> {
+    /// The log id up to which a read has to wait for the state machine to apply, as confirmed by
+    /// the leader with a quorum.
+    pub read_log_id: Option<LogId<C::NodeId>>,
+}
+
+impl<C> fmt::Display for ReadIndexResponse<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{read_log_id:{}}}", self.read_log_id.display())
+    }
+}
@@ -11,34 +11,48 @@
 #[cfg(test)] mod declare_raft_types_test;
 mod external_request;
 mod impl_raft_blocking_write;
+pub(crate) mod liveness;
 pub(crate) mod message;
+mod observer;
+pub(crate) mod quorum_policy;
 mod raft_inner;
 pub mod responder;
 mod runtime_config_handle;
 pub mod trigger;
 
 use std::collections::BTreeMap;
+use std::collections::VecDeque;
 use std::error::Error;
 
 pub(crate) use self::external_request::BoxCoreFn;
 
 pub(in crate::raft) mod core_state;
 
+use std::any::TypeId;
 use std::fmt::Debug;
 use std::future::Future;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
 use core_state::CoreState;
+use futures::Stream;
+pub use liveness::Liveness;
 pub use message::AppendEntriesRequest;
 pub use message::AppendEntriesResponse;
 pub use message::ClientWriteResponse;
 pub use message::ClientWriteResult;
 pub use message::InstallSnapshotRequest;
 pub use message::InstallSnapshotResponse;
+pub use message::ReadIndexRequest;
+pub use message::ReadIndexResponse;
 pub use message::SnapshotResponse;
 pub use message::VoteRequest;
 pub use message::VoteResponse;
+pub use observer::RaftObserver;
+pub use quorum_policy::MajorityQuorumPolicy;
+pub use quorum_policy::QuorumPolicy;
+use tokio::sync::broadcast;
 use tokio::sync::mpsc;
 use tokio::sync::watch;
 use tokio::sync::Mutex;
@@ -50,9 +64,13 @@ use crate::async_runtime::AsyncOneshotSendExt;
 use crate::config::Config;
 use crate::config::RuntimeConfig;
 use crate::core::command_state::CommandState;
+use crate::core::durable_log::DurableLog;
+use crate::core::election_timeout_rng::ElectionTimeoutRng;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::core::raft_msg::RaftMsg;
+use crate::core::read_freshness::ReadFreshness;
 use crate::core::replication_lag;
+use crate::core::sm;
 use crate::core::sm::worker;
 use crate::core::RaftCore;
 use crate::core::Tick;
@@ -61,35 +79,60 @@ use crate::engine::EngineConfig;
 use crate::error::CheckIsLeaderError;
 use crate::error::ClientWriteError;
 use crate::error::Fatal;
+use crate::error::FollowerReadError;
+use crate::error::FollowerReadTimeout;
+use crate::error::ForwardToLeader;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::error::InvalidStateMachineType;
+use crate::error::LineRateError;
+use crate::error::LineRateTimeout;
+use crate::error::NotInMembers;
 use crate::error::RaftError;
+use crate::error::ReplayError;
+use crate::error::UncommittedEntriesError;
 use crate::membership::IntoNodes;
 use crate::metrics::RaftDataMetrics;
+use crate::metrics::RaftEvent;
 use crate::metrics::RaftMetrics;
 use crate::metrics::RaftServerMetrics;
+use crate::metrics::RaftStatus;
+use crate::metrics::SnapshotInstallProgress;
 use crate::metrics::Wait;
 use crate::metrics::WaitError;
+use crate::raft::liveness::LivenessState;
 use crate::raft::raft_inner::RaftInner;
 use crate::raft::responder::Responder;
 pub use crate::raft::runtime_config_handle::RuntimeConfigHandle;
 use crate::raft::trigger::Trigger;
+use crate::raft_state::LogStateReader;
 use crate::storage::RaftLogStorage;
 use crate::storage::RaftStateMachine;
 use crate::type_config::alias::AsyncRuntimeOf;
+use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::JoinErrorOf;
 use crate::type_config::alias::ResponderOf;
 use crate::type_config::alias::ResponderReceiverOf;
 use crate::type_config::alias::SnapshotDataOf;
 use crate::AsyncRuntime;
+use crate::BoxFuture;
+use crate::ClusterCommitView;
+use crate::Electability;
+use crate::Instant;
+use crate::LeadershipDiag;
+use crate::LogEntrySummary;
 use crate::LogId;
 use crate::LogIdOptionExt;
+use crate::MembershipState;
 use crate::OptionalSend;
 use crate::RaftNetworkFactory;
 use crate::RaftState;
 pub use crate::RaftTypeConfig;
+use crate::ServerState;
 use crate::Snapshot;
+use crate::SnapshotMeta;
 use crate::StorageHelper;
+use crate::Topology;
 use crate::Vote;
 
 /// Define types for a Raft type configuration.
@@ -244,6 +287,7 @@ where C: RaftTypeConfig
         let (tx_metrics, rx_metrics) = watch::channel(RaftMetrics::new_initial(id));
         let (tx_data_metrics, rx_data_metrics) = watch::channel(RaftDataMetrics::default());
         let (tx_server_metrics, rx_server_metrics) = watch::channel(RaftServerMetrics::default());
+        let (tx_events, _) = broadcast::channel(config.event_channel_capacity);
         let (tx_shutdown, rx_shutdown) = C::AsyncRuntime::oneshot();
 
         let tick_handle = Tick::spawn(
@@ -253,6 +297,7 @@ where C: RaftTypeConfig
         );
 
         let runtime_config = Arc::new(RuntimeConfig::new(&config));
+        let liveness = Arc::new(LivenessState::new());
 
         let core_span = tracing::span!(
             parent: tracing::Span::current(),
@@ -262,21 +307,27 @@ where C: RaftTypeConfig
             cluster = display(&config.cluster_name)
         );
 
-        let eng_config = EngineConfig::new(id, config.as_ref());
+        let mut election_timeout_rng = ElectionTimeoutRng::new(&id, config.as_ref());
+        let election_timeout = election_timeout_rng.next_timeout::<C::AsyncRuntime>();
+        let eng_config = EngineConfig::new(id, config.as_ref(), election_timeout);
 
         let state = {
             let mut helper = StorageHelper::new(&mut log_store, &mut state_machine);
             helper.get_initial_state().await?
         };
 
+        let last_applied = state.io_applied().copied();
+        let last_log_id = state.last_log_id().copied();
+
         let engine = Engine::new(state, eng_config);
 
-        let sm_handle = worker::Worker::spawn(state_machine, tx_notify.clone());
+        let sm_handle = worker::Worker::spawn(state_machine, last_applied, tx_notify.clone());
 
         let core: RaftCore<C, N, LS, SM> = RaftCore {
             id,
             config: config.clone(),
             runtime_config: runtime_config.clone(),
+            liveness: liveness.clone(),
             network,
             log_store,
             sm_handle,
@@ -295,9 +346,29 @@ where C: RaftTypeConfig
 
             tx_metrics,
             tx_data_metrics,
+            tx_events: tx_events.clone(),
             tx_server_metrics,
 
             command_state: CommandState::default(),
+            read_freshness: ReadFreshness::new(),
+            observer: None,
+            storage_metrics: Default::default(),
+            replication_log_purged: 0,
+            replication_rejections: BTreeMap::new(),
+            installing_snapshot: None,
+            durable_log: DurableLog::new(last_log_id),
+            election_timeout_rng,
+            pending_flush: None,
+            stepped_down_at: None,
+            quiesced_until: None,
+            leaderless_since: None,
+            last_tick_at: None,
+            storage_degraded: None,
+            draining: false,
+            drain_waiters: Vec::new(),
+            member_since: None,
+            removed_waiters: Vec::new(),
+            leadership_epoch: 0,
             span: core_span,
 
             _p: Default::default(),
@@ -309,11 +380,14 @@ where C: RaftTypeConfig
             id,
             config,
             runtime_config,
+            liveness,
             tick_handle,
             tx_api,
             rx_metrics,
             rx_data_metrics,
             rx_server_metrics,
+            tx_events,
+            sm_type: (TypeId::of::<SM>(), std::any::type_name::<SM>()),
             tx_shutdown: Mutex::new(Some(tx_shutdown)),
             core_state: Mutex::new(CoreState::Running(core_handle)),
 
@@ -343,6 +417,23 @@ where C: RaftTypeConfig
         &self.inner.config
     }
 
+    /// Return the config this Raft node is currently running with.
+    ///
+    /// Unlike [`Raft::config()`], which returns the config the node is started with, the returned
+    /// config reflects the changes made with [`Raft::runtime_config()`], i.e., the current
+    /// values of [`Config::enable_tick`], [`Config::enable_heartbeat`] and
+    /// [`Config::enable_elect`]. Other fields can not be changed at runtime and are the same as
+    /// the startup config.
+    pub fn effective_config(&self) -> Config {
+        let mut config = self.inner.config.as_ref().clone();
+
+        config.enable_tick = self.inner.tick_handle.is_enabled();
+        config.enable_heartbeat = self.inner.runtime_config.enable_heartbeat.load(Ordering::Relaxed);
+        config.enable_elect = self.inner.runtime_config.enable_elect.load(Ordering::Relaxed);
+
+        config
+    }
+
     /// Return a handle to manually trigger raft actions, such as elect or build snapshot.
     ///
     /// Example:
@@ -363,7 +454,7 @@ where C: RaftTypeConfig
         tracing::debug!(rpc = display(&rpc), "Raft::append_entries");
 
         let (tx, rx) = C::AsyncRuntime::oneshot();
-        self.inner.call_core(RaftMsg::AppendEntries { rpc, tx }, rx).await
+        self.inner.call_core(RaftMsg::AppendEntries { rpc, tx }, rx).await.map_err(|e| e.flatten_fatal())
     }
 
     /// Submit a VoteRequest (RequestVote in the spec) RPC to this Raft node.
@@ -375,7 +466,7 @@ where C: RaftTypeConfig
         tracing::info!(rpc = display(&rpc), "Raft::vote()");
 
         let (tx, rx) = C::AsyncRuntime::oneshot();
-        self.inner.call_core(RaftMsg::RequestVote { rpc, tx }, rx).await
+        self.inner.call_core(RaftMsg::RequestVote { rpc, tx }, rx).await.map_err(|e| e.flatten_fatal())
     }
 
     /// Get the latest snapshot from the state machine.
@@ -391,6 +482,141 @@ where C: RaftTypeConfig
         self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
     }
 
+    /// Get the metadata of the current snapshot, without reading the snapshot data.
+    ///
+    /// It returns `None` if there is no snapshot yet. Unlike [`Raft::get_snapshot`], it does not
+    /// access the state machine: the metadata of the last built or installed snapshot is kept by
+    /// `RaftCore`. It is a cheap way to find out the last log id a snapshot covers, e.g., to decide
+    /// whether a backup is fresh enough.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn snapshot_meta(&self) -> Result<Option<SnapshotMeta<C>>, RaftError<C>> {
+        let meta = self.with_raft_state(|st| st.snapshot_meta.clone()).await?;
+
+        if meta.last_log_id.is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(meta))
+    }
+
+    /// Returns the last log id that has been purged from the log store, inclusive.
+    ///
+    /// Logs at or before this log id are purged, or are being purged, from the log store and are
+    /// only available in the snapshot, which always includes them. They should not be read with
+    /// [`RaftLogReader::try_get_log_entries()`]. It returns `None` if no log has been purged.
+    ///
+    /// It is read from `RaftCore` and is up to date, while [`RaftMetrics::purged`] may lag
+    /// behind.
+    ///
+    /// [`RaftLogReader::try_get_log_entries()`]: crate::storage::RaftLogReader::try_get_log_entries
+    /// [`RaftMetrics::purged`]: crate::RaftMetrics::purged
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn last_purged_log_id(&self) -> Result<Option<LogId<C::NodeId>>, RaftError<C>> {
+        let purged = self.with_raft_state(|st| st.last_purged_log_id().copied()).await?;
+
+        Ok(purged)
+    }
+
+    /// Returns the committed and the effective membership config of this node.
+    ///
+    /// It tells whether a membership change is in progress, with
+    /// [`MembershipState::is_in_transition()`], and what voter set the cluster transitions to, with
+    /// [`MembershipState::target()`]. The joint config, if any, is in
+    /// [`MembershipState::effective()`].
+    ///
+    /// It is read from `RaftCore` and is up to date, while [`RaftMetrics::membership_config`] may
+    /// lag behind.
+    ///
+    /// [`RaftMetrics::membership_config`]: crate::RaftMetrics::membership_config
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn membership_state(&self) -> Result<MembershipState<C>, Fatal<C>> {
+        self.with_raft_state(|st| st.membership_state.clone()).await
+    }
+
+    /// Estimate whether this node could win an election if it started one right now.
+    ///
+    /// On a leader it is always [`Electability::Leader`]. On other nodes it is a heuristic based on
+    /// the local log and the last seen leader: e.g., a voter that has not yet received any log
+    /// from the current leader can not win, because a quorum has a greater log. It helps operators
+    /// predict which node may take over if the leader dies.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn estimated_electability(&self) -> Result<Electability<C>, Fatal<C>> {
+        let id = self.inner.id;
+        self.with_raft_state(move |st| st.electability(&id)).await
+    }
+
+    /// Returns a report explaining the leadership status of this node.
+    ///
+    /// It aggregates the state used to decide whether this node is, or could become, the leader:
+    /// the server state, the current term and leader, whether its log is behind, whether it is
+    /// receiving heartbeats from a leader and the outcome of the last election it started. See
+    /// [`LeadershipDiag`].
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn leadership_diagnostics(&self) -> Result<LeadershipDiag<C>, RaftError<C>> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let cmd = ExternalCommand::GetLeadershipDiag { tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
+    /// Returns every node in the effective membership of this node with its role.
+    ///
+    /// If this node is the leader, every node also has its replication state: the last log id
+    /// replicated to it and how many logs it lags behind. On other nodes the replication state is
+    /// `None`. See [`Topology`].
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn cluster_topology(&self) -> Result<Topology<C>, RaftError<C>> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let cmd = ExternalCommand::GetTopology { tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
+    /// Returns the replication and commit state of every node, gathered by the leader in one pass.
+    ///
+    /// It includes the leader's own committed, last and applied log ids, and for every node it
+    /// replicates to, the last log id replicated to it and its estimated committed log id. It is
+    /// best-effort: the per-node state is the last known one on the leader, without contacting
+    /// the nodes. It provides a single snapshot of the replication health for debugging, instead of
+    /// collecting metrics from every node separately. See [`ClusterCommitView`].
+    ///
+    /// It returns a [`ForwardToLeader`] error if this node is not the leader.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn cluster_commit_view(&self) -> Result<ClusterCommitView<C>, RaftError<C, ForwardToLeader<C>>> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let cmd = ExternalCommand::GetClusterCommitView { tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
+    /// Returns the [`LogId`] this node has at `index`, for checking whether nodes agree on a log
+    /// without transferring the logs.
+    ///
+    /// The log id is looked up in the local log, or in the snapshot meta if the log at `index` is
+    /// already purged and is the last one included in the snapshot. It returns `None` if this node
+    /// does not know the log id at `index`, i.e., `index` is beyond the last log, or is purged and
+    /// is not the last log of the snapshot.
+    ///
+    /// Two nodes that return the same log id at an index have identical logs up to this index.
+    /// Comparing the results from different nodes, e.g., by a binary search over indexes, quickly
+    /// locates where their logs diverge.
+    ///
+    /// Example:
+    /// ```ignore
+    /// let a = raft_a.log_consistency_probe(100).await?;
+    /// let b = raft_b.log_consistency_probe(100).await?;
+    /// if a.is_some() && b.is_some() && a != b {
+    ///     println!("logs diverge at or before index 100");
+    /// }
+    /// ```
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn log_consistency_probe(&self, index: u64) -> Result<Option<LogId<C::NodeId>>, RaftError<C>> {
+        let log_id = self
+            .with_raft_state(move |st| {
+                st.get_log_id(index).or_else(|| st.snapshot_last_log_id().copied().filter(|x| x.index == index))
+            })
+            .await?;
+
+        Ok(log_id)
+    }
+
     /// Get a snapshot data for receiving snapshot from the leader.
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn begin_receiving_snapshot(&self) -> Result<Box<SnapshotDataOf<C>>, RaftError<C, Infallible>> {
@@ -462,8 +688,21 @@ where C: RaftTypeConfig
             use crate::network::snapshot_transport::Chunked;
             use crate::network::snapshot_transport::SnapshotTransport;
 
+            let last_log_id = req.meta.last_log_id;
+
             let mut streaming = self.inner.snapshot.lock().await;
-            Chunked::receive_snapshot(&mut *streaming, self, req).await?
+            let res = Chunked::receive_snapshot(&mut *streaming, self, req).await;
+
+            // Report the progress even if receiving failed, because a failure may end receiving
+            // the snapshot, e.g., on a checksum mismatch.
+            let progress = streaming.as_ref().map(|s| SnapshotInstallProgress {
+                snapshot_id: s.snapshot_id().clone(),
+                last_log_id,
+                bytes_received: s.offset(),
+            });
+            self.inner.send_msg(RaftMsg::SnapshotChunkReceived { progress }).await?;
+
+            res?
         };
 
         if let Some(snapshot) = finished_snapshot {
@@ -483,6 +722,45 @@ where C: RaftTypeConfig
         self.metrics().borrow().current_leader
     }
 
+    /// Give up the leadership and become a follower, without leaving the cluster.
+    ///
+    /// Unlike shutting down the node, this node keeps running as a follower. No successor is
+    /// chosen: other nodes stop receiving heartbeats and a new leader is elected by a normal
+    /// election, after the election timeout. This node takes part in that election as well, but
+    /// does not start it earlier than other nodes, even if [`Config::leader_affinity_window`] is
+    /// set.
+    ///
+    /// It returns a [`ForwardToLeader`] error if this node is not a leader.
+    ///
+    /// [`Config::leader_affinity_window`]: crate::Config::leader_affinity_window
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn step_down(&self) -> Result<(), RaftError<C, ForwardToLeader<C>>> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let cmd = ExternalCommand::StepDown { quiesce: None, tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
+    /// Give up the leadership as [`Raft::step_down()`] does, and do not start an election for
+    /// `duration`.
+    ///
+    /// Other nodes elect a new leader without this node, which can not win an election it does not
+    /// start. It still votes for other candidates. This is useful for failover testing, or for
+    /// draining a node without removing it from the membership. An explicit
+    /// [`Trigger::elect()`] still starts an election during `duration`.
+    ///
+    /// It returns a [`ForwardToLeader`] error if this node is not a leader.
+    ///
+    /// [`Trigger::elect()`]: crate::raft::trigger::Trigger::elect
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn yield_leadership_for(&self, duration: Duration) -> Result<(), RaftError<C, ForwardToLeader<C>>> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let cmd = ExternalCommand::StepDown {
+            quiesce: Some(duration),
+            tx,
+        };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
     /// Check to ensure this node is still the cluster leader, in order to guard against stale reads
     /// (§8).
     ///
@@ -575,6 +853,119 @@ where C: RaftTypeConfig
         Ok((read_log_id, applied))
     }
 
+    /// Same as [`get_read_log_id()`](Raft::get_read_log_id), but confirms the leadership with the
+    /// leader lease instead of sending heartbeats to a quorum.
+    ///
+    /// The leadership is confirmed if a quorum has acknowledged this leader within
+    /// [`Config::read_lease`]. Otherwise a [`QuorumNotEnough`] error is returned, as soon as the
+    /// quorum contact is lost, even if this node has not yet stepped down. Writes are not affected
+    /// by this check.
+    ///
+    /// [`Config::read_lease`]: crate::Config::read_lease
+    /// [`QuorumNotEnough`]: crate::error::QuorumNotEnough
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn client_read_lease(
+        &self,
+    ) -> Result<(Option<LogId<C::NodeId>>, Option<LogId<C::NodeId>>), RaftError<C, CheckIsLeaderError<C>>> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let (read_log_id, applied) = self.inner.call_core(RaftMsg::LeaseReadRequest { tx }, rx).await?;
+        Ok((read_log_id, applied))
+    }
+
+    /// Check if a read on the local state machine is at most `max_staleness` behind the leader.
+    ///
+    /// Unlike [`Raft::ensure_linearizable()`], it does not contact other nodes and can be called
+    /// on a follower or learner. The local state machine is considered fresh as of the latest time
+    /// at which it had applied every log that the leader reported as committed, in an AppendEntries
+    /// received at that time. On the leader it is the last time a quorum acknowledged it.
+    ///
+    /// On success, it returns the last applied log id, which the local read reflects.
+    /// It returns a [`ForwardToLeader`] error if the local state machine is too stale, and the
+    /// application should send the read to the leader instead.
+    ///
+    /// The staleness is measured with the local clock when AppendEntries are received, thus it
+    /// does not include the network delay from the leader.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn follower_read(
+        &self,
+        max_staleness: Duration,
+    ) -> Result<Option<LogId<C::NodeId>>, RaftError<C, ForwardToLeader<C>>> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let cmd = ExternalCommand::FollowerRead { max_staleness, tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
+    /// Ensures a read from the local state machine of a follower is linearizable, with the help
+    /// of the leader.
+    ///
+    /// It sends a ReadIndex request to the current leader with [`RaftNetworkV2::read_index()`].
+    /// The leader confirms its leadership with a quorum, as [`Raft::get_read_log_id()`] does, and
+    /// returns the log id up to which a read has to wait for. Then it waits for the local state
+    /// machine to apply up to this log id and returns it. The leader only takes part in
+    /// confirming the leadership; the read itself is served by this node.
+    ///
+    /// On the leader, it is the same as [`Raft::ensure_linearizable()`].
+    ///
+    /// It returns a [`ForwardToLeader`] error if no leader is known, and an RPC error if the
+    /// request to the leader fails, including when the leader fails to confirm its leadership. It
+    /// returns a [`FollowerReadTimeout`] error if the local state machine does not apply up to the
+    /// read log id within `timeout`, e.g., when this node is partitioned from the leader after the
+    /// ReadIndex request. It requires the network to implement [`RaftNetworkV2::read_index()`],
+    /// and the leader to handle it with [`Raft::read_index()`].
+    ///
+    /// [`RaftNetworkV2::read_index()`]: crate::network::v2::RaftNetworkV2::read_index
+    /// [`FollowerReadTimeout`]: crate::error::FollowerReadTimeout
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn follower_read_linearizable(
+        &self,
+        timeout: Duration,
+    ) -> Result<Option<LogId<C::NodeId>>, RaftError<C, FollowerReadError<C>>> {
+        if self.current_leader().await == Some(self.inner.id) {
+            return self.ensure_linearizable().await.map_err(|e| match e {
+                RaftError::APIError(e) => RaftError::APIError(e.into()),
+                RaftError::Fatal(f) => RaftError::Fatal(f),
+            });
+        }
+
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let cmd = ExternalCommand::ReadIndexFromLeader { tx };
+        let read_log_id = self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await?;
+
+        let wait_res = self
+            .wait(Some(timeout))
+            .applied_index_at_least(read_log_id.index(), "follower_read_linearizable")
+            .await;
+
+        match wait_res {
+            Ok(_) => Ok(read_log_id),
+            Err(WaitError::ShuttingDown) => Err(RaftError::Fatal(Fatal::Stopped)),
+            Err(WaitError::Timeout(_, _)) => {
+                let err = FollowerReadTimeout {
+                    timeout,
+                    read_log_id,
+                    applied: self.metrics().borrow().last_applied,
+                };
+                Err(RaftError::APIError(err.into()))
+            }
+        }
+    }
+
+    /// Handle a ReadIndex request sent by a follower with
+    /// [`Raft::follower_read_linearizable()`].
+    ///
+    /// It confirms the leadership of this node with a quorum and returns the log id up to which
+    /// the follower has to apply before serving the read, see [`Raft::get_read_log_id()`].
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn read_index(
+        &self,
+        rpc: ReadIndexRequest<C>,
+    ) -> Result<ReadIndexResponse<C>, RaftError<C, CheckIsLeaderError<C>>> {
+        tracing::debug!(rpc = display(&rpc), "Raft::read_index()");
+
+        let (read_log_id, _applied) = self.get_read_log_id().await?;
+        Ok(ReadIndexResponse { read_log_id })
+    }
+
     /// Submit a mutating client request to Raft to update the state of the system (§5.1).
     ///
     /// It will be appended to the log, committed to the cluster, and then applied to the
@@ -610,6 +1001,55 @@ where C: RaftTypeConfig
         Ok(client_write_response)
     }
 
+    /// Submit a batch of mutating client requests as one contiguous block of log entries.
+    ///
+    /// The requests are appended to the log of the leader as one contiguous block, thus they are
+    /// applied to the state machine consecutively, without any other entry in between. The
+    /// responses are returned in the same order as the requests.
+    ///
+    /// If any of the requests is rejected by the leader, e.g., with
+    /// [`ClientWriteError::EntryTooLarge`], none of them is appended and the error is returned.
+    ///
+    /// The batch is **not** atomic once it is appended: every entry is replicated and committed
+    /// like one written by [`Raft::client_write()`], and the batch may be committed in more than
+    /// one step. If the leader crashes before the whole batch is committed, a new leader that
+    /// received only a part of it may commit that part. An application that needs the batch to
+    /// take effect as a whole should encode it in a single request.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn client_write_batch<E>(
+        &self,
+        app_data: Vec<C::D>,
+    ) -> Result<Vec<ClientWriteResponse<C>>, RaftError<C, ClientWriteError<C>>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        let mut batch = Vec::with_capacity(app_data.len());
+        let mut txs = Vec::with_capacity(app_data.len());
+        let mut rxs = Vec::with_capacity(app_data.len());
+
+        for d in app_data {
+            let (d, tx, rx) = ResponderOf::<C>::from_app_data(d);
+            batch.push(d);
+            txs.push(tx);
+            rxs.push(rx);
+        }
+
+        if batch.is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.inner.send_msg(RaftMsg::ClientWriteBatchRequest { app_data: batch, txs }).await?;
+
+        let mut responses = Vec::with_capacity(rxs.len());
+        for rx in rxs {
+            let res: ClientWriteResult<C> = self.inner.recv_msg(rx).await?;
+            responses.push(res.map_err(|e| RaftError::APIError(e))?);
+        }
+
+        Ok(responses)
+    }
+
     /// Submit a mutating client request to Raft to update the state machine, returns an application
     /// defined response receiver [`Responder::Receiver`].
     ///
@@ -618,9 +1058,39 @@ where C: RaftTypeConfig
     /// It is same as [`Raft::client_write`] but does not wait for the response.
     #[tracing::instrument(level = "debug", skip(self, app_data))]
     pub async fn client_write_ff(&self, app_data: C::D) -> Result<ResponderReceiverOf<C>, Fatal<C>> {
+        self.send_client_write(app_data, vec![]).await
+    }
+
+    /// Submit a mutating client request with an opaque `context` attached to its log entry.
+    ///
+    /// It is same as [`Raft::client_write`], except that `context` is attached to the entry with
+    /// [`RaftEntry::set_context()`]. The context is replicated along with the entry and is
+    /// available when the entry is applied to the state machine on every node, e.g., to carry a
+    /// trace context for distributed tracing. It is ignored by Raft.
+    ///
+    /// [`RaftEntry::set_context()`]: crate::entry::RaftEntry::set_context
+    #[tracing::instrument(level = "debug", skip(self, app_data, context))]
+    pub async fn client_write_with_context<E>(
+        &self,
+        app_data: C::D,
+        context: Vec<u8>,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>
+    where
+        ResponderReceiverOf<C>: Future<Output = Result<ClientWriteResult<C>, E>>,
+        E: Error + OptionalSend,
+    {
+        let rx = self.send_client_write(app_data, context).await?;
+
+        let res: ClientWriteResult<C> = self.inner.recv_msg(rx).await?;
+
+        let client_write_response = res.map_err(|e| RaftError::APIError(e))?;
+        Ok(client_write_response)
+    }
+
+    async fn send_client_write(&self, app_data: C::D, context: Vec<u8>) -> Result<ResponderReceiverOf<C>, Fatal<C>> {
         let (app_data, tx, rx) = ResponderOf::<C>::from_app_data(app_data);
 
-        self.inner.send_msg(RaftMsg::ClientWriteRequest { app_data, tx }).await?;
+        self.inner.send_msg(RaftMsg::ClientWriteRequest { app_data, context, tx }).await?;
 
         Ok(rx)
     }
@@ -657,11 +1127,32 @@ where C: RaftTypeConfig
     #[tracing::instrument(level = "debug", skip(self))]
     pub async fn initialize<T>(&self, members: T) -> Result<(), RaftError<C, InitializeError<C>>>
     where T: IntoNodes<C::NodeId, C::Node> + Debug {
+        self.initialize_with_weights(members, BTreeMap::new()).await
+    }
+
+    /// Initialize a pristine Raft node the same as [`Raft::initialize`], with the voting weight of
+    /// voters.
+    ///
+    /// A voter that is not in `weights` has a weight of `1`. A log entry, or a vote, is granted if
+    /// the voters that accept it have more than half of the total weight.
+    /// See [`Membership::with_weights()`].
+    ///
+    /// [`Membership::with_weights()`]: crate::Membership::with_weights
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn initialize_with_weights<T>(
+        &self,
+        members: T,
+        weights: BTreeMap<C::NodeId, u64>,
+    ) -> Result<(), RaftError<C, InitializeError<C>>>
+    where
+        T: IntoNodes<C::NodeId, C::Node> + Debug,
+    {
         let (tx, rx) = C::AsyncRuntime::oneshot();
         self.inner
             .call_core(
                 RaftMsg::Initialize {
                     members: members.into_nodes(),
+                    weights,
                     tx,
                 },
                 rx,
@@ -669,6 +1160,69 @@ where C: RaftTypeConfig
             .await
     }
 
+    /// Wait until the leader observes that the replication to node `id` becomes line-rate.
+    ///
+    /// A replication target is line-rate when its matching log index is within
+    /// [`Config::replication_lag_threshold`] of the leader's last log index. It is the same
+    /// condition [`Raft::add_learner()`] waits for in blocking mode, and is useful to wait for a
+    /// learner to catch up before promoting it to a voter.
+    ///
+    /// On success, it returns the matching log id of the target. It returns an error if this node
+    /// is not a leader, the target is not in the membership config, or the target does not become
+    /// line-rate within `timeout`.
+    ///
+    /// [`Config::replication_lag_threshold`]: crate::Config::replication_lag_threshold
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn await_line_rate(
+        &self,
+        id: C::NodeId,
+        timeout: Duration,
+    ) -> Result<Option<LogId<C::NodeId>>, RaftError<C, LineRateError<C>>> {
+        let wait_res = self
+            .wait(Some(timeout))
+            .metrics(
+                |metrics| self.check_replication_upto_date(metrics, id, None).is_ok(),
+                "wait for replication to become line-rate",
+            )
+            .await;
+
+        let matching_of =
+            |metrics: &RaftMetrics<C>| metrics.replication.as_ref().and_then(|repl| repl.get(&id).copied()).flatten();
+
+        let metrics = match wait_res {
+            Ok(metrics) => metrics,
+            Err(WaitError::ShuttingDown) => return Err(RaftError::Fatal(Fatal::Stopped)),
+            Err(WaitError::Timeout(_, _)) => {
+                let matching = matching_of(&self.metrics().borrow());
+                let err = LineRateTimeout {
+                    target: id,
+                    timeout,
+                    matching,
+                };
+                return Err(RaftError::APIError(err.into()));
+            }
+        };
+
+        let membership = metrics.membership_config.membership();
+
+        if metrics.replication.is_none() {
+            let leader_id = metrics.current_leader;
+            let leader_node = leader_id.and_then(|leader_id| membership.get_node(&leader_id).cloned());
+            let err = ForwardToLeader { leader_id, leader_node };
+            return Err(RaftError::APIError(err.into()));
+        }
+
+        if membership.get_node(&id).is_none() {
+            let err = NotInMembers {
+                node_id: id,
+                membership: membership.clone(),
+            };
+            return Err(RaftError::APIError(err.into()));
+        }
+
+        Ok(matching_of(&metrics))
+    }
+
     /// Returns Ok() with the latest known matched log id if it should quit waiting: leader change,
     /// node removed, or replication becomes upto date.
     ///
@@ -760,6 +1314,64 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Runs a function against the state machine, in the task that applies logs to it.
+    ///
+    /// The application does not have to keep a shared handle to its state machine to serve
+    /// reads: `func` is called with a mutable reference to the state machine `SM` this `Raft` is
+    /// created with. It runs exclusively, after all logs that are already sent to the state
+    /// machine are applied, and before any log that is sent later. Thus it is safe to access the
+    /// state machine concurrently with Raft. A linearizable read can be served by calling it after
+    /// [`Raft::ensure_linearizable()`].
+    ///
+    /// It returns [`InvalidStateMachineType`] if `SM` is not the state machine type of this
+    /// `Raft`. `func` should return quickly, because applying logs is blocked while it runs.
+    ///
+    /// Example:
+    /// ```ignore
+    /// my_raft.ensure_linearizable().await?;
+    /// let v = my_raft.with_state_machine(|sm: &mut MySM| Box::pin(async move { sm.get("foo") })).await??;
+    /// ```
+    pub async fn with_state_machine<F, SM, V>(&self, func: F) -> Result<Result<V, InvalidStateMachineType>, Fatal<C>>
+    where
+        SM: RaftStateMachine<C>,
+        F: FnOnce(&mut SM) -> BoxFuture<V> + OptionalSend + 'static,
+        V: OptionalSend + 'static,
+    {
+        let (expect_type, expect_name) = self.inner.sm_type;
+        if TypeId::of::<SM>() != expect_type {
+            return Ok(Err(InvalidStateMachineType {
+                expect: expect_name,
+                got: std::any::type_name::<SM>(),
+            }));
+        }
+
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+
+        let sm_cmd = sm::Command::func(move |sm: &mut SM| {
+            Box::pin(async move {
+                let result = func(sm).await;
+                if let Err(_err) = tx.send(result) {
+                    tracing::error!("{}: to-Raft tx send error", func_name!());
+                }
+            })
+        });
+
+        self.inner
+            .send_external_command(ExternalCommand::StateMachineCommand { sm_cmd }, "with_state_machine")
+            .await?;
+
+        match rx.await {
+            Ok(res) => Ok(Ok(res)),
+            Err(err) => {
+                tracing::error!(error = display(&err), "{}: rx recv error", func_name!());
+
+                let when = format!("{}: rx recv", func_name!());
+                let fatal = self.inner.get_core_stopped_error(when, None::<u64>).await;
+                Err(fatal)
+            }
+        }
+    }
+
     /// Send a request to the Raft core loop in a fire-and-forget manner.
     ///
     /// The request functor will be called with a mutable reference to both the state machine
@@ -778,6 +1390,392 @@ where C: RaftTypeConfig
         let _ignore_error = self.inner.tx_api.send(RaftMsg::ExternalCoreRequest { req });
     }
 
+    /// Install a [`RaftObserver`] to watch events happening in `RaftCore`, such as every accepted
+    /// AppendEntries request.
+    ///
+    /// The observer only observes and can not alter the behavior of Raft. It is called in a
+    /// separate task and must not block. A previously installed observer is replaced.
+    ///
+    /// Example:
+    /// ```ignore
+    /// struct Audit;
+    /// impl RaftObserver<TypeConfig> for Audit {
+    ///     fn on_append_entries_received(
+    ///         &self,
+    ///         leader_id: Option<u64>,
+    ///         prev_log_id: Option<LogId<u64>>,
+    ///         num_entries: u64,
+    ///         leader_commit: Option<LogId<u64>>,
+    ///     ) {
+    ///         println!("append-entries from {:?}, n: {}", leader_id, num_entries);
+    ///     }
+    /// }
+    ///
+    /// raft.set_observer(Audit).await?;
+    /// ```
+    pub async fn set_observer(&self, observer: impl RaftObserver<C>) -> Result<(), Fatal<C>> {
+        let observer: Arc<dyn RaftObserver<C>> = Arc::new(observer);
+        self.inner.send_external_command(ExternalCommand::SetObserver { observer }, "set_observer").await
+    }
+
+    /// Install a [`QuorumPolicy`] to decide which log id is committed by the leader, replacing
+    /// the default majority-of-every-config rule, e.g., for weighted, hierarchical or flexible
+    /// quorums.
+    ///
+    /// **This can break the safety of Raft.** Openraft does not check whether the quorums of the
+    /// policy intersect with the quorums used for election; an incorrect policy may lose committed
+    /// logs. The policy must be installed on every node that may become a leader.
+    ///
+    /// Example:
+    /// ```ignore
+    /// struct AllVoters;
+    /// impl QuorumPolicy<TypeConfig> for AllVoters {
+    ///     fn committed(
+    ///         &self,
+    ///         matching: &BTreeMap<u64, Option<LogId<u64>>>,
+    ///         membership: &Membership<TypeConfig>,
+    ///     ) -> Option<LogId<u64>> {
+    ///         membership.voter_ids().map(|id| matching.get(&id).copied().flatten()).min().flatten()
+    ///     }
+    /// }
+    ///
+    /// raft.set_quorum_policy(AllVoters).await?;
+    /// ```
+    pub async fn set_quorum_policy(&self, policy: impl QuorumPolicy<C>) -> Result<(), Fatal<C>> {
+        let policy: Arc<dyn QuorumPolicy<C>> = Arc::new(policy);
+        self.inner
+            .send_external_command(ExternalCommand::SetQuorumPolicy { policy }, "set_quorum_policy")
+            .await
+    }
+
+    /// Returns a stream of every server state transition `(from, to)`, e.g., `(Follower,
+    /// Candidate)`, that happens after this call.
+    ///
+    /// Unlike [`Raft::metrics()`], which is a watch channel and may coalesce changes, no transition
+    /// is coalesced: short-lived states such as `Candidate` are always delivered.
+    /// It is built on [`Raft::events()`], thus it is bounded by [`Config::event_channel_capacity`]:
+    /// if the receiver falls behind by more events than that, the stream ends instead of skipping
+    /// transitions. The stream also ends when `RaftCore` quits.
+    ///
+    /// Example:
+    /// ```ignore
+    /// let mut transitions = raft.state_transitions();
+    /// while let Some((from, to)) = transitions.next().await {
+    ///     println!("server state: {:?} -> {:?}", from, to);
+    /// }
+    /// ```
+    pub fn state_transitions(&self) -> impl Stream<Item = (ServerState, ServerState)> {
+        self.event_stream(|event| match event {
+            RaftEvent::ServerStateChanged { from, to } => Some((from, to)),
+            _ => None,
+        })
+    }
+
+    /// Returns a stream of every new term of this node that is persisted after this call.
+    ///
+    /// A term is yielded after the vote containing it is saved with
+    /// [`RaftLogStorage::save_vote()`], thus it is never lost after a restart. Terms are yielded
+    /// in strictly increasing order, without being coalesced like [`Raft::metrics()`]. It is
+    /// useful for fencing an external resource, e.g., invalidating a token issued in a previous
+    /// term.
+    ///
+    /// It is built on [`Raft::events()`]: if the receiver falls behind by more than
+    /// [`Config::event_channel_capacity`] events, the stream ends instead of skipping terms. The
+    /// stream also ends when `RaftCore` quits.
+    ///
+    /// Example:
+    /// ```ignore
+    /// let mut terms = raft.term_changes();
+    /// while let Some(term) = terms.next().await {
+    ///     fence_external_storage(term);
+    /// }
+    /// ```
+    ///
+    /// [`RaftLogStorage::save_vote()`]: crate::storage::RaftLogStorage::save_vote
+    pub fn term_changes(&self) -> impl Stream<Item = u64> {
+        self.event_stream(|event| match event {
+            RaftEvent::TermChanged { term } => Some(term),
+            _ => None,
+        })
+    }
+
+    /// Returns a stream of the meta of every snapshot installed on this node after this call.
+    ///
+    /// An item is yielded when a snapshot received from the leader has been installed into the
+    /// state machine, i.e., the state machine is replaced as a whole and the entries up to
+    /// [`SnapshotMeta::last_log_id`] are not applied one by one with
+    /// [`RaftStateMachine::apply()`]. An application that maintains state derived from applied
+    /// entries should rebuild it upon receiving an item. A snapshot that is not newer than the
+    /// state machine is discarded and does not yield an item.
+    ///
+    /// It is built on [`Raft::events()`]: if the receiver falls behind by more than
+    /// [`Config::event_channel_capacity`] events, the stream ends instead of skipping snapshots.
+    /// The stream also ends when `RaftCore` quits.
+    ///
+    /// Example:
+    /// ```ignore
+    /// let mut installed = raft.snapshot_installed();
+    /// while let Some(meta) = installed.next().await {
+    ///     rebuild_derived_index(meta.last_log_id);
+    /// }
+    /// ```
+    ///
+    /// [`RaftStateMachine::apply()`]: crate::storage::RaftStateMachine::apply
+    pub fn snapshot_installed(&self) -> impl Stream<Item = SnapshotMeta<C>> {
+        self.event_stream(|event| match event {
+            RaftEvent::SnapshotInstalled { meta } => Some(meta),
+            _ => None,
+        })
+    }
+
+    /// Subscribe to the discrete [`RaftEvent`]s happened on this node after this call.
+    ///
+    /// Unlike [`Raft::metrics()`], which only keeps the latest state, every server state, term,
+    /// leader and membership change is delivered in order. Events are buffered in a bounded
+    /// channel of [`Config::event_channel_capacity`]: if the receiver falls behind, the oldest
+    /// events are dropped and the next `recv()` returns [`RecvError::Lagged`] with the number of
+    /// missed events. The channel is not closed while this `Raft` handle exists, even after
+    /// `RaftCore` quits, thus a subscriber should also watch [`Raft::metrics()`] to find out
+    /// that it quits.
+    ///
+    /// This is the only channel `RaftCore` delivers discrete events with:
+    /// [`Raft::state_transitions()`], [`Raft::term_changes()`] and [`Raft::snapshot_installed()`]
+    /// are streams built on it, which end when `RaftCore` quits.
+    ///
+    /// Example:
+    /// ```ignore
+    /// let mut events = raft.events();
+    /// loop {
+    ///     match events.recv().await {
+    ///         Ok(event) => println!("{}", event),
+    ///         Err(RecvError::Lagged(n)) => println!("missed {} events", n),
+    ///         Err(RecvError::Closed) => break,
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`RecvError::Lagged`]: tokio::sync::broadcast::error::RecvError::Lagged
+    pub fn events(&self) -> broadcast::Receiver<RaftEvent<C>> {
+        self.inner.tx_events.subscribe()
+    }
+
+    /// Returns a stream of the items `f` picks from the [`RaftEvent`]s happened after this call.
+    ///
+    /// The stream ends when `RaftCore` quits, or when the receiver falls behind by more than
+    /// [`Config::event_channel_capacity`] events, so that a missed item is never silently skipped.
+    fn event_stream<T>(&self, f: impl Fn(RaftEvent<C>) -> Option<T> + OptionalSend + 'static) -> impl Stream<Item = T>
+    where T: OptionalSend + 'static {
+        let rx_events = self.inner.tx_events.subscribe();
+        let rx_metrics = self.inner.rx_metrics.clone();
+
+        let stream = futures::stream::unfold(
+            (rx_events, rx_metrics, f),
+            |(mut rx_events, mut rx_metrics, f)| async move {
+                loop {
+                    // Deliver the events sent before `RaftCore` quits first.
+                    let res = tokio::select! {
+                        biased;
+                        res = rx_events.recv() => res,
+                        _ = wait_core_quit(&mut rx_metrics) => return None,
+                    };
+
+                    match res {
+                        Ok(event) => {
+                            if let Some(item) = f(event) {
+                                return Some((item, (rx_events, rx_metrics, f)));
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            tracing::warn!("event receiver lagged behind by {} events, end the stream", n);
+                            return None;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            },
+        );
+
+        Box::pin(stream)
+    }
+
+    /// Replay committed log entries from index `from`, and then follow new commits.
+    ///
+    /// The returned stream yields every committed entry in index order, without gap: it reads the
+    /// historical entries from the log storage in batches of [`Config::max_payload_entries`], and
+    /// when it catches up with the committed log id, it waits for new entries to be committed. It
+    /// is useful to build a materialized view or a secondary index.
+    ///
+    /// If an entry to read is already purged, it yields a [`ReplayError::LogPurged`] and ends. The
+    /// application should install the state from a snapshot in such a case. The stream also ends
+    /// after yielding any other error.
+    ///
+    /// Logs are purged after being included in a snapshot, a slow consumer should be aware of
+    /// [`Config::max_in_snapshot_log_to_keep`].
+    ///
+    /// Besides application data, the stream also yields blank and membership entries; use
+    /// [`RaftPayload::payload_kind()`] to tell them apart.
+    ///
+    /// [`RaftPayload::payload_kind()`]: crate::entry::RaftPayload::payload_kind
+    /// [`Config::max_payload_entries`]: crate::Config::max_payload_entries
+    /// [`Config::max_in_snapshot_log_to_keep`]: crate::Config::max_in_snapshot_log_to_keep
+    pub fn replay_committed(&self, from: u64) -> impl Stream<Item = Result<C::Entry, RaftError<C, ReplayError<C>>>> {
+        let max = self.inner.config.max_payload_entries;
+        let rx_data_metrics = self.data_metrics();
+
+        let init = Some((self.clone(), rx_data_metrics, from, VecDeque::new()));
+
+        futures::stream::unfold(init, move |st| async move {
+            let (raft, mut rx_data_metrics, mut next, mut buf) = st?;
+
+            loop {
+                if let Some(entry) = buf.pop_front() {
+                    return Some((Ok(entry), Some((raft, rx_data_metrics, next, buf))));
+                }
+
+                // Mark the metrics as seen before reading,
+                // so that a commit after the read wakes up the wait below.
+                rx_data_metrics.borrow_and_update();
+
+                let (tx, rx) = C::AsyncRuntime::oneshot();
+                let cmd = ExternalCommand::ReadCommitted { start: next, max, tx };
+                let res = raft.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await;
+
+                let entries = match res {
+                    Ok(x) => x,
+                    Err(e) => return Some((Err(e), None)),
+                };
+
+                if entries.is_empty() {
+                    // Committed entries are applied, thus a new commit always changes the metrics.
+                    if rx_data_metrics.changed().await.is_err() {
+                        return Some((Err(RaftError::Fatal(Fatal::Stopped)), None));
+                    }
+                    continue;
+                }
+
+                next += entries.len() as u64;
+                buf.extend(entries);
+            }
+        })
+    }
+
+    /// Returns summaries of the log entries on this leader that are not yet committed.
+    ///
+    /// The entries are those after the committed log id up to the last log id, in index order.
+    /// Together with the matching log id of every follower in [`RaftMetrics::replication`], it
+    /// tells which follower blocks a commit and what is waiting to be committed.
+    ///
+    /// It returns a [`ForwardToLeader`] error if this node is not a leader.
+    ///
+    /// [`RaftMetrics::replication`]: crate::metrics::RaftMetrics::replication
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn uncommitted_entries(
+        &self,
+    ) -> Result<Vec<LogEntrySummary<C>>, RaftError<C, UncommittedEntriesError<C>>> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        let cmd = ExternalCommand::ReadUncommitted { tx };
+        self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await
+    }
+
+    /// Mark or unmark a learner as a read replica.
+    ///
+    /// A read replica is a learner that serves reads and should be kept as fresh as possible.
+    /// When this node is leader, it replicates to read replicas before other learners, and allows
+    /// twice of [`Config::max_payload_entries`] in one AppendEntries request to a read replica.
+    ///
+    /// This setting is local to this node and is not replicated. To keep read replicas favored
+    /// after a leader change, it should be set on every node that may become leader.
+    ///
+    /// [`Config::max_payload_entries`]: crate::Config::max_payload_entries
+    pub async fn set_read_replica(&self, id: C::NodeId, read_replica: bool) -> Result<(), Fatal<C>> {
+        self.inner
+            .send_external_command(ExternalCommand::SetReadReplica { id, read_replica }, "set_read_replica")
+            .await
+    }
+
+    /// Set the replication priority of a follower or learner, e.g., higher for nearby nodes.
+    ///
+    /// When this node is leader and replicates to several targets at once, it sends to targets
+    /// with a higher priority first, so that in a geo-distributed cluster a nearby quorum receives
+    /// new logs, and commits them, before the remote nodes. Targets without a priority set have
+    /// priority `0`; setting `0` resets it. It only changes the order in which targets are sent
+    /// to: a log is still committed once any quorum accepts it, and every target is replicated to.
+    ///
+    /// Like [`Raft::set_read_replica()`], this setting is local to this node and is not
+    /// replicated, it should be set on every node that may become leader.
+    pub async fn set_replication_priority(&self, id: C::NodeId, priority: u64) -> Result<(), Fatal<C>> {
+        self.inner
+            .send_external_command(
+                ExternalCommand::SetReplicationPriority { id, priority },
+                "set_replication_priority",
+            )
+            .await
+    }
+
+    /// Re-replicate to a follower or learner from scratch, without removing and re-adding it.
+    ///
+    /// The leader forgets the matching log id it has tracked for node `id`, probes the log of
+    /// the node backward from the last log to find the matching point again, and sends a snapshot
+    /// if the logs it needs are purged. If some data is in flight to the node, the reset happens
+    /// after it is acknowledged.
+    ///
+    /// It is a repair primitive for a node whose log is suspected to be inconsistent with the
+    /// leader. It does nothing if this node is not the leader or `id` is not replicated by it.
+    pub async fn resync_follower(&self, id: C::NodeId) -> Result<(), Fatal<C>> {
+        self.inner.send_external_command(ExternalCommand::ResyncFollower { id }, "resync_follower").await
+    }
+
+    /// Wait until this node is removed from the cluster.
+    ///
+    /// It returns once this node, after having been a member since it started, learns that a
+    /// membership log that includes it neither as a voter nor as a learner is committed. It is
+    /// the signal for an application to stop serving and decommission the process.
+    ///
+    /// An uncommitted membership log that removes this node is not a removal: a new leader may
+    /// truncate it. The leader stops replicating to a removed node as soon as the new membership
+    /// is appended, thus a removed node learns about the commitment only if
+    /// [`Config::notify_removed_nodes`] is enabled; otherwise this method may never return, and
+    /// the application has to shut down removed nodes by other means.
+    ///
+    /// It returns a [`Fatal`] error if `RaftCore` quits before this node is removed.
+    ///
+    /// Example:
+    /// ```ignore
+    /// raft.removed_from_cluster().await?;
+    /// decommission().await;
+    /// raft.shutdown().await?;
+    /// ```
+    ///
+    /// [`Config::notify_removed_nodes`]: crate::Config::notify_removed_nodes
+    pub async fn removed_from_cluster(&self) -> Result<(), Fatal<C>> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        self.inner
+            .send_external_command(ExternalCommand::WaitRemoved { tx }, "removed_from_cluster")
+            .await?;
+
+        let _ = self.inner.recv_msg(rx).await?;
+        Ok(())
+    }
+
+    /// Returns a small liveness report of this node: its server state, whether it knows of a
+    /// leader, and how long ago the leader was last heard from.
+    ///
+    /// It is much cheaper than [`Self::metrics()`]: it does not allocate, does not touch storage
+    /// and does not contend on the metrics channel, so it is suitable for frequent health checks.
+    /// The state is updated by `RaftCore` every time it reports metrics, while the heartbeat age is
+    /// computed at the time of the call.
+    pub fn liveness(&self) -> Liveness {
+        self.inner.liveness.load()
+    }
+
+    /// Returns the server state, term, leader, commit index and last log index of this node.
+    ///
+    /// They are read from the latest metrics value at once, so that they are consistent with each
+    /// other, which is not guaranteed if they are read one by one from [`Self::metrics()`].
+    pub fn status(&self) -> RaftStatus<C::NodeId> {
+        RaftStatus::from(&*self.inner.rx_metrics.borrow())
+    }
+
     /// Get a handle to the metrics channel.
     pub fn metrics(&self) -> watch::Receiver<RaftMetrics<C>> {
         self.inner.rx_metrics.clone()
@@ -824,6 +1822,58 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Stop accepting client writes, wait for the accepted ones to finish, give up leadership and
+    /// shut down, for a rolling restart without failing any accepted write.
+    ///
+    /// It proceeds in the following steps, all bounded by `timeout`:
+    /// - New [`Raft::client_write()`] requests are rejected with [`NotReady`]. Membership changes
+    ///   are still accepted.
+    /// - Wait until every client write that is already accepted is committed and applied, i.e.,
+    ///   [`RaftMetrics::pending_client_writes`] becomes `0`.
+    /// - If this node is a leader, it steps down with [`Raft::step_down()`], so that other nodes
+    ///   elect a new leader instead of waiting for this one.
+    /// - Shut down this node with [`Raft::shutdown()`].
+    ///
+    /// This node is shut down even if a step does not finish in time. In that case a
+    /// [`WaitError::Timeout`] is returned, and client writes that are not finished receive an
+    /// error or no response.
+    ///
+    /// [`NotReady`]: crate::error::NotReady
+    pub async fn drain_and_shutdown(&self, timeout: Duration) -> Result<(), WaitError> {
+        let deadline = InstantOf::<C>::now() + timeout;
+
+        let res = self.drain_and_step_down(timeout, deadline).await;
+
+        let _ = self.shutdown().await;
+        res
+    }
+
+    async fn drain_and_step_down(&self, timeout: Duration, deadline: InstantOf<C>) -> Result<(), WaitError> {
+        let (tx, rx) = C::AsyncRuntime::oneshot();
+        self.inner
+            .send_external_command(ExternalCommand::Drain { tx }, "drain")
+            .await
+            .map_err(|_| WaitError::ShuttingDown)?;
+
+        match C::AsyncRuntime::timeout_at(deadline, rx).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(_)) => return Err(WaitError::ShuttingDown),
+            Err(_) => {
+                return Err(WaitError::Timeout(
+                    timeout,
+                    "draining pending client writes".to_string(),
+                ))
+            }
+        }
+
+        match C::AsyncRuntime::timeout_at(deadline, self.step_down()).await {
+            // Not a leader, nothing to step down from.
+            Ok(Ok(_)) | Ok(Err(RaftError::APIError(_))) => Ok(()),
+            Ok(Err(RaftError::Fatal(_))) => Err(WaitError::ShuttingDown),
+            Err(_) => Err(WaitError::Timeout(timeout, "stepping down".to_string())),
+        }
+    }
+
     /// Shutdown this Raft node.
     ///
     /// It sends a shutdown signal and waits until `RaftCore` returns.
@@ -844,3 +1894,9 @@ where C: RaftTypeConfig
         Ok(())
     }
 }
+
+/// Wait until `RaftCore` quits and drops the sending end of the metrics channel.
+async fn wait_core_quit<C>(rx_metrics: &mut watch::Receiver<RaftMetrics<C>>)
+where C: RaftTypeConfig {
+    while rx_metrics.changed().await.is_ok() {}
+}
@@ -0,0 +1,132 @@
+//! Observer of Raft events, for auditing and monitoring.
+
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tracing::Instrument;
+
+use crate::AsyncRuntime;
+use crate::LogId;
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+
+/// Observe events that happen inside `RaftCore`.
+///
+/// An observer is installed with [`Raft::set_observer()`]. `RaftCore` sends the events to a
+/// separate task, which calls the observer with them one by one, in the order they happen. Thus
+/// an observer is called after the event has happened, possibly after `RaftCore` has handled
+/// other events. It only receives copies of the event data and can not alter the behavior of
+/// Raft.
+///
+/// An observer must not block: it runs on the async runtime, and a slow observer delays the
+/// following events, which are queued without bound in the meantime. If an observer panics, it
+/// receives no more events, while `RaftCore` keeps running.
+///
+/// All methods have a default no-op implementation.
+///
+/// [`Raft::set_observer()`]: crate::Raft::set_observer
+pub trait RaftObserver<C>: OptionalSend + OptionalSync + 'static
+where C: RaftTypeConfig
+{
+    /// Called on a follower or learner for every AppendEntries request that is accepted.
+    ///
+    /// It is called for heartbeats too, in which case `num_entries` is 0.
+    ///
+    /// - `leader_id` is the node that sends the request.
+    /// - `prev_log_id` is the log id immediately preceding the entries in the request.
+    /// - `num_entries` is the number of entries in the request.
+    /// - `leader_commit` is the committed log id of the leader.
+    fn on_append_entries_received(
+        &self,
+        leader_id: Option<C::NodeId>,
+        prev_log_id: Option<LogId<C::NodeId>>,
+        num_entries: u64,
+        leader_commit: Option<LogId<C::NodeId>>,
+    ) {
+        let _ = (leader_id, prev_log_id, num_entries, leader_commit);
+    }
+
+    /// Called when the state machine returns an error when applying log entries.
+    ///
+    /// The event is sent before `RaftCore` handles the error, i.e., before it shuts down or enters
+    /// degraded mode, and it is still delivered if `RaftCore` shuts down meanwhile.
+    ///
+    /// - `log_id` is the entry that failed to apply. It is the log id in the error if the error
+    ///   subject is [`ErrorSubject::Apply`], otherwise the first entry of the failed batch.
+    /// - `error` is the error returned by the state machine.
+    ///
+    /// [`ErrorSubject::Apply`]: crate::ErrorSubject::Apply
+    fn on_apply_error(&self, log_id: &LogId<C::NodeId>, error: &StorageError<C::NodeId>) {
+        let _ = (log_id, error);
+    }
+}
+
+/// An event sent by `RaftCore` to the task running a [`RaftObserver`].
+pub(crate) enum ObserverEvent<C>
+where C: RaftTypeConfig
+{
+    AppendEntriesReceived {
+        leader_id: Option<C::NodeId>,
+        prev_log_id: Option<LogId<C::NodeId>>,
+        num_entries: u64,
+        leader_commit: Option<LogId<C::NodeId>>,
+    },
+    ApplyError {
+        log_id: LogId<C::NodeId>,
+        error: StorageError<C::NodeId>,
+    },
+}
+
+/// Sends events to a [`RaftObserver`] running in a task separate from `RaftCore`.
+///
+/// The task quits when this handle is dropped, after calling the observer with every event sent.
+pub(crate) struct ObserverHandle<C>
+where C: RaftTypeConfig
+{
+    tx: mpsc::UnboundedSender<ObserverEvent<C>>,
+}
+
+impl<C> ObserverHandle<C>
+where C: RaftTypeConfig
+{
+    /// Spawn a task that calls `observer` with every event sent to the returned handle.
+    pub(crate) fn spawn(observer: Arc<dyn RaftObserver<C>>) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<ObserverEvent<C>>();
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::AsyncRuntime::spawn(
+            async move {
+                while let Some(event) = rx.recv().await {
+                    match event {
+                        ObserverEvent::AppendEntriesReceived {
+                            leader_id,
+                            prev_log_id,
+                            num_entries,
+                            leader_commit,
+                        } => {
+                            observer.on_append_entries_received(leader_id, prev_log_id, num_entries, leader_commit);
+                        }
+                        ObserverEvent::ApplyError { log_id, error } => {
+                            observer.on_apply_error(&log_id, &error);
+                        }
+                    }
+                }
+                tracing::debug!("observer channel closed, observer task quit");
+            }
+            .instrument(tracing::debug_span!("observer")),
+        );
+
+        Self { tx }
+    }
+
+    /// Send an event to the observer without waiting for it to be handled.
+    ///
+    /// An error means the observer task has quit, e.g., because the observer panicked, and is
+    /// ignored.
+    pub(crate) fn send(&self, event: ObserverEvent<C>) {
+        let _ = self.tx.send(event);
+    }
+}
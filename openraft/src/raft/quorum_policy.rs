@@ -0,0 +1,151 @@
+//! Policy that decides the committed log id from the replication progress.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::quorum::QuorumSet;
+use crate::LogId;
+use crate::Membership;
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftTypeConfig;
+
+/// Decide which log id is committed by a leader, given the replication progress of every node.
+///
+/// A policy is installed with [`Raft::set_quorum_policy()`] and is called by the leader every
+/// time the matching log id of a node is updated. The default policy is [`MajorityQuorumPolicy`]:
+/// a log id is committed when it is accepted by a majority of voters in every config of the joint
+/// membership.
+///
+/// **Safety is entirely the responsibility of the implementation.** Openraft does not verify the
+/// returned value. A policy whose quorums do not intersect with the quorums used for election
+/// breaks the Raft safety guarantee: committed logs may be lost or overridden, and different
+/// nodes may apply different logs. Only override it if you know exactly what you are doing.
+///
+/// The returned log id still has to be proposed by the current leader to be committed, and the
+/// committed log id never goes backward.
+///
+/// [`Raft::set_quorum_policy()`]: crate::Raft::set_quorum_policy
+pub trait QuorumPolicy<C>: OptionalSend + OptionalSync + 'static
+where C: RaftTypeConfig
+{
+    /// Returns the greatest log id that is accepted by a quorum.
+    ///
+    /// - `matching` is the last log id known to be replicated to each node, including learners and
+    ///   the leader itself.
+    /// - `membership` is the effective membership of the leader.
+    fn committed(
+        &self,
+        matching: &BTreeMap<C::NodeId, Option<LogId<C::NodeId>>>,
+        membership: &Membership<C>,
+    ) -> Option<LogId<C::NodeId>>;
+}
+
+/// The default [`QuorumPolicy`]: a majority of voters in every config of the joint membership.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MajorityQuorumPolicy;
+
+impl<C> QuorumPolicy<C> for MajorityQuorumPolicy
+where C: RaftTypeConfig
+{
+    fn committed(
+        &self,
+        matching: &BTreeMap<C::NodeId, Option<LogId<C::NodeId>>>,
+        membership: &Membership<C>,
+    ) -> Option<LogId<C::NodeId>> {
+        let quorum_set = membership.to_quorum_set();
+
+        let mut candidates = matching.values().copied().collect::<Vec<_>>();
+        candidates.sort();
+        candidates.dedup();
+
+        for candidate in candidates.into_iter().rev() {
+            let ids = matching.iter().filter(|(_, m)| **m >= candidate).map(|(id, _)| id).collect::<Vec<_>>();
+
+            if quorum_set.is_quorum(ids.iter().copied()) {
+                return candidate;
+            }
+        }
+
+        None
+    }
+}
+
+/// A shared [`QuorumPolicy`] that can be stored in a config.
+pub(crate) struct QuorumPolicyRef<C>(pub(crate) Arc<dyn QuorumPolicy<C>>)
+where C: RaftTypeConfig;
+
+impl<C> Default for QuorumPolicyRef<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self(Arc::new(MajorityQuorumPolicy))
+    }
+}
+
+impl<C> Clone for QuorumPolicyRef<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<C> fmt::Debug for QuorumPolicyRef<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "QuorumPolicy")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::btreemap;
+    use maplit::btreeset;
+
+    use crate::engine::testing::UTConfig;
+    use crate::raft::quorum_policy::MajorityQuorumPolicy;
+    use crate::raft::quorum_policy::QuorumPolicy;
+    use crate::testing::log_id;
+    use crate::Membership;
+
+    #[test]
+    fn test_majority_quorum_policy() -> anyhow::Result<()> {
+        let m = Membership::<UTConfig>::new(vec![btreeset! {1,2,3}], btreeset! {4});
+
+        let matching = btreemap! {
+            1 => Some(log_id(1, 1, 5)),
+            2 => Some(log_id(1, 1, 3)),
+            3 => None,
+            4 => Some(log_id(1, 1, 9)),
+        };
+        assert_eq!(Some(log_id(1, 1, 3)), MajorityQuorumPolicy.committed(&matching, &m));
+
+        let matching = btreemap! {
+            1 => Some(log_id(1, 1, 5)),
+            2 => None,
+            3 => None,
+            4 => Some(log_id(1, 1, 9)),
+        };
+        assert_eq!(
+            None,
+            MajorityQuorumPolicy.committed(&matching, &m),
+            "learner does not count"
+        );
+
+        // Joint config: both configs have to accept it.
+        let m = Membership::<UTConfig>::new(vec![btreeset! {1,2,3}, btreeset! {3,4,5}], None);
+        let matching = btreemap! {
+            1 => Some(log_id(1, 1, 5)),
+            2 => Some(log_id(1, 1, 5)),
+            3 => Some(log_id(1, 1, 2)),
+            4 => Some(log_id(1, 1, 4)),
+            5 => None,
+        };
+        assert_eq!(Some(log_id(1, 1, 2)), MajorityQuorumPolicy.committed(&matching, &m));
+
+        Ok(())
+    }
+}
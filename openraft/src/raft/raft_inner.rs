@@ -1,8 +1,10 @@
+use std::any::TypeId;
 use std::fmt;
 use std::fmt::Debug;
 use std::future::Future;
 use std::sync::Arc;
 
+use tokio::sync::broadcast;
 use tokio::sync::mpsc;
 use tokio::sync::watch;
 use tokio::sync::Mutex;
@@ -15,8 +17,10 @@ use crate::core::TickHandle;
 use crate::error::Fatal;
 use crate::error::RaftError;
 use crate::metrics::RaftDataMetrics;
+use crate::metrics::RaftEvent;
 use crate::metrics::RaftServerMetrics;
 use crate::raft::core_state::CoreState;
+use crate::raft::liveness::LivenessState;
 use crate::type_config::alias::OneshotReceiverOf;
 use crate::type_config::alias::OneshotSenderOf;
 use crate::AsyncRuntime;
@@ -33,11 +37,16 @@ where C: RaftTypeConfig
     pub(in crate::raft) id: C::NodeId,
     pub(in crate::raft) config: Arc<Config>,
     pub(in crate::raft) runtime_config: Arc<RuntimeConfig>,
+    pub(in crate::raft) liveness: Arc<LivenessState<C>>,
     pub(in crate::raft) tick_handle: TickHandle<C>,
     pub(in crate::raft) tx_api: mpsc::UnboundedSender<RaftMsg<C>>,
     pub(in crate::raft) rx_metrics: watch::Receiver<RaftMetrics<C>>,
     pub(in crate::raft) rx_data_metrics: watch::Receiver<RaftDataMetrics<C>>,
     pub(in crate::raft) rx_server_metrics: watch::Receiver<RaftServerMetrics<C>>,
+    pub(in crate::raft) tx_events: broadcast::Sender<RaftEvent<C>>,
+
+    /// The type of the state machine this `Raft` is created with, and its name.
+    pub(in crate::raft) sm_type: (TypeId, &'static str),
 
     // TODO(xp): it does not need to be a async mutex.
     #[allow(clippy::type_complexity)]
@@ -136,6 +145,19 @@ where C: RaftTypeConfig
         when: impl fmt::Display,
         message_summary: Option<impl fmt::Display + Default>,
     ) -> Fatal<C> {
+        // In read-only degraded mode RaftCore keeps running but drops the requests it can not
+        // serve: do not wait for it to quit.
+        let degraded = self.rx_metrics.borrow().storage_degraded.clone();
+        if let Some(storage_error) = degraded {
+            tracing::info!(
+                error = display(&storage_error),
+                "failure {}, storage is degraded; message: {}",
+                when,
+                message_summary.unwrap_or_default()
+            );
+            return Fatal::StorageError(storage_error);
+        }
+
         // Wait for the core task to finish.
         self.join_core_task().await;
 
@@ -39,6 +39,24 @@ where C: RaftTypeConfig
     }
 
     /// Enable or disable election for a follower when its leader lease timed out.
+    ///
+    /// A node with election disabled still replicates logs from the leader, acknowledges
+    /// heartbeats and grants votes to other candidates; it just never becomes a candidate by
+    /// itself. It can be used to quiesce a follower for a planned maintenance, so that the
+    /// leadership stays stable, without removing it from the membership:
+    ///
+    /// ```ignore
+    /// raft.runtime_config().elect(false);
+    /// // ... maintenance ...
+    /// raft.runtime_config().elect(true);
+    /// ```
+    ///
+    /// It does not affect a node that is already a leader; use [`Raft::step_down()`] to give up
+    /// the leadership first. An election can still be started explicitly with
+    /// [`Trigger::elect()`].
+    ///
+    /// [`Raft::step_down()`]: crate::Raft::step_down
+    /// [`Trigger::elect()`]: crate::raft::trigger::Trigger::elect
     pub fn elect(&self, enabled: bool) {
         self.raft_inner.runtime_config.enable_elect.store(enabled, Ordering::Relaxed);
     }
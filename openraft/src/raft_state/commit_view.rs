@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::type_config::alias::InstantOf;
+use crate::LogId;
+use crate::NodeRole;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+/// The replication and commit state of a node, as last known by the leader.
+#[derive(Debug, Clone)]
+#[derive(PartialEq, Eq)]
+pub struct NodeCommitView<C>
+where C: RaftTypeConfig
+{
+    /// The role of the node.
+    pub role: NodeRole,
+
+    /// The last log id known to be replicated to the node.
+    pub matched: Option<LogId<C::NodeId>>,
+
+    /// The committed log id the node has been told of.
+    ///
+    /// A node does not report its committed log id. It is estimated as the leader's committed log
+    /// id capped by `matched`, because the leader sends its committed log id with every
+    /// replication and a node can not commit a log it does not have.
+    pub committed: Option<LogId<C::NodeId>>,
+}
+
+/// A best-effort view of the replication and commit state of every node, built by the leader in
+/// one pass.
+///
+/// It is returned by [`Raft::cluster_commit_view()`]. The per-node state is the last known one on
+/// the leader, and is not refreshed by contacting the nodes.
+///
+/// [`Raft::cluster_commit_view()`]: crate::Raft::cluster_commit_view
+#[derive(Debug, Clone)]
+#[derive(PartialEq, Eq)]
+pub struct ClusterCommitView<C>
+where C: RaftTypeConfig
+{
+    /// The id of the leader that built this view.
+    pub leader_id: C::NodeId,
+
+    /// The vote of the leader.
+    pub vote: Vote<C::NodeId>,
+
+    /// When this view is built.
+    pub taken_at: InstantOf<C>,
+
+    /// The committed log id of the leader.
+    pub committed: Option<LogId<C::NodeId>>,
+
+    /// The last log id of the leader.
+    pub last_log_id: Option<LogId<C::NodeId>>,
+
+    /// The last log id applied to the state machine of the leader.
+    pub last_applied: Option<LogId<C::NodeId>>,
+
+    /// Every node that the leader replicates to, including the leader itself.
+    pub nodes: BTreeMap<C::NodeId, NodeCommitView<C>>,
+}
+
+impl<C> fmt::Display for ClusterCommitView<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ClusterCommitView{{leader_id: {}, vote: {}, committed: {}, last_log_id: {}, last_applied: {}, nodes: [",
+            self.leader_id,
+            self.vote,
+            self.committed.display(),
+            self.last_log_id.display(),
+            self.last_applied.display(),
+        )?;
+
+        for (i, (id, n)) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                "{}: {}(matched: {}, committed: {})",
+                id,
+                n.role,
+                n.matched.display(),
+                n.committed.display()
+            )?;
+        }
+
+        write!(f, "]}}")
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::LogId;
+use crate::RaftTypeConfig;
+use crate::Vote;
+
+/// An estimation of whether a node could win an election if it started one right now.
+///
+/// It is returned by [`Raft::estimated_electability()`]. It is a heuristic based on the local
+/// state of the node: a node only knows what it has received from the leader, but not the logs of
+/// other nodes.
+///
+/// [`Raft::estimated_electability()`]: crate::Raft::estimated_electability
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+pub enum Electability<C>
+where C: RaftTypeConfig
+{
+    /// This node is the leader.
+    Leader,
+
+    /// This node is a voter and no quorum is known to have a greater log: it could win.
+    Electable,
+
+    /// This node is not a voter in the effective membership and can not be elected.
+    NotVoter,
+
+    /// The log of this node is behind a quorum thus it can not win.
+    ///
+    /// An established leader has its first log replicated to a quorum. If this node does not have
+    /// any log proposed by the leader it has voted for, no quorum will grant its vote.
+    LogBehind {
+        /// The last log id of this node.
+        last_log_id: Option<LogId<C::NodeId>>,
+
+        /// The vote of the established leader.
+        leader_vote: Vote<C::NodeId>,
+    },
+}
+
+impl<C> Electability<C>
+where C: RaftTypeConfig
+{
+    /// Returns `true` if this node is the leader or could win an election.
+    pub fn could_win(&self) -> bool {
+        matches!(self, Self::Leader | Self::Electable)
+    }
+}
+
+impl<C> fmt::Display for Electability<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Leader => write!(f, "Leader"),
+            Self::Electable => write!(f, "Electable"),
+            Self::NotVoter => write!(f, "NotVoter"),
+            Self::LogBehind {
+                last_log_id,
+                leader_vote,
+            } => write!(
+                f,
+                "LogBehind{{last_log_id: {}, leader_vote: {}}}",
+                last_log_id.display(),
+                leader_vote
+            ),
+        }
+    }
+}
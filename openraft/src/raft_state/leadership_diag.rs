@@ -0,0 +1,135 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::Electability;
+use crate::LogId;
+use crate::RaftTypeConfig;
+use crate::ServerState;
+use crate::Vote;
+
+/// The outcome of the last election started by this node.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+pub enum ElectionOutcome<C>
+where C: RaftTypeConfig
+{
+    /// The election is started with `vote` and is waiting for a quorum to grant it.
+    InProgress { vote: Vote<C::NodeId> },
+
+    /// A quorum granted `vote` and this node became the leader.
+    Won { vote: Vote<C::NodeId> },
+
+    /// The vote request with `vote` is rejected by node `by`.
+    ///
+    /// If `peer_vote` is greater than `vote`, this node reverted to follower. If `peer_last_log_id`
+    /// is greater than the last log id of this node, its log is behind and the next election is
+    /// delayed.
+    Rejected {
+        vote: Vote<C::NodeId>,
+        by: C::NodeId,
+        peer_vote: Vote<C::NodeId>,
+        peer_last_log_id: Option<LogId<C::NodeId>>,
+    },
+}
+
+impl<C> fmt::Display for ElectionOutcome<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InProgress { vote } => write!(f, "InProgress{{vote: {}}}", vote),
+            Self::Won { vote } => write!(f, "Won{{vote: {}}}", vote),
+            Self::Rejected {
+                vote,
+                by,
+                peer_vote,
+                peer_last_log_id,
+            } => write!(
+                f,
+                "Rejected{{vote: {}, by: {}, peer_vote: {}, peer_last_log_id: {}}}",
+                vote,
+                by,
+                peer_vote,
+                peer_last_log_id.display()
+            ),
+        }
+    }
+}
+
+/// A report explaining the leadership status of a node.
+///
+/// It is returned by [`Raft::leadership_diagnostics()`] and aggregates the state the node uses to
+/// decide whether and when to elect itself, so that an operator can tell why a node is or is not
+/// the leader without reading through logs.
+///
+/// [`Raft::leadership_diagnostics()`]: crate::Raft::leadership_diagnostics
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+pub struct LeadershipDiag<C>
+where C: RaftTypeConfig
+{
+    /// The id of this node.
+    pub id: C::NodeId,
+
+    /// The current server state, such as Leader or Follower.
+    pub server_state: ServerState,
+
+    /// The current term.
+    pub current_term: u64,
+
+    /// The current vote of this node.
+    pub vote: Vote<C::NodeId>,
+
+    /// The leader this node knows of, if any.
+    pub current_leader: Option<C::NodeId>,
+
+    /// The last log id of this node.
+    pub last_log_id: Option<LogId<C::NodeId>>,
+
+    /// Whether this node could win an election if it started one now.
+    pub electability: Electability<C>,
+
+    /// Whether election is enabled, see [`Config::enable_elect`](crate::Config::enable_elect).
+    pub election_enabled: bool,
+
+    /// Whether a greater log is seen on another node during the last election.
+    ///
+    /// If it is `true`, the next election of this node is delayed to let that node win.
+    pub seen_greater_log: bool,
+
+    /// The time elapsed since the vote was last updated, e.g., by a heartbeat from the leader.
+    pub since_vote_updated: Option<Duration>,
+
+    /// Whether the leader lease is valid, i.e., this node has recently heard from the leader and
+    /// will not start an election.
+    pub receiving_heartbeat: bool,
+
+    /// The outcome of the last election started by this node.
+    pub last_election: Option<ElectionOutcome<C>>,
+}
+
+impl<C> fmt::Display for LeadershipDiag<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LeadershipDiag{{id: {}, server_state: {:?}, term: {}, vote: {}, current_leader: {}, last_log_id: {}, \
+             electability: {}, election_enabled: {}, seen_greater_log: {}, since_vote_updated: {:?}, \
+             receiving_heartbeat: {}, last_election: {}}}",
+            self.id,
+            self.server_state,
+            self.current_term,
+            self.vote,
+            self.current_leader.display(),
+            self.last_log_id.display(),
+            self.electability,
+            self.election_enabled,
+            self.seen_greater_log,
+            self.since_vote_updated,
+            self.receiving_heartbeat,
+            self.last_election.display(),
+        )
+    }
+}
@@ -35,7 +35,14 @@ where C: RaftTypeConfig
     ) -> Result<Membership<C>, ChangeMembershipError<C>> {
         self.ensure_committed()?;
 
-        let new_membership = self.state.effective().membership().clone().change(change, retain)?;
+        // The effective membership is committed.
+        let effective = self.state.effective();
+
+        let new_membership = effective
+            .membership()
+            .clone()
+            .change(change, retain)
+            .map_err(|e| e.with_committed(*effective.log_id()))?;
         Ok(new_membership)
     }
 
@@ -53,7 +53,12 @@ fn test_apply_empty_voters() -> anyhow::Result<()> {
     let new = || MembershipState::<UTConfig>::new(effmem(3, 4, m1()), effmem(3, 4, m1()));
     let res = new().change_handler().apply(ChangeMembers::RemoveVoters(btreeset! {1}), false);
 
-    assert_eq!(Err(ChangeMembershipError::EmptyMembership(EmptyMembership {})), res);
+    assert_eq!(
+        Err(ChangeMembershipError::EmptyMembership(EmptyMembership {
+            committed: Some(log_id(3, 1, 4))
+        })),
+        res
+    );
 
     Ok(())
 }
@@ -64,7 +69,10 @@ fn test_apply_learner_not_found() -> anyhow::Result<()> {
     let res = new().change_handler().apply(ChangeMembers::AddVoterIds(btreeset! {2}), false);
 
     assert_eq!(
-        Err(ChangeMembershipError::LearnerNotFound(LearnerNotFound { node_id: 2 })),
+        Err(ChangeMembershipError::LearnerNotFound(LearnerNotFound {
+            node_id: 2,
+            committed: Some(log_id(3, 1, 4))
+        })),
         res
     );
 
@@ -175,3 +175,35 @@ fn test_membership_state_truncate() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_membership_state_transition() -> anyhow::Result<()> {
+    // Uniform and committed.
+    {
+        let x = MembershipState::<UTConfig>::new(effmem(2, 2, m12()), effmem(2, 2, m12()));
+        assert!(!x.effective().membership().is_joint());
+        assert!(!x.is_in_transition());
+        assert_eq!(&btreeset! {1,2}, x.target());
+    }
+
+    // Joint.
+    {
+        let x = MembershipState::<UTConfig>::new(effmem(1, 1, m1()), effmem(3, 4, m123_345()));
+        assert!(x.effective().membership().is_joint());
+        assert!(x.is_in_transition());
+        assert_eq!(
+            &vec![btreeset! {1,2,3}, btreeset! {3,4,5}],
+            x.effective().membership().get_joint_config()
+        );
+        assert_eq!(&btreeset! {3,4,5}, x.target());
+    }
+
+    // Uniform but not yet committed.
+    {
+        let x = MembershipState::<UTConfig>::new(effmem(1, 1, m1()), effmem(2, 2, m12()));
+        assert!(x.is_in_transition());
+        assert_eq!(&btreeset! {1,2}, x.target());
+    }
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::error::Error;
 use std::fmt;
 use std::sync::Arc;
@@ -208,6 +209,22 @@ where C: RaftTypeConfig
         &self.effective
     }
 
+    /// Returns true if a membership change is in progress.
+    ///
+    /// A membership change is in progress if the effective membership is a joint config, or if it
+    /// is not yet committed.
+    pub fn is_in_transition(&self) -> bool {
+        self.effective.membership().is_joint() || self.effective.log_id() != self.committed.log_id()
+    }
+
+    /// Returns the voter set the effective membership transitions to.
+    ///
+    /// For a joint config it is the last config, which becomes the uniform config when the joint
+    /// config is committed. For a uniform config it is the config itself.
+    pub fn target(&self) -> &BTreeSet<C::NodeId> {
+        self.effective.membership().get_joint_config().last().unwrap()
+    }
+
     pub(crate) fn change_handler(&self) -> ChangeHandler<C> {
         ChangeHandler { state: self }
     }
@@ -16,10 +16,14 @@ use crate::SnapshotMeta;
 use crate::Vote;
 
 mod accepted;
+mod commit_view;
+mod electability;
 pub(crate) mod io_state;
+mod leadership_diag;
 mod log_state_reader;
 mod membership_state;
 pub(crate) mod snapshot_streaming;
+mod topology;
 mod vote_state_reader;
 
 pub(crate) use io_state::IOState;
@@ -28,6 +32,7 @@ pub(crate) use io_state::IOState;
 #[cfg(test)]
 mod tests {
     mod accepted_test;
+    mod electability_test;
     mod forward_to_leader_test;
     mod is_initialized_test;
     mod log_state_reader_test;
@@ -35,8 +40,17 @@ mod tests {
 }
 
 pub(crate) use accepted::Accepted;
+pub use commit_view::ClusterCommitView;
+pub use commit_view::NodeCommitView;
+pub use electability::Electability;
+pub use leadership_diag::ElectionOutcome;
+pub use leadership_diag::LeadershipDiag;
 pub(crate) use log_state_reader::LogStateReader;
 pub use membership_state::MembershipState;
+pub use topology::NodeReplication;
+pub use topology::NodeRole;
+pub use topology::NodeTopology;
+pub use topology::Topology;
 pub(crate) use vote_state_reader::VoteStateReader;
 
 pub(crate) use crate::raft_state::snapshot_streaming::StreamingState;
@@ -88,6 +102,15 @@ where C: RaftTypeConfig
     /// If a log is in use by a replication task, the purge is postponed and is stored in this
     /// field.
     pub(crate) purge_upto: Option<LogId<C::NodeId>>,
+
+    /// Whether this node gave up the leadership granted by the current `vote`, by
+    /// [`Raft::step_down()`].
+    ///
+    /// A yielded leader keeps its vote, so that it won't vote for another node in the same term,
+    /// but it acts as a follower. It is reset when the vote changes.
+    ///
+    /// [`Raft::step_down()`]: crate::Raft::step_down
+    pub(crate) yielded: bool,
 }
 
 impl<C> Default for RaftState<C>
@@ -106,6 +129,7 @@ where C: RaftTypeConfig
             io_state: IOState::default(),
             snapshot_streaming: None,
             purge_upto: None,
+            yielded: false,
         }
     }
 }
@@ -359,7 +383,7 @@ where C: RaftTypeConfig
     ///
     /// [Determine Server State]: crate::docs::data::vote#vote-and-membership-define-the-server-state
     pub(crate) fn is_leading(&self, id: &C::NodeId) -> bool {
-        self.membership_state.contains(id) && self.vote.leader_id().voted_for().as_ref() == Some(id)
+        self.membership_state.contains(id) && self.vote.leader_id().voted_for().as_ref() == Some(id) && !self.yielded
     }
 
     /// The node is leader
@@ -393,7 +417,7 @@ where C: RaftTypeConfig
     pub(crate) fn forward_to_leader(&self) -> ForwardToLeader<C> {
         let vote = self.vote_ref();
 
-        if vote.is_committed() {
+        if vote.is_committed() && !self.yielded {
             // Safe unwrap(): vote that is committed has to already have voted for some node.
             let id = vote.leader_id().voted_for().unwrap();
 
@@ -409,4 +433,33 @@ where C: RaftTypeConfig
 
         ForwardToLeader::empty()
     }
+
+    /// Estimate whether node `id` could win an election right now, from its local state.
+    ///
+    /// See [`Electability`].
+    pub(crate) fn electability(&self, id: &C::NodeId) -> Electability<C> {
+        if self.is_leader(id) {
+            return Electability::Leader;
+        }
+
+        if !self.is_voter(id) {
+            return Electability::NotVoter;
+        }
+
+        let vote = self.vote_ref();
+
+        // An established leader has replicated its first log to a quorum.
+        // Without any log from it, this node's log is smaller than that of a quorum.
+        if vote.is_committed() {
+            let last_leader_id = self.last_log_id().map(|x| *x.committed_leader_id());
+            if last_leader_id < vote.committed_leader_id() {
+                return Electability::LogBehind {
+                    last_log_id: self.last_log_id().copied(),
+                    leader_vote: *vote,
+                };
+            }
+        }
+
+        Electability::Electable
+    }
 }
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use maplit::btreeset;
+
+use crate::engine::testing::UTConfig;
+use crate::engine::LogIdList;
+use crate::utime::UTime;
+use crate::CommittedLeaderId;
+use crate::EffectiveMembership;
+use crate::Electability;
+use crate::LogId;
+use crate::Membership;
+use crate::MembershipState;
+use crate::RaftState;
+use crate::TokioInstant;
+use crate::Vote;
+
+fn log_id(term: u64, index: u64) -> LogId<u64> {
+    LogId::<u64> {
+        leader_id: CommittedLeaderId::new(term, 0),
+        index,
+    }
+}
+
+fn m12() -> Membership<UTConfig> {
+    Membership::new(vec![btreeset! {1,2}], None)
+}
+
+fn state(vote: Vote<u64>, log_ids: Vec<LogId<u64>>) -> RaftState<UTConfig> {
+    RaftState::<UTConfig> {
+        vote: UTime::new(TokioInstant::now(), vote),
+        log_ids: LogIdList::new(log_ids),
+        membership_state: MembershipState::new(
+            Arc::new(EffectiveMembership::new(Some(log_id(1, 1)), m12())),
+            Arc::new(EffectiveMembership::new(Some(log_id(1, 1)), m12())),
+        ),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_electability_leader() {
+    let rs = state(Vote::new_committed(2, 1), vec![log_id(1, 1)]);
+
+    assert_eq!(Electability::Leader, rs.electability(&1));
+    assert!(rs.electability(&1).could_win());
+}
+
+#[test]
+fn test_electability_not_voter() {
+    let rs = state(Vote::new_committed(2, 1), vec![log_id(1, 1), log_id(2, 2)]);
+
+    assert_eq!(Electability::NotVoter, rs.electability(&3));
+    assert!(!rs.electability(&3).could_win());
+}
+
+#[test]
+fn test_electability_log_behind_leader() {
+    let rs = state(Vote::new_committed(2, 1), vec![log_id(1, 1)]);
+
+    assert_eq!(
+        Electability::LogBehind {
+            last_log_id: Some(log_id(1, 1)),
+            leader_vote: Vote::new_committed(2, 1),
+        },
+        rs.electability(&2)
+    );
+    assert!(!rs.electability(&2).could_win());
+}
+
+#[test]
+fn test_electability_electable() {
+    // Has log from the current leader.
+    let rs = state(Vote::new_committed(2, 1), vec![log_id(1, 1), log_id(2, 2)]);
+    assert_eq!(Electability::Electable, rs.electability(&2));
+    assert!(rs.electability(&2).could_win());
+
+    // No established leader.
+    let rs = state(Vote::new(3, 1), vec![log_id(1, 1)]);
+    assert_eq!(Electability::Electable, rs.electability(&2));
+}
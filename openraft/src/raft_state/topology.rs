@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+use crate::LogId;
+use crate::RaftTypeConfig;
+
+/// The role of a node in the effective membership.
+#[derive(Debug, Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub enum NodeRole {
+    /// The node votes in elections and its log counts toward commitment.
+    Voter,
+
+    /// The node receives logs but does not vote.
+    Learner,
+}
+
+impl fmt::Display for NodeRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Voter => write!(f, "Voter"),
+            Self::Learner => write!(f, "Learner"),
+        }
+    }
+}
+
+/// Replication state of a node, as seen by the leader.
+#[derive(Debug, Clone)]
+#[derive(PartialEq, Eq)]
+pub struct NodeReplication<C>
+where C: RaftTypeConfig
+{
+    /// The last log id known to be replicated to the node.
+    pub matched: Option<LogId<C::NodeId>>,
+
+    /// The number of logs on the leader that are not yet replicated to the node.
+    pub lag: u64,
+}
+
+/// A node in the cluster topology.
+#[derive(Debug, Clone)]
+#[derive(PartialEq, Eq)]
+pub struct NodeTopology<C>
+where C: RaftTypeConfig
+{
+    /// The role of the node.
+    pub role: NodeRole,
+
+    /// Whether the node is a read replica, see
+    /// [`Raft::set_read_replica()`](crate::Raft::set_read_replica).
+    pub read_replica: bool,
+
+    /// The node info stored in the membership.
+    pub node: C::Node,
+
+    /// The replication state of this node, only present when the topology is built by the leader.
+    pub replication: Option<NodeReplication<C>>,
+}
+
+/// All nodes known to a Raft node and their roles.
+///
+/// It is returned by [`Raft::cluster_topology()`] and combines the effective membership with the
+/// replication progress of every node, if it is built by the leader.
+///
+/// [`Raft::cluster_topology()`]: crate::Raft::cluster_topology
+#[derive(Debug, Clone)]
+#[derive(PartialEq, Eq)]
+pub struct Topology<C>
+where C: RaftTypeConfig
+{
+    /// The id of the node that built this topology.
+    pub id: C::NodeId,
+
+    /// The leader this node knows of, if any.
+    pub current_leader: Option<C::NodeId>,
+
+    /// The log id of the effective membership.
+    pub membership_log_id: Option<LogId<C::NodeId>>,
+
+    /// Every node in the effective membership.
+    pub nodes: BTreeMap<C::NodeId, NodeTopology<C>>,
+}
+
+impl<C> fmt::Display for Topology<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Topology{{id: {}, current_leader: {}, membership_log_id: {}, nodes: [",
+            self.id,
+            self.current_leader.display(),
+            self.membership_log_id.display()
+        )?;
+
+        for (i, (id, n)) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", id, n.role)?;
+            if let Some(r) = &n.replication {
+                write!(f, "(matched: {}, lag: {})", r.matched.display(), r.lag)?;
+            }
+        }
+
+        write!(f, "]}}")
+    }
+}
@@ -139,6 +139,11 @@ where
     /// Last matching log id on a follower/learner
     matching: Option<LogId<C::NodeId>>,
 
+    /// When the last AppendEntries that the target responded to was sent.
+    ///
+    /// It is used to skip redundant heartbeats, see [`Config::heartbeat_suppression`].
+    last_append_sent: Option<InstantOf<C>>,
+
     /// Next replication action to run.
     next_action: Option<Data<C>>,
 
@@ -193,6 +198,7 @@ where
             config,
             committed,
             matching,
+            last_append_sent: None,
             tx_raft_core,
             rx_event,
             weak_tx_event: tx_event.downgrade(),
@@ -210,6 +216,15 @@ where
 
     #[tracing::instrument(level="debug", skip(self), fields(session=%self.session_id, target=display(self.target), cluster=%self.config.cluster_name))]
     async fn main(mut self) -> Result<(), ReplicationClosed> {
+        let res = self.replication_loop().await;
+
+        // The target may have been removed, do not keep streaming a snapshot to it.
+        self.cancel_snapshot_streaming();
+
+        res
+    }
+
+    async fn replication_loop(&mut self) -> Result<(), ReplicationClosed> {
         loop {
             let action = self.next_action.take();
 
@@ -271,6 +286,16 @@ where
                             });
                             return Ok(());
                         }
+                        ReplicationError::LogPurged { needed_index, .. } => {
+                            let _ = self.tx_raft_core.send(Notify::Network {
+                                response: Response::LogPurged {
+                                    target: self.target,
+                                    request_id,
+                                    needed_index,
+                                    session_id: self.session_id,
+                                },
+                            });
+                        }
                         ReplicationError::StorageError(error) => {
                             tracing::error!(error=%error, "error replication to target={}", self.target);
 
@@ -302,6 +327,19 @@ where
                                     true
                                 }
                                 RPCError::Network(_) => false,
+                                RPCError::Malformed(malformed) => {
+                                    // A malformed response is most likely a bug or a version
+                                    // mismatch: retrying at once won't help.
+                                    tracing::error!(
+                                        error = display(malformed),
+                                        "malformed response from target={}",
+                                        self.target
+                                    );
+                                    if self.backoff.is_none() {
+                                        self.backoff = Some(self.network.backoff());
+                                    }
+                                    false
+                                }
                                 RPCError::RemoteError(_) => false,
                             };
 
@@ -333,6 +371,24 @@ where
         Ok(())
     }
 
+    /// Returns true if a heartbeat is not needed, because the target has received an
+    /// AppendEntries within the last heartbeat interval.
+    fn is_heartbeat_suppressed(&self) -> bool {
+        if !self.config.heartbeat_suppression {
+            return false;
+        }
+
+        let Some(sent) = self.last_append_sent else {
+            return false;
+        };
+
+        let suppressed = InstantOf::<C>::now() < sent + Duration::from_millis(self.config.heartbeat_interval);
+        if suppressed {
+            tracing::debug!(last_append_sent = debug(sent), "heartbeat is suppressed");
+        }
+        suppressed
+    }
+
     /// When a [`PayloadTooLarge`] error is received, update the hint for the next several RPC.
     fn update_hint(&mut self, too_large: &PayloadTooLarge) {
         const DEFAULT_ENTRIES_HINT_TTL: u64 = 10;
@@ -349,6 +405,9 @@ where
                 // TODO: handle too large
                 tracing::error!("InstallSnapshot RPC is too large, but it is not supported yet");
             }
+            RPCTypes::ReadIndex => {
+                unreachable!("ReadIndex RPC is not sent by replication")
+            }
         }
     }
 
@@ -394,6 +453,15 @@ where
                 (vec![], r)
             } else {
                 let logs = self.log_reader.try_get_log_entries(start..end).await?;
+
+                let first_index = logs.first().map(|ent| ent.get_log_id().index);
+                if first_index != Some(start) {
+                    return Err(ReplicationError::LogPurged {
+                        needed_index: start,
+                        snapshot_index: first_index.map(|index| index - 1),
+                    });
+                }
+
                 debug_assert_eq!(
                     logs.len(),
                     (end - start) as usize,
@@ -448,6 +516,9 @@ where
 
         let append_resp = append_res?;
 
+        // Any response means the target has received this request.
+        self.last_append_sent = Some(leader_time);
+
         tracing::debug!(
             req = display(&sending_range),
             resp = display(&append_resp),
@@ -488,6 +559,17 @@ where
 
                 Ok(None)
             }
+            AppendEntriesResponse::TermRegression(e) => {
+                // The entries are read from the local log store, which returned corrupted data.
+                tracing::error!(
+                    error = display(&e),
+                    "target={} rejected corrupted log entries",
+                    self.target
+                );
+
+                let io_err = StorageIOError::read_logs(AnyError::new(&e));
+                Err(ReplicationError::StorageError(StorageError::IO { source: io_err }))
+            }
         }
     }
 
@@ -664,7 +746,7 @@ where
             }
             Replicate::Heartbeat => {
                 // Never overwrite action with payload.
-                if self.next_action.is_none() {
+                if self.next_action.is_none() && !self.is_heartbeat_suppressed() {
                     self.next_action = Some(Data::new_heartbeat());
                 }
             }
@@ -787,6 +869,24 @@ where
         }
     }
 
+    /// Signal the running snapshot streaming task to quit, if there is one.
+    ///
+    /// The default chunked implementation checks the cancel signal before sending every chunk, and
+    /// does not wait for the response of an outstanding chunk once canceled.
+    ///
+    /// It does not wait for the task to quit: a [`RaftNetworkV2::full_snapshot()`] implementation
+    /// that does not respect the cancel signal should not block removing a replication.
+    ///
+    /// [`RaftNetworkV2::full_snapshot()`]: crate::network::v2::RaftNetworkV2::full_snapshot
+    fn cancel_snapshot_streaming(&mut self) {
+        let Some((tx_cancel, _jh)) = self.snapshot_state.take() else {
+            return;
+        };
+
+        tracing::info!("cancel snapshot streaming to target={}", self.target);
+        drop(tx_cancel);
+    }
+
     fn handle_snapshot_callback(
         &mut self,
         callback: DataWithId<SnapshotCallback<C>>,
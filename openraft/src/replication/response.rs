@@ -45,6 +45,23 @@ where C: RaftTypeConfig
         session_id: ReplicationSessionId<C::NodeId>,
     },
 
+    /// The logs to replicate to a target are found purged from the log store, and the target has
+    /// to be replicated with a snapshot.
+    /// Sent by a replication task `ReplicationCore`.
+    LogPurged {
+        /// The ID of the target node to replicate to.
+        target: C::NodeId,
+
+        /// The id of the replication action that failed to read the logs.
+        request_id: RequestId,
+
+        /// The index of the first log to replicate.
+        needed_index: u64,
+
+        /// In which session this message is sent.
+        session_id: ReplicationSessionId<C::NodeId>,
+    },
+
     /// [`StorageError`] error has taken place locally(not on remote node) when replicating, and
     /// [`RaftCore`](`crate::core::RaftCore`) needs to shutdown. Sent by a replication task
     /// [`crate::replication::ReplicationCore`].
@@ -85,6 +102,19 @@ where C: RaftTypeConfig
                 )
             }
 
+            Self::LogPurged {
+                target,
+                request_id,
+                needed_index,
+                session_id,
+            } => {
+                write!(
+                    f,
+                    "ReplicationLogPurged: target: {}, id: {}, needed_index: {}, session_id: {}",
+                    target, request_id, needed_index, session_id
+                )
+            }
+
             Self::StorageError { error } => write!(f, "ReplicationStorageError: {}", error),
 
             Self::HigherVote { target, higher, vote } => {
@@ -12,6 +12,10 @@ use crate::RaftTypeConfig;
 use crate::StorageIOError;
 
 /// A oneshot callback for completion of log io operation.
+///
+/// If it is dropped without calling [`Self::log_io_completed()`], the logs are never considered
+/// durable: a follower fails with a storage error, and a leader does not count the logs as
+/// accepted by itself.
 pub struct LogFlushed<C>
 where C: RaftTypeConfig
 {
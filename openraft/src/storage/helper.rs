@@ -80,6 +80,16 @@ where
             "get_initial_state"
         );
 
+        // The saved committed log id is advisory and must not point to a log that is not durable.
+        if committed > last_log_id {
+            tracing::warn!(
+                committed = display(committed.display()),
+                last_log_id = display(last_log_id.display()),
+                "saved committed log id is greater than the last log id, ignore it"
+            );
+            committed = last_log_id;
+        }
+
         // TODO: It is possible `committed < last_applied` because when installing snapshot,
         //       new committed should be saved, but not yet.
         if committed < last_applied {
@@ -168,6 +178,7 @@ where
             accepted: Default::default(),
             io_state,
             snapshot_streaming: None,
+            yielded: false,
             purge_upto: last_purged_log_id,
         })
     }
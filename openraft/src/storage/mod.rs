@@ -3,6 +3,7 @@
 mod callback;
 mod helper;
 mod log_store_ext;
+mod replay;
 mod snapshot_signature;
 mod v2;
 
@@ -13,6 +14,8 @@ use std::ops::RangeBounds;
 pub use helper::StorageHelper;
 pub use log_store_ext::RaftLogReaderExt;
 use openraft_macros::add_async_trait;
+pub use replay::replay_log_into;
+pub use replay::ReplayObserver;
 pub use snapshot_signature::SnapshotSignature;
 pub use v2::RaftLogStorage;
 pub use v2::RaftLogStorageExt;
@@ -50,6 +53,18 @@ where C: RaftTypeConfig
     /// Caveat: even when two snapshot is built with the same `last_log_id`, they still could be
     /// different in bytes.
     pub snapshot_id: SnapshotId,
+
+    /// A 64-bit checksum of the snapshot data, for verifying the data after it is transferred.
+    ///
+    /// It is computed by the sender of a snapshot and is set only in the last chunk of a
+    /// [`Chunked`] transfer. The receiver rejects the snapshot with
+    /// [`InstallSnapshotError::ChecksumMismatch`] if the received data does not match it, and the
+    /// sender transfers the snapshot again. `None` means the data is not verified.
+    ///
+    /// [`Chunked`]: crate::network::snapshot_transport::Chunked
+    /// [`InstallSnapshotError::ChecksumMismatch`]: crate::error::InstallSnapshotError::ChecksumMismatch
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub checksum: Option<u64>,
 }
 
 impl<C> fmt::Display for SnapshotMeta<C>
@@ -69,6 +84,29 @@ where C: RaftTypeConfig
 impl<C> SnapshotMeta<C>
 where C: RaftTypeConfig
 {
+    /// Create a snapshot meta without a checksum.
+    ///
+    /// Prefer this to a struct literal, so that adding a field to `SnapshotMeta` does not break
+    /// the application.
+    pub fn new(
+        last_log_id: Option<LogId<C::NodeId>>,
+        last_membership: StoredMembership<C>,
+        snapshot_id: SnapshotId,
+    ) -> Self {
+        Self {
+            last_log_id,
+            last_membership,
+            snapshot_id,
+            checksum: None,
+        }
+    }
+
+    /// Set the checksum of the snapshot data.
+    pub fn with_checksum(mut self, checksum: u64) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
     pub fn signature(&self) -> SnapshotSignature<C::NodeId> {
         SnapshotSignature {
             last_log_id: self.last_log_id,
@@ -0,0 +1,93 @@
+//! Replay logs into a state machine outside of a running Raft node.
+
+use crate::storage::RaftLogReaderExt;
+use crate::storage::RaftStateMachine;
+use crate::LogId;
+use crate::RaftLogId;
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+
+/// The max number of log entries to read from the log store at a time.
+const READ_BATCH_SIZE: u64 = 64;
+
+/// Callbacks to inspect every step of [`replay_log_into()`].
+///
+/// Every method has a default implementation that does nothing. `()` is an observer that does
+/// nothing.
+pub trait ReplayObserver<C, SM>
+where C: RaftTypeConfig
+{
+    /// Called before `entry` is applied to `sm`.
+    fn before_apply(&mut self, _sm: &mut SM, _entry: &C::Entry) {}
+
+    /// Called after the entry of `log_id` is applied to `sm`, with the response returned by
+    /// [`RaftStateMachine::apply()`].
+    fn after_apply(&mut self, _sm: &mut SM, _log_id: &LogId<C::NodeId>, _response: &C::R) {}
+}
+
+impl<C, SM> ReplayObserver<C, SM> for () where C: RaftTypeConfig {}
+
+/// Read the log entries in the index range `[from, to)` from `log_reader`, and apply them to `sm`
+/// one by one, in index order.
+///
+/// It runs without a Raft node: there is no network, no timer and no concurrency, thus feeding the
+/// same log into the same initial state machine always produces the same sequence of
+/// [`RaftStateMachine::apply()`] calls. It is meant to reproduce a state machine bug with a
+/// recorded log, or to build a regression test for a state machine.
+///
+/// Every entry is applied with a separate call to `apply()`, so that `observer` can inspect the
+/// state machine before and after each of them. Pass `&mut ()` if no inspection is needed.
+///
+/// It returns the log id of the last applied entry, or `None` if the range is empty. An error is
+/// returned if an entry in the range is not found in `log_reader`, e.g., it is already purged.
+///
+/// Example:
+/// ```ignore
+/// let (mut log_store, _) = open_recorded_store();
+/// let mut sm = MyStateMachine::default();
+/// let last = replay_log_into(&mut log_store, &mut sm, 1, 101, &mut ()).await?;
+/// ```
+pub async fn replay_log_into<C, LR, SM, O>(
+    log_reader: &mut LR,
+    sm: &mut SM,
+    from: u64,
+    to: u64,
+    observer: &mut O,
+) -> Result<Option<LogId<C::NodeId>>, StorageError<C::NodeId>>
+where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+    SM: RaftStateMachine<C>,
+    O: ReplayObserver<C, SM>,
+{
+    let mut last_applied = None;
+    let mut start = from;
+
+    while start < to {
+        let end = std::cmp::min(to, start + READ_BATCH_SIZE);
+        let entries = log_reader.get_log_entries(start..end).await?;
+
+        for entry in entries {
+            let log_id = *entry.get_log_id();
+
+            tracing::debug!(log_id = display(&log_id), "replay log entry");
+
+            observer.before_apply(sm, &entry);
+
+            let responses = sm.apply([entry]).await?;
+
+            debug_assert_eq!(1, responses.len(), "apply() should return one response for one entry");
+
+            if let Some(response) = responses.first() {
+                observer.after_apply(sm, &log_id, response);
+            }
+
+            last_applied = Some(log_id);
+        }
+
+        start = end;
+    }
+
+    Ok(last_applied)
+}
@@ -76,9 +76,16 @@ where C: RaftTypeConfig
     /// deal with state reversion of state machine carefully upon restart. E.g., do not serve
     /// read operation a new `commit` message is received.
     ///
+    /// It is not called if [`Config::persist_commit_index`] is disabled.
+    ///
+    /// The saved committed log id is advisory: it is only used to re-apply logs upon startup, and
+    /// never replaces the quorum as the source of safety. A value read back that is greater than
+    /// the last log id in this log store is ignored.
+    ///
     /// See: [`docs::data::log_pointers`].
     ///
     /// [`docs::data::log_pointers`]: `crate::docs::data::log_pointers#optionally-persisted-committed`
+    /// [`Config::persist_commit_index`]: crate::Config::persist_commit_index
     async fn save_committed(&mut self, _committed: Option<LogId<C::NodeId>>) -> Result<(), StorageError<C::NodeId>> {
         // By default `committed` log id is not saved
         Ok(())
@@ -183,6 +190,17 @@ where C: RaftTypeConfig
     /// - An implementation with persistent snapshot: `apply()` does not have to persist state on
     ///   disk. But every snapshot has to be persistent. And when starting up the application, the
     ///   state machine should be rebuilt from the last snapshot.
+    ///
+    /// ### Execution
+    ///
+    /// `apply()` is not called by `RaftCore`: the state machine is owned by a worker task spawned
+    /// with [`AsyncRuntime::spawn()`], which applies committed entries in log order, exactly once,
+    /// and reports the responses and the last applied log id back to `RaftCore`. A slow `apply()`
+    /// therefore delays the responses to clients, but not heartbeats or replication. If `apply()`
+    /// is CPU-bound and the runtime has few worker threads, an implementation should move the
+    /// heavy work off the async worker threads, e.g., with `tokio::task::spawn_blocking()`.
+    ///
+    /// [`AsyncRuntime::spawn()`]: crate::AsyncRuntime::spawn
     async fn apply<I>(&mut self, entries: I) -> Result<Vec<C::R>, StorageError<C::NodeId>>
     where
         I: IntoIterator<Item = C::Entry> + OptionalSend,
@@ -238,5 +256,13 @@ where C: RaftTypeConfig
     /// A proper snapshot implementation will store last-applied-log-id and the
     /// last-applied-membership config as part of the snapshot, which should be decoded for
     /// creating this method's response data.
+    ///
+    /// ### application metadata
+    ///
+    /// Application metadata that has to stay in sync with a snapshot, such as offsets or versions,
+    /// should be stored in the snapshot data itself, e.g., as a manifest header in
+    /// [`RaftTypeConfig::SnapshotData`]. Openraft treats the data as a whole: the manifest is
+    /// built with it, returned with it by this method, and transferred and installed with it on a
+    /// follower. It then never gets out of sync with the data it describes.
     async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<C>>, StorageError<C::NodeId>>;
 }
@@ -159,6 +159,12 @@ pub enum Violation<NID: NodeId> {
     #[error("invalid next log to apply: prev: {prev:?}, next: {next}")]
     ApplyNonConsecutive { prev: Option<LogId<NID>>, next: LogId<NID> },
 
+    #[error("can not apply log that is not durable, durable: {durable:?}, apply upto: {upto}")]
+    ApplyNonDurable {
+        durable: Option<LogId<NID>>,
+        upto: LogId<NID>,
+    },
+
     #[error("applied log can not conflict, last_applied: {last_applied:?}, delete since: {first_conflict_log_id}")]
     AppliedWontConflict {
         last_applied: Option<LogId<NID>>,
@@ -203,6 +209,14 @@ where NID: NodeId
 impl<NID> StorageError<NID>
 where NID: NodeId
 {
+    /// Returns the subject on which this error occurred.
+    pub fn subject(&self) -> &ErrorSubject<NID> {
+        match self {
+            StorageError::Defensive { source } => &source.subject,
+            StorageError::IO { source } => source.subject(),
+        }
+    }
+
     pub fn into_defensive(self) -> Option<DefensiveError<NID>> {
         match self {
             StorageError::Defensive { source } => Some(source),
@@ -259,6 +273,11 @@ where NID: NodeId
         }
     }
 
+    /// Returns the subject on which this error occurred.
+    pub fn subject(&self) -> &ErrorSubject<NID> {
+        &self.subject
+    }
+
     pub fn write_log_entry(log_id: LogId<NID>, source: impl Into<AnyError>) -> Self {
         Self::new(ErrorSubject::Log(log_id), ErrorVerb::Write, source)
     }
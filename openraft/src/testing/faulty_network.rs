@@ -0,0 +1,358 @@
+//! An in-memory network that injects faults between Raft nodes.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyerror::AnyError;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+use crate::error::CheckIsLeaderError;
+use crate::error::Fatal;
+use crate::error::NetworkError;
+use crate::error::RPCError;
+use crate::error::RaftError;
+use crate::error::RemoteError;
+use crate::error::ReplicationClosed;
+use crate::error::StreamingError;
+use crate::error::Unreachable;
+use crate::network::v2::RaftNetworkV2;
+use crate::network::RPCOption;
+use crate::network::RaftNetworkFactory;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::AppendEntriesResponse;
+use crate::raft::ReadIndexRequest;
+use crate::raft::ReadIndexResponse;
+use crate::raft::SnapshotResponse;
+use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
+use crate::AsyncRuntime;
+use crate::OptionalSend;
+use crate::Raft;
+use crate::RaftTypeConfig;
+use crate::Snapshot;
+use crate::Vote;
+
+/// An in-memory network connecting [`Raft`] nodes in one process, with faults injected at
+/// runtime.
+///
+/// Every RPC is delivered by calling the target [`Raft`] directly. Before delivery, the link from
+/// the sending node to the target is checked:
+///
+/// - If the link is blocked by a partition, the RPC fails with [`Unreachable`].
+/// - With probability `drop_rate`, the RPC is dropped and fails with [`NetworkError`].
+/// - Otherwise, it is delayed by the latency of the link plus a random jitter in `[0, jitter]`. A
+///   non-zero jitter lets concurrent RPCs on the same link arrive out of order.
+///
+/// The random decisions are made by a generator seeded with the seed passed to
+/// [`FaultyNetwork::new()`], so that a scenario can be reproduced.
+///
+/// Usage:
+/// ```ignore
+/// let net = FaultyNetwork::<C>::new(0);
+///
+/// for id in [1, 2, 3] {
+///     let raft = Raft::new(id, config.clone(), net.factory(id), log_store, sm).await?;
+///     net.add_node(id, raft);
+/// }
+///
+/// net.partition([1], [2, 3]);
+/// net.set_latency(2, 3, Duration::from_millis(50));
+/// net.set_drop_rate(0.1);
+/// // ...
+/// net.heal();
+/// ```
+pub struct FaultyNetwork<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<Mutex<FaultState<C>>>,
+}
+
+impl<C> Clone for FaultyNetwork<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+struct FaultState<C>
+where C: RaftTypeConfig
+{
+    nodes: BTreeMap<C::NodeId, Raft<C>>,
+
+    /// Directed links `(from, to)` that do not deliver any RPC.
+    blocked: BTreeSet<(C::NodeId, C::NodeId)>,
+
+    /// Nodes that can not send to or receive from any other node.
+    isolated: BTreeSet<C::NodeId>,
+
+    /// Latency of a directed link `(from, to)`, overriding `default_latency`.
+    latency: BTreeMap<(C::NodeId, C::NodeId), Duration>,
+
+    default_latency: Duration,
+
+    jitter: Duration,
+
+    drop_rate: f64,
+
+    rng: StdRng,
+}
+
+impl<C> FaultyNetwork<C>
+where C: RaftTypeConfig
+{
+    /// Create a network without any fault, using `seed` to make random decisions.
+    pub fn new(seed: u64) -> Self {
+        let state = FaultState {
+            nodes: BTreeMap::new(),
+            blocked: BTreeSet::new(),
+            isolated: BTreeSet::new(),
+            latency: BTreeMap::new(),
+            default_latency: Duration::default(),
+            jitter: Duration::default(),
+            drop_rate: 0.0,
+            rng: StdRng::seed_from_u64(seed),
+        };
+
+        Self {
+            inner: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Build a [`RaftNetworkFactory`] for the node `id`, to pass to [`Raft::new()`].
+    pub fn factory(&self, id: C::NodeId) -> FaultyNetworkFactory<C> {
+        FaultyNetworkFactory { id, net: self.clone() }
+    }
+
+    /// Register a Raft node so that it receives RPCs sent to `id`.
+    pub fn add_node(&self, id: C::NodeId, raft: Raft<C>) {
+        self.inner.lock().unwrap().nodes.insert(id, raft);
+    }
+
+    /// Unregister a Raft node. RPCs sent to it fail with [`Unreachable`].
+    pub fn remove_node(&self, id: &C::NodeId) -> Option<Raft<C>> {
+        self.inner.lock().unwrap().nodes.remove(id)
+    }
+
+    /// Returns the Raft node registered with `id`.
+    pub fn get_node(&self, id: &C::NodeId) -> Option<Raft<C>> {
+        self.inner.lock().unwrap().nodes.get(id).cloned()
+    }
+
+    /// Block RPCs in both directions between every node in `a` and every node in `b`.
+    pub fn partition(&self, a: impl IntoIterator<Item = C::NodeId>, b: impl IntoIterator<Item = C::NodeId>) {
+        let b = b.into_iter().collect::<Vec<_>>();
+
+        let mut st = self.inner.lock().unwrap();
+        for x in a {
+            for y in b.iter() {
+                st.blocked.insert((x, *y));
+                st.blocked.insert((*y, x));
+            }
+        }
+    }
+
+    /// Block or unblock RPCs sent from `from` to `to`, leaving the other direction untouched.
+    pub fn set_link_blocked(&self, from: C::NodeId, to: C::NodeId, blocked: bool) {
+        let mut st = self.inner.lock().unwrap();
+        if blocked {
+            st.blocked.insert((from, to));
+        } else {
+            st.blocked.remove(&(from, to));
+        }
+    }
+
+    /// Isolate a node from, or reconnect it to, all other nodes.
+    pub fn set_isolated(&self, id: C::NodeId, isolated: bool) {
+        let mut st = self.inner.lock().unwrap();
+        if isolated {
+            st.isolated.insert(id);
+        } else {
+            st.isolated.remove(&id);
+        }
+    }
+
+    /// Remove all partitions and isolations. Latency, jitter and drop rate are kept.
+    pub fn heal(&self) {
+        let mut st = self.inner.lock().unwrap();
+        st.blocked.clear();
+        st.isolated.clear();
+    }
+
+    /// Set the latency of RPCs sent from `from` to `to`.
+    pub fn set_latency(&self, from: C::NodeId, to: C::NodeId, latency: Duration) {
+        self.inner.lock().unwrap().latency.insert((from, to), latency);
+    }
+
+    /// Set the latency of links without a latency set by [`Self::set_latency()`].
+    pub fn set_default_latency(&self, latency: Duration) {
+        self.inner.lock().unwrap().default_latency = latency;
+    }
+
+    /// Set the max random delay added to every RPC, which reorders RPCs sent on the same link.
+    pub fn set_jitter(&self, jitter: Duration) {
+        self.inner.lock().unwrap().jitter = jitter;
+    }
+
+    /// Set the probability in `[0, 1]` that an RPC is dropped.
+    pub fn set_drop_rate(&self, drop_rate: f64) {
+        assert!(
+            (0.0..=1.0).contains(&drop_rate),
+            "drop_rate must be in [0, 1], got {}",
+            drop_rate
+        );
+        self.inner.lock().unwrap().drop_rate = drop_rate;
+    }
+
+    /// Decide the fate of an RPC from `from` to `to`, wait for the delay, and return the target.
+    async fn deliver(&self, from: C::NodeId, to: C::NodeId) -> Result<Raft<C>, Fault> {
+        let (raft, delay) = {
+            let mut st = self.inner.lock().unwrap();
+
+            if st.isolated.contains(&from) || st.isolated.contains(&to) || st.blocked.contains(&(from, to)) {
+                let msg = format!("link {} -> {} is partitioned", from, to);
+                return Err(Fault::Unreachable(Unreachable::new(&AnyError::error(msg))));
+            }
+
+            let drop_rate = st.drop_rate;
+            if drop_rate > 0.0 && st.rng.gen_bool(drop_rate) {
+                let msg = format!("RPC {} -> {} is dropped", from, to);
+                return Err(Fault::Network(NetworkError::new(&AnyError::error(msg))));
+            }
+
+            let Some(raft) = st.nodes.get(&to).cloned() else {
+                let msg = format!("node {} is not found", to);
+                return Err(Fault::Unreachable(Unreachable::new(&AnyError::error(msg))));
+            };
+
+            let mut delay = st.latency.get(&(from, to)).copied().unwrap_or(st.default_latency);
+            let jitter = st.jitter;
+            if !jitter.is_zero() {
+                delay += st.rng.gen_range(Duration::ZERO..=jitter);
+            }
+
+            (raft, delay)
+        };
+
+        if !delay.is_zero() {
+            C::AsyncRuntime::sleep(delay).await;
+        }
+
+        Ok(raft)
+    }
+}
+
+/// The fault injected to an RPC.
+enum Fault {
+    Unreachable(Unreachable),
+    Network(NetworkError),
+}
+
+impl Fault {
+    fn into_rpc_error<C, E>(self) -> RPCError<C, E>
+    where
+        C: RaftTypeConfig,
+        E: std::error::Error,
+    {
+        match self {
+            Fault::Unreachable(e) => RPCError::Unreachable(e),
+            Fault::Network(e) => RPCError::Network(e),
+        }
+    }
+
+    fn into_streaming_error<C, E>(self) -> StreamingError<C, E>
+    where
+        C: RaftTypeConfig,
+        E: std::error::Error,
+    {
+        match self {
+            Fault::Unreachable(e) => StreamingError::Unreachable(e),
+            Fault::Network(e) => StreamingError::Network(e),
+        }
+    }
+}
+
+/// The [`RaftNetworkFactory`] of one node in a [`FaultyNetwork`].
+pub struct FaultyNetworkFactory<C>
+where C: RaftTypeConfig
+{
+    id: C::NodeId,
+    net: FaultyNetwork<C>,
+}
+
+impl<C> RaftNetworkFactory<C> for FaultyNetworkFactory<C>
+where C: RaftTypeConfig
+{
+    type Network = FaultyConnection<C>;
+
+    async fn new_client(&mut self, target: C::NodeId, _node: &C::Node) -> Self::Network {
+        FaultyConnection {
+            id: self.id,
+            target,
+            net: self.net.clone(),
+        }
+    }
+}
+
+/// A connection from one node to another in a [`FaultyNetwork`].
+pub struct FaultyConnection<C>
+where C: RaftTypeConfig
+{
+    id: C::NodeId,
+    target: C::NodeId,
+    net: FaultyNetwork<C>,
+}
+
+impl<C> RaftNetworkV2<C> for FaultyConnection<C>
+where C: RaftTypeConfig
+{
+    async fn append_entries(
+        &mut self,
+        rpc: AppendEntriesRequest<C>,
+        _option: RPCOption,
+    ) -> Result<AppendEntriesResponse<C>, RPCError<C, RaftError<C>>> {
+        let raft = self.net.deliver(self.id, self.target).await.map_err(Fault::into_rpc_error)?;
+        let resp = raft.append_entries(rpc).await.map_err(|e| RemoteError::new(self.target, e))?;
+        Ok(resp)
+    }
+
+    async fn vote(
+        &mut self,
+        rpc: VoteRequest<C>,
+        _option: RPCOption,
+    ) -> Result<VoteResponse<C>, RPCError<C, RaftError<C>>> {
+        let raft = self.net.deliver(self.id, self.target).await.map_err(Fault::into_rpc_error)?;
+        let resp = raft.vote(rpc).await.map_err(|e| RemoteError::new(self.target, e))?;
+        Ok(resp)
+    }
+
+    async fn read_index(
+        &mut self,
+        rpc: ReadIndexRequest<C>,
+        _option: RPCOption,
+    ) -> Result<ReadIndexResponse<C>, RPCError<C, RaftError<C, CheckIsLeaderError<C>>>> {
+        let raft = self.net.deliver(self.id, self.target).await.map_err(Fault::into_rpc_error)?;
+        let resp = raft.read_index(rpc).await.map_err(|e| RemoteError::new(self.target, e))?;
+        Ok(resp)
+    }
+
+    async fn full_snapshot(
+        &mut self,
+        vote: Vote<C::NodeId>,
+        snapshot: Snapshot<C>,
+        _cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse<C>, StreamingError<C, Fatal<C>>> {
+        let raft = self.net.deliver(self.id, self.target).await.map_err(Fault::into_streaming_error)?;
+        let resp = raft.install_full_snapshot(vote, snapshot).await.map_err(|e| RemoteError::new(self.target, e))?;
+        Ok(resp)
+    }
+}
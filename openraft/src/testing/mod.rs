@@ -1,10 +1,14 @@
 //! Testing utilities for OpenRaft.
 
+mod faulty_network;
 mod store_builder;
 mod suite;
 
 use std::collections::BTreeSet;
 
+pub use faulty_network::FaultyConnection;
+pub use faulty_network::FaultyNetwork;
+pub use faulty_network::FaultyNetworkFactory;
 pub use store_builder::StoreBuilder;
 pub use suite::Suite;
 
@@ -57,8 +57,12 @@ pub trait RaftTypeConfig:
 
     /// Snapshot data for exposing a snapshot for reading & writing.
     ///
+    /// Openraft never inspects it, so it can carry application metadata along with the state
+    /// machine data, see [`RaftStateMachine::get_current_snapshot()`].
+    ///
     /// See the [storage chapter of the guide][sto] for details on log compaction / snapshotting.
     ///
+    /// [`RaftStateMachine::get_current_snapshot()`]: crate::storage::RaftStateMachine::get_current_snapshot
     /// [sto]: crate::docs::getting_started#3-implement-raftlogstorage-and-raftstatemachine
     type SnapshotData: OptionalSend + 'static;
 
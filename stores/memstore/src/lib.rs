@@ -112,6 +112,13 @@ pub enum BlockOperation {
     DelayBuildingSnapshot,
     BuildSnapshot,
     PurgeLog,
+    /// Fail appending log entries with an IO error, to simulate a failing disk.
+    FailAppend,
+    /// Append log entries but drop the flush callback without calling it, to simulate a log store
+    /// that never reports the entries durable.
+    DropLogFlush,
+    /// Fail applying log entries to the state machine with an IO error.
+    FailApply,
 }
 
 /// Block operations for testing purposes.
@@ -284,11 +291,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<MemStateMachine> {
             format!("--{}", snapshot_idx)
         };
 
-        let meta = SnapshotMeta {
-            last_log_id: last_applied_log,
-            last_membership,
-            snapshot_id,
-        };
+        let meta = SnapshotMeta::new(last_applied_log, last_membership, snapshot_id);
 
         let snapshot = MemStoreSnapshot {
             meta: meta.clone(),
@@ -365,6 +368,11 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
     #[tracing::instrument(level = "trace", skip_all)]
     async fn append<I>(&mut self, entries: I, callback: LogFlushed<TypeConfig>) -> Result<(), StorageError<MemNodeId>>
     where I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend {
+        if self.block.get_blocking(&BlockOperation::FailAppend).is_some() {
+            let e = std::io::Error::new(std::io::ErrorKind::Other, "injected append failure");
+            return Err(StorageIOError::write_logs(&e).into());
+        }
+
         let mut log = self.log.write().await;
         for entry in entries {
             let s =
@@ -372,6 +380,11 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
             log.insert(entry.log_id.index, s);
         }
 
+        if self.block.get_blocking(&BlockOperation::DropLogFlush).is_some() {
+            tracing::info!("drop log flush callback");
+            return Ok(());
+        }
+
         callback.log_io_completed(Ok(()));
         Ok(())
     }
@@ -443,6 +456,11 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
         for entry in entries {
             tracing::debug!(%entry.log_id, "replicate to sm");
 
+            if self.block.get_blocking(&BlockOperation::FailApply).is_some() {
+                let e = std::io::Error::new(std::io::ErrorKind::Other, "injected apply failure");
+                return Err(StorageIOError::apply(entry.log_id, &e).into());
+            }
+
             sm.last_applied_log = Some(entry.log_id);
 
             match entry.payload {
@@ -448,11 +448,7 @@ impl RaftSnapshotBuilder<TypeConfig> for Arc<SledStore> {
             format!("--{}", snapshot_idx)
         };
 
-        let meta = SnapshotMeta {
-            last_log_id: last_applied_log,
-            last_membership,
-            snapshot_id,
-        };
+        let meta = SnapshotMeta::new(last_applied_log, last_membership, snapshot_id);
 
         let snapshot = ExampleSnapshot {
             meta: meta.clone(),
@@ -13,8 +13,10 @@ mod t11_append_conflicts;
 mod t11_append_entries_with_bigger_term;
 mod t11_append_inconsistent_log;
 mod t11_append_updates_membership;
+mod t20_append_entries_observer;
 mod t30_replication_1_voter_to_isolated_learner;
 mod t60_enable_heartbeat;
 mod t61_heartbeat_reject_vote;
 mod t61_large_heartbeat;
+mod t62_heartbeat_suppression;
 mod t90_issue_216_stale_last_log_id;
@@ -71,6 +71,7 @@ async fn conflict_with_empty_entries() -> Result<()> {
         prev_log_id: None,
         entries: vec![blank_ent(0, 0, 0), blank_ent(1, 0, 1), Entry {
             log_id: LogId::new(CommittedLeaderId::new(1, 0), 2),
+            context: vec![],
             payload: EntryPayload::Normal(ClientRequest {
                 client: "foo".to_string(),
                 serial: 1,
@@ -51,11 +51,13 @@ async fn append_updates_membership() -> Result<()> {
                 blank_ent(1, 0, 1),
                 Entry {
                     log_id: LogId::new(CommittedLeaderId::new(1, 0), 2),
+                    context: vec![],
                     payload: EntryPayload::Membership(Membership::new(vec![btreeset! {1,2}], None)),
                 },
                 blank_ent(1, 0, 3),
                 Entry {
                     log_id: LogId::new(CommittedLeaderId::new(1, 0), 4),
+                    context: vec![],
                     payload: EntryPayload::Membership(Membership::new(vec![btreeset! {1,2,3,4}], None)),
                 },
                 blank_ent(1, 0, 5),
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::RaftObserver;
+use openraft::Config;
+use openraft::LogId;
+use openraft_memstore::MemNodeId;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// An observed AppendEntries: `(leader_id, prev_log_id, num_entries, leader_commit)`.
+type Received = (
+    Option<MemNodeId>,
+    Option<LogId<MemNodeId>>,
+    u64,
+    Option<LogId<MemNodeId>>,
+);
+
+/// Records every observed AppendEntries.
+#[derive(Clone, Default)]
+struct Recorder {
+    received: Arc<Mutex<Vec<Received>>>,
+}
+
+impl RaftObserver<TypeConfig> for Recorder {
+    fn on_append_entries_received(
+        &self,
+        leader_id: Option<MemNodeId>,
+        prev_log_id: Option<LogId<MemNodeId>>,
+        num_entries: u64,
+        leader_commit: Option<LogId<MemNodeId>>,
+    ) {
+        self.received.lock().unwrap().push((leader_id, prev_log_id, num_entries, leader_commit));
+    }
+}
+
+/// A follower calls the installed observer for every accepted AppendEntries, including heartbeats.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn append_entries_observer() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let recorder = Recorder::default();
+
+    tracing::info!(log_index, "--- install observer on node-1");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.set_observer(recorder.clone()).await?;
+    }
+
+    tracing::info!(log_index, "--- write logs, the observer sees entries from node-0");
+    {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied logs").await?;
+
+        // The observer is called in a separate task.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let received = recorder.received.lock().unwrap().clone();
+        assert!(received.iter().all(|(leader_id, ..)| leader_id == &Some(0)));
+
+        let n_entries: u64 = received.iter().map(|(_, _, n, _)| n).sum();
+        assert_eq!(3, n_entries);
+    }
+
+    tracing::info!(log_index, "--- heartbeat is observed with zero entries");
+    {
+        recorder.received.lock().unwrap().clear();
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().heartbeat().await?;
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let received = recorder.received.lock().unwrap().clone();
+        assert!(!received.is_empty());
+        assert!(received.iter().all(|(_, _, n, _)| *n == 0));
+        assert_eq!(Some(log_index), received.last().unwrap().3.map(|x| x.index));
+    }
+
+    Ok(())
+}
+
+/// Panics on every observed AppendEntries.
+struct Panicker;
+
+impl RaftObserver<TypeConfig> for Panicker {
+    fn on_append_entries_received(
+        &self,
+        _leader_id: Option<MemNodeId>,
+        _prev_log_id: Option<LogId<MemNodeId>>,
+        _num_entries: u64,
+        _leader_commit: Option<LogId<MemNodeId>>,
+    ) {
+        panic!("observer panics");
+    }
+}
+
+/// A panicking observer does not stop the follower from replicating logs, and can be replaced.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn append_entries_observer_panic() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- install a panicking observer on node-1");
+    {
+        n1.set_observer(Panicker).await?;
+    }
+
+    tracing::info!(log_index, "--- node-1 keeps replicating logs");
+    {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied logs").await?;
+
+        let m = n1.metrics().borrow().clone();
+        assert!(m.running_state.is_ok(), "RaftCore is still running");
+    }
+
+    tracing::info!(log_index, "--- a replacing observer receives events");
+    {
+        let recorder = Recorder::default();
+        n1.set_observer(recorder.clone()).await?;
+
+        log_index += router.client_request_many(0, "foo", 2).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied logs").await?;
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let received = recorder.received.lock().unwrap().clone();
+        let n_entries: u64 = received.iter().map(|(_, _, n, _)| n).sum();
+        assert_eq!(2, n_entries);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
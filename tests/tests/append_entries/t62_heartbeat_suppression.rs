@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::alias::InstantOf;
+use openraft::AsyncRuntime;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::TokioRuntime;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With heartbeat suppression enabled, followers still hear from the leader often enough to keep
+/// the leadership, both when the cluster is idle and when it is busy.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn heartbeat_suppression() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_suppression: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let term = n0.metrics().borrow().current_term;
+
+    tracing::info!(log_index, "--- idle cluster, heartbeats are sent");
+    {
+        for _i in 0..3 {
+            let now = InstantOf::<TypeConfig>::now();
+            TokioRuntime::sleep(Duration::from_millis(500)).await;
+
+            for node_id in [1, 2] {
+                // leader lease is extended.
+                router.external_request(node_id, move |state| {
+                    assert!(state.vote_last_modified() > Some(now));
+                });
+            }
+        }
+    }
+
+    tracing::info!(log_index, "--- busy cluster, AppendEntries serve as heartbeats");
+    {
+        for _i in 0..10 {
+            log_index += router.client_request_many(0, "0", 10).await?;
+            TokioRuntime::sleep(Duration::from_millis(50)).await;
+        }
+
+        for node_id in [1, 2] {
+            router.wait(&node_id, timeout()).applied_index(Some(log_index), "followers applied").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- no election happened");
+    {
+        n0.wait(timeout()).state(ServerState::Leader, "node 0 is still leader").await?;
+        assert_eq!(term, n0.metrics().borrow().current_term);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
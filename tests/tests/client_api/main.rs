@@ -14,6 +14,19 @@ mod t13_begin_receiving_snapshot;
 mod t13_get_snapshot;
 mod t13_install_full_snapshot;
 mod t13_trigger_snapshot;
+mod t16_cluster_commit_view;
+mod t16_cluster_topology;
+mod t16_log_consistency_probe;
 mod t16_with_raft_state;
+mod t17_replay_committed;
+mod t18_follower_read;
+mod t18_follower_read_linearizable;
+mod t19_client_write_batch;
+mod t19_uncommitted_entries;
 mod t50_lagging_network_write;
 mod t51_write_when_leader_quit;
+mod t52_require_quorum_before_writes;
+mod t53_max_write_rate;
+mod t54_max_log_entries;
+mod t55_write_with_context;
+mod t56_reject_writes_during_membership_change;
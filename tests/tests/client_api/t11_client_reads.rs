@@ -4,11 +4,13 @@ use std::time::Duration;
 use anyerror::AnyError;
 use anyhow::Result;
 use maplit::btreeset;
+use openraft::error::CheckIsLeaderError;
 use openraft::error::NetworkError;
 use openraft::error::RPCError;
 use openraft::Config;
 use openraft::LogIdOptionExt;
 use openraft::RPCTypes;
+use openraft::ServerState;
 
 use crate::fixtures::init_default_ut_tracing;
 use crate::fixtures::RPCRequest;
@@ -181,6 +183,120 @@ async fn get_read_log_id() -> Result<()> {
     Ok(())
 }
 
+/// - A leader serves lease reads while a quorum acknowledges it with heartbeats.
+/// - Lease reads are rejected once the quorum contact is lost, while the node is still a leader.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn client_read_lease() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            heartbeat_interval: 50,
+            election_timeout_min: 300,
+            election_timeout_max: 400,
+            read_lease: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.network_send_delay(0);
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- lease read on leader succeeds, on follower fails");
+    {
+        let (read_log_id, applied) = n0.client_read_lease().await?;
+        assert_eq!(read_log_id.index(), Some(log_index));
+        assert_eq!(applied.index(), Some(log_index));
+
+        let n1 = router.get_raft_handle(&1)?;
+        let err = n1.client_read_lease().await.unwrap_err();
+        assert!(matches!(err.api_error(), Some(CheckIsLeaderError::ForwardToLeader(_))));
+    }
+
+    tracing::info!(log_index, "--- isolate node 1 and 2, lease read is rejected");
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let err = n0.client_read_lease().await.unwrap_err();
+        assert!(matches!(err.api_error(), Some(CheckIsLeaderError::QuorumNotEnough(_))));
+
+        let metrics = n0.metrics().borrow().clone();
+        assert_eq!(ServerState::Leader, metrics.state, "node 0 is still leader");
+    }
+
+    tracing::info!(log_index, "--- restore network, lease read succeeds again");
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        n0.client_read_lease().await?;
+    }
+
+    Ok(())
+}
+
+/// A new leader rejects lease reads with `LeaderNotReady` during `read_lease_warmup`.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn client_read_lease_warmup() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            heartbeat_interval: 50,
+            election_timeout_min: 300,
+            election_timeout_max: 400,
+            read_lease_warmup: 1_000,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- lease read is rejected during warm-up");
+    {
+        let err = n0.client_read_lease().await.unwrap_err();
+        match err.api_error() {
+            Some(CheckIsLeaderError::LeaderNotReady(e)) => {
+                assert!(
+                    e.committed >= e.noop_log_id,
+                    "blank log is committed, only the warm-up blocks it"
+                );
+                assert_eq!(Duration::from_millis(1_000), e.warmup);
+                assert!(e.elapsed < e.warmup);
+            }
+            _ => unreachable!("expect LeaderNotReady, got: {:?}", err),
+        }
+
+        // Reads confirmed with heartbeats are not affected.
+        n0.ensure_linearizable().await?;
+    }
+
+    tracing::info!(log_index, "--- lease read succeeds after warm-up");
+    {
+        tokio::time::sleep(Duration::from_millis(1_100)).await;
+
+        let (read_log_id, _applied) = n0.client_read_lease().await?;
+        assert_eq!(read_log_id.index(), Some(log_index));
+    }
+
+    Ok(())
+}
+
 fn timeout() -> Option<Duration> {
     Some(Duration::from_millis(200))
 }
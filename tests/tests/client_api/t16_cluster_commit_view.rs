@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft::NodeRole;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::cluster_commit_view()` returns the matched and estimated committed log id of every node
+/// on the leader, and is rejected on a follower.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn cluster_commit_view() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- every node has the committed log");
+    {
+        let view = n0.cluster_commit_view().await?;
+
+        assert_eq!(0, view.leader_id);
+        assert_eq!(Some(log_index), view.committed.map(|x| x.index));
+        assert_eq!(Some(log_index), view.last_log_id.map(|x| x.index));
+        assert_eq!(vec![0, 1, 2, 3], view.nodes.keys().copied().collect::<Vec<_>>());
+        assert_eq!(NodeRole::Learner, view.nodes[&3].role);
+
+        for n in view.nodes.values() {
+            assert_eq!(view.committed, n.matched);
+            assert_eq!(view.committed, n.committed);
+        }
+    }
+
+    tracing::info!(log_index, "--- an unreachable learner falls behind");
+    {
+        router.set_unreachable(3, true);
+
+        router.client_request_many(0, "foo", 5).await?;
+        log_index += 5;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "leader applied").await?;
+
+        let view = n0.cluster_commit_view().await?;
+        assert_eq!(Some(log_index), view.committed.map(|x| x.index));
+        assert_eq!(Some(log_index), view.nodes[&1].committed.map(|x| x.index));
+        assert_eq!(Some(log_index - 5), view.nodes[&3].matched.map(|x| x.index));
+        assert_eq!(Some(log_index - 5), view.nodes[&3].committed.map(|x| x.index));
+    }
+
+    tracing::info!(log_index, "--- a follower can not build the view");
+    {
+        let res = n1.cluster_commit_view().await;
+        match res {
+            Err(RaftError::APIError(e)) => assert_eq!(Some(0), e.leader_id),
+            other => panic!("expect ForwardToLeader, got: {:?}", other),
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::NodeRole;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::cluster_topology()` returns every node with its role, and the replication lag of every
+/// node on the leader.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn cluster_topology() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- every node with its role");
+    {
+        let topology = n0.cluster_topology().await?;
+
+        assert_eq!(0, topology.id);
+        assert_eq!(Some(0), topology.current_leader);
+        assert_eq!(vec![0, 1, 2, 3], topology.nodes.keys().copied().collect::<Vec<_>>());
+
+        for id in [0, 1, 2] {
+            assert_eq!(NodeRole::Voter, topology.nodes[&id].role);
+        }
+        assert_eq!(NodeRole::Learner, topology.nodes[&3].role);
+        assert!(topology.nodes.values().all(|n| n.replication.is_some()));
+    }
+
+    tracing::info!(log_index, "--- a follower does not know the replication state");
+    {
+        let topology = n1.cluster_topology().await?;
+
+        assert_eq!(1, topology.id);
+        assert_eq!(Some(0), topology.current_leader);
+        assert_eq!(4, topology.nodes.len());
+        assert!(topology.nodes.values().all(|n| n.replication.is_none()));
+    }
+
+    tracing::info!(log_index, "--- the lag of an unreachable learner grows");
+    {
+        router.set_unreachable(3, true);
+
+        router.client_request_many(0, "foo", 5).await?;
+        log_index += 5;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "leader applied").await?;
+
+        let topology = n0.cluster_topology().await?;
+        let r = topology.nodes[&3].replication.clone().unwrap();
+        assert_eq!(Some(log_index - 5), r.matched.map(|x| x.index));
+        assert_eq!(5, r.lag);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
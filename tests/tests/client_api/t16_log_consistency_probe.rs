@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Look up the log id at an index on different nodes with `Raft::log_consistency_probe()`.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn log_consistency_probe() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: u64::MAX,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "0", 5).await?;
+    for id in [0, 1] {
+        router.wait(&id, timeout()).applied_index(Some(log_index), "write logs").await?;
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- nodes agree on every log");
+    {
+        for index in 1..=log_index {
+            let a = n0.log_consistency_probe(index).await?;
+            let b = n1.log_consistency_probe(index).await?;
+            assert_eq!(Some(log_id(1, 0, index)), a);
+            assert_eq!(a, b);
+        }
+
+        assert_eq!(None, n0.log_consistency_probe(log_index + 1).await?);
+    }
+
+    tracing::info!(log_index, "--- purged logs are looked up in snapshot meta");
+    {
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purged").await?;
+
+        assert_eq!(
+            Some(log_id(1, 0, log_index)),
+            n0.log_consistency_probe(log_index).await?
+        );
+        assert_eq!(None, n0.log_consistency_probe(log_index - 1).await?);
+        assert_eq!(
+            Some(log_id(1, 0, log_index - 1)),
+            n1.log_consistency_probe(log_index - 1).await?
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
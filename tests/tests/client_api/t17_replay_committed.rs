@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use maplit::btreeset;
+use openraft::entry::RaftPayload;
+use openraft::error::RaftError;
+use openraft::error::ReplayError;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::EntryPayloadKind;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::replay_committed()` yields historical committed entries, then follows new commits,
+/// and reports purged logs.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn replay_committed() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            // Read the historical logs in several batches.
+            max_payload_entries: 3,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let init_log_index = log_index;
+
+    tracing::info!(log_index, "--- write some logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied").await?;
+    }
+
+    let n1 = router.get_raft_handle(&1)?;
+    let mut stream = std::pin::pin!(n1.replay_committed(1));
+
+    tracing::info!(log_index, "--- replay historical logs on follower node-1");
+    {
+        for index in 1..=log_index {
+            let entry = next(&mut stream).await?;
+            assert_eq!(index, entry.log_id.index);
+
+            let want = if index == 1 {
+                EntryPayloadKind::Blank
+            } else if index <= init_log_index {
+                EntryPayloadKind::Membership
+            } else {
+                EntryPayloadKind::Normal
+            };
+            assert_eq!(want, entry.payload_kind(), "payload kind of log {}", index);
+        }
+    }
+
+    tracing::info!(log_index, "--- follow new commits");
+    {
+        let res = tokio::time::timeout(Duration::from_millis(200), stream.next()).await;
+        assert!(res.is_err(), "no more committed log");
+
+        let from = log_index + 1;
+        log_index += router.client_request_many(0, "0", 5).await?;
+
+        for index in from..=log_index {
+            let entry = next(&mut stream).await?;
+            assert_eq!(index, entry.log_id.index);
+        }
+    }
+
+    tracing::info!(log_index, "--- replay purged logs returns LogPurged");
+    {
+        n1.trigger().snapshot().await?;
+        router.wait(&1, timeout()).purged(Some(log_id(1, 0, log_index)), "node-1 purged").await?;
+
+        let mut stream = std::pin::pin!(n1.replay_committed(1));
+        let res = stream.next().await.unwrap();
+        match res {
+            Err(RaftError::APIError(ReplayError::LogPurged(purged))) => {
+                assert_eq!(1, purged.index);
+                assert_eq!(log_id(1, 0, log_index), purged.last_purged_log_id);
+            }
+            _ => panic!("expect LogPurged, got: {:?}", res),
+        }
+        assert!(stream.next().await.is_none(), "stream ends after an error");
+    }
+
+    Ok(())
+}
+
+async fn next<S, T, E>(stream: &mut S) -> anyhow::Result<T>
+where
+    S: futures::Stream<Item = Result<T, E>> + Unpin,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let res = tokio::time::timeout(Duration::from_millis(1_000), stream.next()).await?;
+    Ok(res.expect("stream should not end")?)
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A follower serves a bounded-staleness read if it has recently applied every log the leader
+/// reported as committed, and rejects it with a forward-to-leader error otherwise.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn follower_read() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- write logs, node 1 catches up with the leader commit");
+    {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+
+        n0.trigger().heartbeat().await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node 1 applied logs").await?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let applied = n1.follower_read(Duration::from_millis(1_000)).await?;
+        assert_eq!(Some(log_index), applied.map(|x| x.index));
+    }
+
+    tracing::info!(log_index, "--- no AppendEntries for a while, node 1 is stale");
+    {
+        tokio::time::sleep(Duration::from_millis(1_500)).await;
+
+        let err = n1.follower_read(Duration::from_millis(1_000)).await.unwrap_err();
+        let forward = err.api_error().unwrap();
+        assert_eq!(Some(0), forward.leader_id);
+
+        let applied = n1.follower_read(Duration::from_millis(10_000)).await?;
+        assert_eq!(Some(log_index), applied.map(|x| x.index));
+    }
+
+    tracing::info!(log_index, "--- a heartbeat refreshes node 1");
+    {
+        n0.trigger().heartbeat().await?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let applied = n1.follower_read(Duration::from_millis(1_000)).await?;
+        assert_eq!(Some(log_index), applied.map(|x| x.index));
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the leader is fresh as of the last quorum acknowledgement"
+    );
+    {
+        let applied = n0.follower_read(Duration::from_millis(1_000)).await?;
+        assert_eq!(Some(log_index), applied.map(|x| x.index));
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
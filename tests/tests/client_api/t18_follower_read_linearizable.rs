@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyerror::AnyError;
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::FollowerReadError;
+use openraft::error::NetworkError;
+use openraft::error::RPCError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft::RPCTypes;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A follower asks the leader for the read log id with a ReadIndex RPC, and waits for its local
+/// state machine to apply up to it.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn follower_read_linearizable() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- read on a follower waits for the leader's read log id");
+    {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+
+        let read_log_id = n1.follower_read_linearizable(timeout()).await?;
+        assert_eq!(Some(log_index), read_log_id.map(|x| x.index));
+        assert_eq!(Some(1), router.get_rpc_count().get(&RPCTypes::ReadIndex).copied());
+
+        let applied = n1.metrics().borrow().last_applied;
+        assert!(applied >= read_log_id, "node 1 applied up to the read log id");
+    }
+
+    tracing::info!(log_index, "--- on the leader it does not send ReadIndex RPC");
+    {
+        let read_log_id = n0.follower_read_linearizable(timeout()).await?;
+        assert_eq!(Some(log_index), read_log_id.map(|x| x.index));
+        assert_eq!(Some(1), router.get_rpc_count().get(&RPCTypes::ReadIndex).copied());
+    }
+
+    tracing::info!(log_index, "--- logs are not replicated to the follower, read times out");
+    {
+        router.set_rpc_pre_hook(RPCTypes::AppendEntries, |_router, _req, _id, target| {
+            if target == 1 {
+                let any_err = AnyError::error("block append-entries to node 1");
+                Err(RPCError::Network(NetworkError::new(&any_err)))
+            } else {
+                Ok(())
+            }
+        });
+
+        log_index += router.client_request_many(0, "foo", 1).await?;
+
+        let err = n1.follower_read_linearizable(timeout()).await.unwrap_err();
+        match err {
+            RaftError::APIError(FollowerReadError::Timeout(e)) => {
+                assert_eq!(Some(log_index), e.read_log_id.map(|x| x.index));
+                assert_eq!(Some(log_index - 1), e.applied.map(|x| x.index));
+            }
+            _ => unreachable!("expect Timeout, got: {:?}", err),
+        }
+
+        router.rpc_pre_hook(RPCTypes::AppendEntries, None);
+    }
+
+    tracing::info!(log_index, "--- leader is unreachable, read fails");
+    {
+        router.set_unreachable(0, true);
+
+        let err = n1.follower_read_linearizable(timeout()).await.unwrap_err();
+        assert!(
+            matches!(err, RaftError::APIError(FollowerReadError::RPCError(_))),
+            "got: {:?}",
+            err
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Duration {
+    Duration::from_millis(500)
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A batch of entries is appended contiguously and every entry gets its own response.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn client_write_batch() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write a batch of 3 entries");
+    {
+        let batch = (0..3).map(|i| ClientRequest::make_request("cli", i)).collect::<Vec<_>>();
+        let resps = n0.client_write_batch(batch).await?;
+
+        assert_eq!(3, resps.len());
+        for (i, resp) in resps.iter().enumerate() {
+            assert_eq!(log_index + 1 + i as u64, resp.log_id.index);
+        }
+        log_index += 3;
+    }
+
+    tracing::info!(log_index, "--- all entries are applied on every node");
+    {
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "the whole batch is applied").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- an empty batch appends nothing");
+    {
+        let resps = n0.client_write_batch(vec![]).await?;
+        assert!(resps.is_empty());
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(Some(log_index), m.last_log_index);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
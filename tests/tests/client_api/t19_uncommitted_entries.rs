@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::UncommittedEntriesError;
+use openraft::Config;
+use openraft::EntryPayloadKind;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// The leader reports the entries that are appended but not yet committed.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn uncommitted_entries() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- all entries are committed");
+    {
+        let entries = n0.uncommitted_entries().await?;
+        assert!(entries.is_empty());
+    }
+
+    tracing::info!(log_index, "--- block replication, a write is not committed");
+    {
+        router.set_unreachable(1, true);
+
+        let n = n0.clone();
+        tokio::spawn(async move {
+            let res = n.client_write(ClientRequest::make_request("cli", 1)).await;
+            tracing::info!("do not expect res: {:?}", res);
+        });
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.last_log_index == Some(log_index + 1),
+                "the write is appended on the leader",
+            )
+            .await?;
+
+        let entries = n0.uncommitted_entries().await?;
+        assert_eq!(1, entries.len());
+        assert_eq!(log_index + 1, entries[0].log_id.index);
+        assert_eq!(EntryPayloadKind::Normal, entries[0].payload_kind);
+    }
+
+    tracing::info!(log_index, "--- a follower forwards to the leader");
+    {
+        let err = n1.uncommitted_entries().await.unwrap_err();
+        match err.api_error().unwrap() {
+            UncommittedEntriesError::ForwardToLeader(forward) => {
+                assert_eq!(Some(0), forward.leader_id);
+            }
+            e => unreachable!("expect ForwardToLeader, got: {:?}", e),
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
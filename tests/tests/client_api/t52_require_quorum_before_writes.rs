@@ -0,0 +1,97 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyerror::AnyError;
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::NetworkError;
+use openraft::error::RPCError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `require_quorum_before_writes` enabled, a newly elected leader rejects writes with
+/// `NotReady` until a quorum responds to its replication, while it still accepts membership
+/// changes.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn require_quorum_before_writes() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            require_quorum_before_writes: true,
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {2}).await?;
+
+    tracing::info!(log_index, "--- block append-entries to node 0");
+    router.set_rpc_pre_hook(RPCTypes::AppendEntries, |_router, _req, _id, target| {
+        if target == 0 {
+            let any_err = AnyError::error("block append-entries to node 0");
+            Err(RPCError::Network(NetworkError::new(&any_err)))
+        } else {
+            Ok(())
+        }
+    });
+
+    tracing::info!(
+        log_index,
+        "--- node 1 becomes leader, but no quorum responds to its replication"
+    );
+    let n1 = router.get_raft_handle(&1)?;
+    {
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).current_leader(1, "node 1 becomes leader").await?;
+
+        let res = n1.client_write(ClientRequest::make_request("foo", 1)).await;
+        let err = res.unwrap_err();
+        match err {
+            RaftError::APIError(ClientWriteError::NotReady(not_ready)) => {
+                assert_eq!(1, not_ready.vote.leader_id().voted_for().unwrap());
+            }
+            _ => panic!("expect NotReady, got: {:?}", err),
+        }
+    }
+
+    tracing::info!(log_index, "--- membership change is accepted before quorum contact");
+    let change = {
+        let n1 = n1.clone();
+        let change = tokio::spawn(async move { n1.change_membership([0, 1, 2], false).await });
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!change.is_finished(), "membership change waits to commit, not rejected");
+        change
+    };
+
+    tracing::info!(log_index, "--- unblock append-entries, writes are accepted");
+    {
+        router.rpc_pre_hook(RPCTypes::AppendEntries, None);
+
+        n1.trigger().heartbeat().await?;
+
+        // The blank log of the new leader, the joint and the uniform membership logs.
+        change.await??;
+        log_index += 3;
+        n1.wait(timeout()).applied_index(Some(log_index), "membership change committed").await?;
+
+        log_index += router.client_request_many(1, "foo", 1).await?;
+        n1.wait(timeout()).applied_index(Some(log_index), "write committed").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `max_write_rate` set, a leader rejects writes exceeding the rate with `RateLimited`, and
+/// accepts writes again once the rate drops.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn max_write_rate() -> Result<()> {
+    let max_rate = 5;
+
+    let config = Arc::new(
+        Config {
+            max_write_rate: max_rate,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a burst of up to `max_write_rate` writes is accepted");
+    {
+        for i in 0..max_rate {
+            n0.client_write(ClientRequest::make_request("foo", i)).await?;
+            log_index += 1;
+        }
+    }
+
+    tracing::info!(log_index, "--- more writes are rejected");
+    {
+        let res = n0.client_write(ClientRequest::make_request("foo", max_rate)).await;
+        let err = res.unwrap_err();
+        match err {
+            RaftError::APIError(ClientWriteError::RateLimited(rate_limited)) => {
+                assert_eq!(max_rate, rate_limited.max_rate);
+            }
+            _ => panic!("expect RateLimited, got: {:?}", err),
+        }
+
+        n0.wait(timeout()).applied_index(Some(log_index), "rejected write is not appended").await?;
+    }
+
+    tracing::info!(log_index, "--- writes are accepted after the bucket is refilled");
+    {
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+
+        n0.client_write(ClientRequest::make_request("foo", max_rate)).await?;
+        log_index += 1;
+        n0.wait(timeout()).applied_index(Some(log_index), "write committed").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `max_log_entries` set, a leader rejects writes with `LogFull` when the log is full, and
+/// triggers a snapshot to purge the log so that writes are accepted again.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn max_log_entries() -> Result<()> {
+    let max_entries = 10;
+
+    let config = Arc::new(
+        Config {
+            max_log_entries: max_entries,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- fill up the log");
+    {
+        // Log index starts from 0.
+        while log_index + 1 < max_entries {
+            n0.client_write(ClientRequest::make_request("foo", log_index)).await?;
+            log_index += 1;
+        }
+    }
+
+    tracing::info!(log_index, "--- a write is rejected when the log is full");
+    {
+        let err = n0.client_write(ClientRequest::make_request("foo", log_index)).await.unwrap_err();
+        match err {
+            RaftError::APIError(ClientWriteError::LogFull(log_full)) => {
+                assert_eq!(max_entries, log_full.entries);
+                assert_eq!(max_entries, log_full.max);
+            }
+            _ => panic!("expect LogFull, got: {:?}", err),
+        }
+    }
+
+    tracing::info!(log_index, "--- the rejection triggers a snapshot and the log is purged");
+    {
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "snapshot is built").await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "log is purged").await?;
+    }
+
+    tracing::info!(log_index, "--- writes are accepted again");
+    {
+        n0.client_write(ClientRequest::make_request("foo", log_index)).await?;
+        log_index += 1;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "write is applied").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::entry::RaftEntry;
+use openraft::storage::RaftLogReaderExt;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// The context attached with `client_write_with_context()` is replicated to every node along with
+/// the entry.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn write_with_context() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write with and without context");
+    {
+        let resp = n0.client_write_with_context(ClientRequest::make_request("cli", 1), b"trace-1".to_vec()).await?;
+        log_index += 1;
+        assert_eq!(log_id(1, 0, log_index), resp.log_id);
+
+        n0.client_write(ClientRequest::make_request("cli", 2)).await?;
+        log_index += 1;
+    }
+
+    tracing::info!(log_index, "--- every node has the context");
+    {
+        for id in [0, 1, 2, 3] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "all applied").await?;
+
+            let (mut sto, _sm) = router.get_storage_handle(&id)?;
+            let entries = sto.get_log_entries(log_index - 1..=log_index).await?;
+
+            assert_eq!(b"trace-1", entries[0].context(), "node-{} has context", id);
+            assert!(entries[1].context().is_empty(), "node-{} has no context", id);
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `reject_writes_during_membership_change` set, a leader rejects writes with
+/// `MembershipChanging` until the membership change is committed.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn reject_writes_during_membership_change() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            reject_writes_during_membership_change: true,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- writes are accepted without a membership change");
+    {
+        n0.client_write(ClientRequest::make_request("foo", 1)).await?;
+        log_index += 1;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the joint config can not be committed without node-1 and node-2"
+    );
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let n = n0.clone();
+        let _h = tokio::spawn(async move { n.change_membership([0, 1, 2, 3], false).await });
+        log_index += 1;
+
+        n0.wait(timeout())
+            .metrics(|m| m.last_log_index == Some(log_index), "joint config is appended")
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- writes are rejected during the membership change");
+    {
+        let err = n0.client_write(ClientRequest::make_request("foo", 2)).await.unwrap_err();
+        match err {
+            RaftError::APIError(ClientWriteError::MembershipChanging(changing)) => {
+                assert_eq!(Some(log_index), changing.membership_log_id.map(|x| x.index));
+            }
+            _ => panic!("expect MembershipChanging, got: {:?}", err),
+        }
+    }
+
+    tracing::info!(
+        log_index,
+        "--- writes are accepted after the membership change is committed"
+    );
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+        log_index += 1;
+
+        n0.wait(Some(Duration::from_millis(5_000)))
+            .applied_index(Some(log_index), "uniform config is committed")
+            .await?;
+
+        n0.client_write(ClientRequest::make_request("foo", 3)).await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -9,3 +9,11 @@ mod fixtures;
 
 mod t10_elect_compare_last_log;
 mod t11_elect_seize_leadership;
+mod t12_elect_with_hanging_voter;
+mod t13_leader_affinity;
+mod t14_step_down;
+mod t15_quiesce_follower;
+mod t16_yield_leadership_for;
+mod t20_state_transitions;
+mod t21_term_changes;
+mod t30_elect_with_faulty_network;
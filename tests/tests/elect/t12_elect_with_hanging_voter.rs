@@ -0,0 +1,59 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A candidate becomes leader as soon as a quorum granted its vote, without waiting for a voter
+/// that never responds.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn elect_with_hanging_voter() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            election_timeout_min: 3_000,
+            election_timeout_max: 3_001,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- wait for the leader lease to expire");
+    {
+        tokio::time::sleep(Duration::from_millis(config.election_timeout_max + 100)).await;
+    }
+
+    tracing::info!(log_index, "--- vote request to node 2 never returns");
+    {
+        router.set_rpc_hang(2, true);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- node 1 becomes leader before the vote to node 2 times out"
+    );
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.trigger().elect().await?;
+
+        n1.wait(timeout()).state(ServerState::Leader, "node 1 becomes leader").await?;
+    }
+
+    Ok(())
+}
+
+/// Shorter than `election_timeout_min`, which is the deadline of a single vote RPC.
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
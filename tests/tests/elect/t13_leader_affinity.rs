@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A node that just stepped down from leader starts an election earlier than other nodes, within
+/// `leader_affinity_window`, and takes back the leadership.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn leader_affinity() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            election_timeout_min: 1_000,
+            election_timeout_max: 2_000,
+            leader_affinity_window: 60_000,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    n0.wait(timeout()).state(ServerState::Leader, "node 0 becomes leader").await?;
+
+    tracing::info!(log_index, "--- node 1 seizes leadership, node 0 steps down");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).state(ServerState::Leader, "node 1 becomes leader").await?;
+        n0.wait(timeout()).state(ServerState::Follower, "node 0 steps down").await?;
+    }
+
+    tracing::info!(log_index, "--- isolate node 1, node 0 takes back the leadership");
+    {
+        router.set_unreachable(1, true);
+
+        for id in [0, 2] {
+            router.get_raft_handle(&id)?.runtime_config().elect(true);
+        }
+
+        n0.wait(timeout()).state(ServerState::Leader, "node 0 becomes leader again").await?;
+
+        let n2 = router.get_raft_handle(&2)?;
+        n2.wait(timeout()).current_leader(0, "node 2 follows node 0").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(10_000))
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A leader gives up its leadership with `Raft::step_down()`, keeps running as a follower, and
+/// follows the leader elected by a normal election.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn step_down() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- a follower can not step down");
+    {
+        let err = n1.step_down().await.unwrap_err();
+        let forward = err.api_error().unwrap();
+        assert_eq!(Some(0), forward.leader_id);
+    }
+
+    tracing::info!(log_index, "--- node 0 steps down and becomes a follower");
+    {
+        n0.step_down().await?;
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.state == ServerState::Follower && m.current_leader.is_none(),
+                "node 0 becomes a follower without leader",
+            )
+            .await?;
+
+        let err = n0.step_down().await.unwrap_err();
+        assert!(err.api_error().is_some(), "node 0 is no longer a leader");
+    }
+
+    tracing::info!(log_index, "--- node 1 is elected, node 0 follows it");
+    {
+        n1.runtime_config().elect(true);
+
+        n1.wait(timeout()).state(ServerState::Leader, "node 1 becomes leader").await?;
+        n0.wait(timeout()).current_leader(1, "node 0 follows node 1").await?;
+        n0.wait(timeout()).state(ServerState::Follower, "node 0 is still a follower").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(10_000))
+}
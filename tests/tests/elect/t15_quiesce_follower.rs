@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A follower with election disabled by `runtime_config().elect(false)` keeps replicating and
+/// voting, but never campaigns, until election is enabled again.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn quiesce_follower() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let n2 = router.get_raft_handle(&2)?;
+    n2.runtime_config().elect(true);
+
+    tracing::info!(log_index, "--- node-1 is quiesced, it still replicates logs");
+    {
+        n1.runtime_config().elect(false);
+
+        log_index += router.client_request_many(0, "foo", 3).await?;
+        n1.wait(timeout()).applied_index(Some(log_index), "node-1 replicates logs").await?;
+    }
+
+    tracing::info!(log_index, "--- the leader is gone, only node-2 campaigns");
+    {
+        router.set_network_error(0, true);
+
+        n2.wait(Some(Duration::from_millis(3_000)))
+            .state(ServerState::Leader, "node-2 becomes leader with node-1's vote")
+            .await?;
+
+        n1.wait(timeout()).current_leader(2, "node-1 follows node-2").await?;
+        n1.wait(timeout()).state(ServerState::Follower, "node-1 is still a follower").await?;
+    }
+
+    tracing::info!(log_index, "--- node-1 campaigns after it is unquiesced");
+    {
+        n2.runtime_config().elect(false);
+        router.set_network_error(2, true);
+        n1.runtime_config().elect(true);
+
+        n1.wait(Some(Duration::from_millis(3_000)))
+            .metrics(|m| m.state != ServerState::Follower, "node-1 starts an election")
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A leader that yields the leadership with `Raft::yield_leadership_for()` is not elected again
+/// during the given duration, even if the next leader yields as well.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn yield_leadership_for() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 200,
+            election_timeout_max: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a follower can not yield leadership");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let err = n1.yield_leadership_for(Duration::from_millis(5_000)).await.unwrap_err();
+        assert!(err.api_error().is_some());
+    }
+
+    tracing::info!(log_index, "--- node 0 yields, another node is elected");
+    let leader = {
+        n0.yield_leadership_for(Duration::from_millis(5_000)).await?;
+
+        let m = n0
+            .wait(timeout())
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(0),
+                "another node becomes leader",
+            )
+            .await?;
+        m.current_leader.unwrap()
+    };
+
+    tracing::info!(
+        log_index,
+        "--- the new leader yields too, only the third node can be elected"
+    );
+    {
+        router.get_raft_handle(&leader)?.yield_leadership_for(Duration::from_millis(5_000)).await?;
+
+        let m = n0
+            .wait(timeout())
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(leader),
+                "a leader is elected again",
+            )
+            .await?;
+        assert_ne!(Some(0), m.current_leader, "node 0 is not elected");
+        assert_eq!(ServerState::Follower, m.state);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
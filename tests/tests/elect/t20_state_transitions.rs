@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::StreamExt;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Every server state transition is delivered by `Raft::state_transitions()`,
+/// including the short-lived candidate state.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn state_transitions() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_raft_node(0).await;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let mut transitions = n0.state_transitions();
+
+    tracing::info!("--- initialize a single node cluster, it becomes leader at once");
+    {
+        router.initialize(0).await?;
+        router.wait(&0, timeout()).state(ServerState::Leader, "node-0 is leader").await?;
+
+        let mut got = vec![];
+        for _ in 0..3 {
+            let t = tokio::time::timeout(Duration::from_millis(1_000), transitions.next()).await?;
+            got.push(t.unwrap());
+        }
+
+        assert_eq!(
+            vec![
+                (ServerState::Learner, ServerState::Follower),
+                (ServerState::Follower, ServerState::Candidate),
+                (ServerState::Candidate, ServerState::Leader),
+            ],
+            got
+        );
+    }
+
+    tracing::info!("--- the stream ends when raft shuts down");
+    {
+        n0.shutdown().await?;
+        let t = tokio::time::timeout(Duration::from_millis(1_000), transitions.next()).await?;
+        assert!(t.is_none());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
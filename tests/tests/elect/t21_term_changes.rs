@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::StreamExt;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Every new term persisted by a node is delivered by `Raft::term_changes()`, in order.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn term_changes() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+    let mut terms = n1.term_changes();
+
+    tracing::info!(log_index, "--- node-1 elects itself, its new term is delivered");
+    {
+        // Wait for the leader lease to expire, so that the vote request is granted.
+        tokio::time::sleep(Duration::from_millis(config.election_timeout_max + 100)).await;
+
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).current_leader(1, "node-1 becomes leader").await?;
+
+        let t = tokio::time::timeout(Duration::from_millis(1_000), terms.next()).await?;
+        assert_eq!(Some(2), t);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- node-0 elects itself, node-1 grants and persists the new term"
+    );
+    {
+        tokio::time::sleep(Duration::from_millis(config.election_timeout_max + 100)).await;
+
+        n0.trigger().elect().await?;
+        n1.wait(timeout()).current_leader(0, "node-0 becomes leader").await?;
+
+        let t = tokio::time::timeout(Duration::from_millis(1_000), terms.next()).await?;
+        assert_eq!(Some(3), t);
+    }
+
+    tracing::info!(log_index, "--- the stream ends when raft shuts down");
+    {
+        n1.shutdown().await?;
+        let t = tokio::time::timeout(Duration::from_millis(1_000), terms.next()).await?;
+        assert!(t.is_none());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::FaultyNetwork;
+use openraft::Config;
+use openraft::Raft;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+
+/// With `testing::FaultyNetwork`, isolating the leader lets the others elect a new leader, and the
+/// old leader follows it after the partition heals.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn elect_with_faulty_network() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+
+    let net = FaultyNetwork::<TypeConfig>::new(0);
+    net.set_default_latency(Duration::from_millis(1));
+    net.set_jitter(Duration::from_millis(5));
+
+    for id in [0, 1, 2] {
+        let (log_store, sm) = openraft_memstore::new_mem_store();
+        let raft = Raft::new(id, config.clone(), net.factory(id), log_store, sm).await?;
+        net.add_node(id, raft);
+    }
+
+    let n0 = net.get_node(&0).unwrap();
+    let n1 = net.get_node(&1).unwrap();
+
+    tracing::info!("--- initialize cluster, node-0 becomes leader");
+    {
+        n0.initialize(btreeset! {0,1,2}).await?;
+        n1.wait(timeout()).current_leader(0, "node-1 follows node-0").await?;
+    }
+
+    tracing::info!("--- isolate node-0, a new leader is elected");
+    let leader = {
+        net.set_isolated(0, true);
+
+        let m = n1
+            .wait(timeout())
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(0),
+                "node-1 sees a new leader",
+            )
+            .await?;
+        m.current_leader.unwrap()
+    };
+
+    tracing::info!("--- heal the partition, node-0 follows the new leader");
+    {
+        net.heal();
+        n0.wait(timeout()).current_leader(leader, "node-0 follows the new leader").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
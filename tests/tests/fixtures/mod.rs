@@ -25,6 +25,7 @@ use openraft::error::ClientWriteError;
 use openraft::error::Fatal;
 use openraft::error::Infallible;
 use openraft::error::InstallSnapshotError;
+use openraft::error::Malformed;
 use openraft::error::NetworkError;
 use openraft::error::PayloadTooLarge;
 use openraft::error::RPCError;
@@ -40,6 +41,8 @@ use openraft::raft::AppendEntriesResponse;
 use openraft::raft::ClientWriteResponse;
 use openraft::raft::InstallSnapshotRequest;
 use openraft::raft::InstallSnapshotResponse;
+use openraft::raft::ReadIndexRequest;
+use openraft::raft::ReadIndexResponse;
 use openraft::raft::VoteRequest;
 use openraft::raft::VoteResponse;
 use openraft::storage::RaftLogStorage;
@@ -164,6 +167,8 @@ pub enum RPCErrorType {
     Unreachable,
     /// Returns [`NetworkError`](`openraft::error::NetworkError`).
     NetworkError,
+    /// Returns [`Malformed`](`openraft::error::Malformed`).
+    Malformed,
     /// Returns [`PayloadTooLarge`](`openraft::error::PayloadTooLarge`).
     PayloadTooLarge { action: RPCTypes, entries_hint: u64 },
 }
@@ -179,6 +184,7 @@ impl RPCErrorType {
         match self {
             RPCErrorType::Unreachable => Unreachable::new(&AnyError::error(msg)).into(),
             RPCErrorType::NetworkError => NetworkError::new(&AnyError::error(msg)).into(),
+            RPCErrorType::Malformed => Malformed::new(&AnyError::error(msg)).into(),
             RPCErrorType::PayloadTooLarge { action, entries_hint } => match action {
                 RPCTypes::Vote => {
                     unreachable!("Vote RPC should not be too large")
@@ -187,6 +193,9 @@ impl RPCErrorType {
                 RPCTypes::InstallSnapshot => {
                     unreachable!("InstallSnapshot RPC should not be too large")
                 }
+                RPCTypes::ReadIndex => {
+                    unreachable!("ReadIndex RPC should not be too large")
+                }
             },
         }
     }
@@ -245,6 +254,9 @@ pub struct TypedRaftRouter {
 
     /// A hook function to be called when before an RPC is sent to target node.
     rpc_pre_hook: Arc<Mutex<HashMap<RPCTypes, RPCPreHook>>>,
+
+    /// Nodes to which an RPC never returns, to emulate a peer that does not respond.
+    hang_rpc: Arc<Mutex<BTreeSet<MemNodeId>>>,
 }
 
 /// Default `RaftRouter` for memstore.
@@ -280,6 +292,7 @@ impl Builder {
             append_entries_quota: Arc::new(Mutex::new(None)),
             rpc_count: Default::default(),
             rpc_pre_hook: Default::default(),
+            hang_rpc: Default::default(),
         }
     }
 }
@@ -510,6 +523,28 @@ impl TypedRaftRouter {
         }
     }
 
+    /// Set to `true` to make an RPC sent to a node never return.
+    ///
+    /// Unlike [`Self::set_unreachable`], the caller does not receive an error and has to rely on
+    /// its own timeout.
+    pub fn set_rpc_hang(&self, id: MemNodeId, hang: bool) {
+        let mut hang_rpc = self.hang_rpc.lock().unwrap();
+        if hang {
+            hang_rpc.insert(id);
+        } else {
+            hang_rpc.remove(&id);
+        }
+    }
+
+    /// Block forever if RPC to `target` is set to hang.
+    async fn hang_if_set(&self, target: MemNodeId) {
+        let hang = self.hang_rpc.lock().unwrap().contains(&target);
+        if hang {
+            tracing::debug!("RPC to {} hangs", target);
+            futures::future::pending::<()>().await;
+        }
+    }
+
     /// Set a hook function to be called when before an RPC is sent to target node.
     pub fn set_rpc_pre_hook<F>(&self, rpc_type: RPCTypes, hook: F)
     where F: Fn(&TypedRaftRouter, RPCRequest<TypeConfig>, MemNodeId, MemNodeId) -> PreHookResult + Send + 'static {
@@ -552,6 +587,7 @@ impl TypedRaftRouter {
                         RPCError::Unreachable(e) => e.into(),
                         RPCError::PayloadTooLarge(e) => e.into(),
                         RPCError::Network(e) => e.into(),
+                        RPCError::Malformed(e) => e.into(),
                         RPCError::RemoteError(e) => {
                             unreachable!("unexpected RemoteError: {:?}", e);
                         }
@@ -989,6 +1025,7 @@ impl RaftNetwork<MemConfig> for RaftRouterNetwork {
         self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
         self.owner.emit_rpc_error(from_id, self.target)?;
         self.owner.rand_send_delay().await;
+        self.owner.hang_if_set(self.target).await;
 
         // decrease quota if quota is set
         let truncated = {
@@ -1053,6 +1090,7 @@ impl RaftNetwork<MemConfig> for RaftRouterNetwork {
         self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
         self.owner.emit_rpc_error(from_id, self.target)?;
         self.owner.rand_send_delay().await;
+        self.owner.hang_if_set(self.target).await;
 
         let node = self.owner.get_raft_handle(&self.target)?;
 
@@ -1074,6 +1112,7 @@ impl RaftNetwork<MemConfig> for RaftRouterNetwork {
         self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
         self.owner.emit_rpc_error(from_id, self.target)?;
         self.owner.rand_send_delay().await;
+        self.owner.hang_if_set(self.target).await;
 
         let node = self.owner.get_raft_handle(&self.target)?;
 
@@ -1082,6 +1121,26 @@ impl RaftNetwork<MemConfig> for RaftRouterNetwork {
 
         Ok(resp)
     }
+
+    /// Send a ReadIndex RPC to the target Raft node.
+    async fn read_index(
+        &mut self,
+        rpc: ReadIndexRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<ReadIndexResponse<MemConfig>, RPCError<MemConfig, RaftError<MemConfig, CheckIsLeaderError<MemConfig>>>>
+    {
+        self.owner.count_rpc(RPCTypes::ReadIndex);
+        self.owner.emit_rpc_error(rpc.from, self.target)?;
+        self.owner.rand_send_delay().await;
+        self.owner.hang_if_set(self.target).await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.read_index(rpc).await;
+        let resp = resp.map_err(|e| RemoteError::new(self.target, e))?;
+
+        Ok(resp)
+    }
 }
 
 pub enum ValueTest<T> {
@@ -9,6 +9,10 @@ mod fixtures;
 
 mod t10_initialization;
 mod t11_shutdown;
+mod t12_drain_and_shutdown;
+mod t20_storage_degraded;
+mod t21_apply_error_observer;
+mod t22_commit_non_durable;
 mod t50_follower_restart_does_not_interrupt;
 mod t50_single_follower_restart;
 mod t50_single_leader_restart_re_apply_logs;
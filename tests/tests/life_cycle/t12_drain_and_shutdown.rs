@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::Config;
+use openraft::ServerState;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `drain_and_shutdown()` rejects new writes, waits for accepted writes to finish, steps down and
+/// shuts down the leader.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn drain_and_shutdown() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- block replication, a write waits for commit");
+    let pending_write = {
+        router.set_unreachable(1, true);
+        router.set_unreachable(2, true);
+
+        let n = n0.clone();
+        let h = tokio::spawn(async move { n.client_write(ClientRequest::make_request("cli", 1)).await });
+
+        n0.wait(timeout()).metrics(|m| m.pending_client_writes == 1, "1 pending write").await?;
+        h
+    };
+
+    tracing::info!(log_index, "--- start draining, new writes are rejected");
+    let drain = {
+        let n = n0.clone();
+        let drain = tokio::spawn(async move { n.drain_and_shutdown(Duration::from_millis(3_000)).await });
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let res = n0.client_write(ClientRequest::make_request("cli", 2)).await;
+        let err = res.unwrap_err();
+        assert!(
+            matches!(err, RaftError::APIError(ClientWriteError::NotReady(_))),
+            "got: {:?}",
+            err
+        );
+        drain
+    };
+
+    tracing::info!(log_index, "--- restore replication, the accepted write finishes");
+    {
+        router.set_unreachable(1, false);
+        router.set_unreachable(2, false);
+        n0.trigger().heartbeat().await?;
+
+        let resp = pending_write.await??;
+        assert_eq!(log_index + 1, resp.log_id.index);
+
+        drain.await??;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(ServerState::Shutdown, m.state);
+    }
+
+    Ok(())
+}
+
+/// When accepted writes do not finish in time, `drain_and_shutdown()` returns a timeout error and
+/// still shuts down.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn drain_and_shutdown_timeout() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- block replication, a write waits for commit");
+    {
+        router.set_unreachable(1, true);
+        router.set_unreachable(2, true);
+
+        let n = n0.clone();
+        tokio::spawn(async move { n.client_write(ClientRequest::make_request("cli", 1)).await });
+
+        n0.wait(timeout()).metrics(|m| m.pending_client_writes == 1, "1 pending write").await?;
+    }
+
+    tracing::info!(log_index, "--- drain times out");
+    {
+        let res = n0.drain_and_shutdown(Duration::from_millis(300)).await;
+        assert!(
+            matches!(res, Err(openraft::metrics::WaitError::Timeout(_, _))),
+            "got: {:?}",
+            res
+        );
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(ServerState::Shutdown, m.state);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
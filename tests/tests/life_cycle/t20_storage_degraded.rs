@@ -0,0 +1,141 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::error::Fatal;
+use openraft::error::RaftError;
+use openraft::raft::AppendEntriesRequest;
+use openraft::raft::VoteRequest;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::Vote;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::MemStateMachine;
+use crate::fixtures::RaftRouter;
+
+/// With `degrade_on_storage_error`, a leader whose log store fails to write steps down and keeps
+/// serving reads in degraded mode, instead of shutting down.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn storage_degraded() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            degrade_on_storage_error: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 3).await?;
+    router.wait(&0, timeout()).applied_index(Some(log_index), "logs applied").await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- fail appending logs on the leader");
+    {
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        sm0.block.set_blocking(BlockOperation::FailAppend, Duration::from_secs(0));
+
+        let err = n0.client_write(ClientRequest::make_request("foo", 100)).await.unwrap_err();
+        match err.api_error() {
+            Some(ClientWriteError::ForwardToLeader(_)) => {}
+            _ => unreachable!("expect ForwardToLeader, got: {:?}", err),
+        }
+    }
+
+    tracing::info!(log_index, "--- the leader steps down and enters degraded mode");
+    {
+        n0.wait(timeout())
+            .metrics(
+                |m| m.storage_degraded.is_some() && m.state != ServerState::Leader,
+                "n0 is degraded",
+            )
+            .await?;
+
+        let m = n0.metrics().borrow().clone();
+        assert!(m.running_state.is_ok(), "RaftCore is still running");
+    }
+
+    tracing::info!(log_index, "--- another node becomes the leader");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(Some(Duration::from_millis(5_000)))
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(0),
+                "a new leader is elected",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- the degraded node refuses writes but serves reads");
+    {
+        let err = n0.client_write(ClientRequest::make_request("foo", 101)).await.unwrap_err();
+        match err.api_error() {
+            Some(ClientWriteError::ForwardToLeader(_)) => {}
+            _ => unreachable!("expect ForwardToLeader, got: {:?}", err),
+        }
+
+        let last_applied = n0
+            .with_state_machine(|sm: &mut MemStateMachine| {
+                Box::pin(async move { sm.get_state_machine().await.last_applied_log })
+            })
+            .await??;
+        assert_eq!(Some(log_index), last_applied.map(|x| x.index));
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the degraded node fails replication and vote requests with the storage error"
+    );
+    {
+        let err = n0
+            .append_entries(AppendEntriesRequest {
+                vote: Vote::new_committed(10, 1),
+                prev_log_id: None,
+                entries: vec![],
+                leader_commit: None,
+            })
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, RaftError::Fatal(Fatal::StorageError(_))),
+            "expect StorageError, got: {:?}",
+            err
+        );
+
+        let err = n0.vote(VoteRequest::new(Vote::new(10, 1), None)).await.unwrap_err();
+        assert!(
+            matches!(err, RaftError::Fatal(Fatal::StorageError(_))),
+            "expect StorageError, got: {:?}",
+            err
+        );
+    }
+
+    tracing::info!(log_index, "--- the degraded node does not build snapshot or purge logs");
+    {
+        n0.trigger().snapshot().await?;
+        n0.trigger().purge_log(log_index).await?;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(None, m.snapshot);
+        assert_eq!(None, m.purged);
+        assert!(m.storage_degraded.is_some());
+        assert!(m.running_state.is_ok(), "RaftCore is still running");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::metrics::RaftEvent;
+use openraft::raft::RaftObserver;
+use openraft::Config;
+use openraft::ErrorSubject;
+use openraft::LogId;
+use openraft::StorageError;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::MemNodeId;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// Records every observed apply error.
+#[derive(Clone, Default)]
+struct Recorder {
+    errors: Arc<Mutex<Vec<(LogId<MemNodeId>, StorageError<MemNodeId>)>>>,
+}
+
+impl RaftObserver<TypeConfig> for Recorder {
+    fn on_apply_error(&self, log_id: &LogId<MemNodeId>, error: &StorageError<MemNodeId>) {
+        self.errors.lock().unwrap().push((*log_id, error.clone()));
+    }
+}
+
+/// When the state machine fails to apply an entry, the observer and the event subscribers are
+/// informed of the failing log id and the error, before the node shuts down.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn apply_error_observer() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 3).await?;
+    router.wait(&0, timeout()).applied_index(Some(log_index), "logs applied").await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    let recorder = Recorder::default();
+    n0.set_observer(recorder.clone()).await?;
+    let mut events = n0.events();
+
+    tracing::info!(log_index, "--- fail applying logs on node-0");
+    {
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        sm0.block.set_blocking(BlockOperation::FailApply, Duration::from_secs(0));
+
+        let res = n0.client_write(ClientRequest::make_request("foo", 100)).await;
+        assert!(res.is_err(), "write fails, got: {:?}", res);
+        log_index += 1;
+    }
+
+    tracing::info!(log_index, "--- the observer sees the failing log id and the error");
+    {
+        // The observer is called in a separate task.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let errors = recorder.errors.lock().unwrap().clone();
+        assert_eq!(1, errors.len(), "got: {:?}", errors);
+
+        let (log_id, error) = &errors[0];
+        assert_eq!(log_index, log_id.index);
+        assert_eq!(&ErrorSubject::Apply(*log_id), error.subject());
+    }
+
+    tracing::info!(log_index, "--- the error is delivered as an event");
+    {
+        let mut found = false;
+        while let Ok(ev) = events.try_recv() {
+            if let RaftEvent::ApplyFailed { log_id, .. } = ev {
+                assert_eq!(log_index, log_id.index);
+                found = true;
+            }
+        }
+        assert!(found, "ApplyFailed event is delivered");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::error::ClientWriteError;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::StorageError;
+use openraft::Violation;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::MemStateMachine;
+use crate::fixtures::RaftRouter;
+
+/// A leader whose log store never reports the flush of new logs does not apply them when the
+/// followers commit them: it reports the violation and steps down, without
+/// `degrade_on_storage_error` being enabled.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn commit_non_durable() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 3).await?;
+    router.wait(&0, timeout()).applied_index(Some(log_index), "logs applied").await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- the log store of the leader drops flush callbacks");
+    {
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        sm0.block.set_blocking(BlockOperation::DropLogFlush, Duration::from_secs(0));
+
+        let err = n0.client_write(ClientRequest::make_request("foo", 100)).await.unwrap_err();
+        match err.api_error() {
+            Some(ClientWriteError::ForwardToLeader(_)) => {}
+            _ => unreachable!("expect ForwardToLeader, got: {:?}", err),
+        }
+    }
+
+    tracing::info!(log_index, "--- the leader reports the violation and steps down");
+    {
+        n0.wait(timeout())
+            .metrics(
+                |m| m.storage_degraded.is_some() && m.state != ServerState::Leader,
+                "n0 steps down",
+            )
+            .await?;
+
+        let m = n0.metrics().borrow().clone();
+        assert!(m.running_state.is_ok(), "RaftCore is still running");
+
+        match m.storage_degraded {
+            Some(StorageError::Defensive { source }) => match source.violation {
+                Violation::ApplyNonDurable { durable, upto } => {
+                    assert_eq!(Some(log_index), durable.map(|x| x.index));
+                    assert_eq!(log_index + 1, upto.index);
+                }
+                v => unreachable!("expect ApplyNonDurable, got: {:?}", v),
+            },
+            e => unreachable!("expect Defensive error, got: {:?}", e),
+        }
+    }
+
+    tracing::info!(log_index, "--- nothing is applied past the durable log");
+    {
+        let last_applied = n0
+            .with_state_machine(|sm: &mut MemStateMachine| {
+                Box::pin(async move { sm.get_state_machine().await.last_applied_log })
+            })
+            .await??;
+        assert_eq!(Some(log_index), last_applied.map(|x| x.index));
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(Some(log_index), m.last_applied.map(|x| x.index));
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the log is durable on the followers and is applied by them"
+    );
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(Some(Duration::from_millis(5_000)))
+            .applied_index_at_least(Some(log_index + 1), "n1 applies the log")
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -8,3 +8,4 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_save_committed;
+mod t20_batched_log_flush;
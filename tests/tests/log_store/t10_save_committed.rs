@@ -41,6 +41,38 @@ async fn write_committed_log_id_to_log_store() -> Result<()> {
     Ok(())
 }
 
+/// With `Config::persist_commit_index` disabled, `committed` log id is not written to log store.
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn do_not_write_committed_log_id_if_disabled() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            persist_commit_index: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "0", 10).await?;
+
+    for i in [0, 1, 2] {
+        router.wait(&i, timeout()).applied_index(Some(log_index), "write logs").await?;
+    }
+
+    for id in [0, 1, 2] {
+        let (_, mut ls, _) = router.remove_node(id).unwrap();
+        let committed = ls.read_committed().await?;
+        assert_eq!(None, committed, "node-{} committed", id);
+    }
+
+    Ok(())
+}
+
 fn timeout() -> Option<Duration> {
     Some(Duration::from_millis(1000))
 }
@@ -0,0 +1,144 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::prelude::*;
+use maplit::btreeset;
+use openraft::raft::RaftObserver;
+use openraft::Config;
+use openraft::LogFlushMode;
+use openraft::LogId;
+use openraft_memstore::MemNodeId;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `LogFlushMode::Batched`, concurrent writes are committed and applied on every node, and a
+/// single write is not held back longer than `max_delay` when no more entries arrive.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn batched_log_flush() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            log_flush_mode: LogFlushMode::Batched {
+                max_delay: Duration::from_millis(10),
+                max_entries: 16,
+            },
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write concurrently, entries are flushed in batches");
+    {
+        let mut clients = futures::stream::FuturesUnordered::new();
+        for client_id in ["0", "1", "2", "3"] {
+            clients.push(router.client_request_many(0, client_id, 50));
+        }
+        while let Some(res) = clients.next().await {
+            res?;
+        }
+        log_index += 4 * 50;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "applied all writes").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- a single write is flushed after max_delay");
+    {
+        router.client_request(0, "single", 0).await?;
+        log_index += 1;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "applied single write").await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Records the number of entries of every non-empty AppendEntries.
+#[derive(Clone, Default)]
+struct Recorder {
+    num_entries: Arc<Mutex<Vec<u64>>>,
+}
+
+impl RaftObserver<TypeConfig> for Recorder {
+    fn on_append_entries_received(
+        &self,
+        _leader_id: Option<MemNodeId>,
+        _prev_log_id: Option<LogId<MemNodeId>>,
+        num_entries: u64,
+        _leader_commit: Option<LogId<MemNodeId>>,
+    ) {
+        if num_entries > 0 {
+            self.num_entries.lock().unwrap().push(num_entries);
+        }
+    }
+}
+
+/// A follower flushes once for every AppendEntries request. Under load, the leader sends more
+/// entries in every request, thus the follower flushes fewer times than the number of entries.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn batched_log_flush_follower() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            log_flush_mode: LogFlushMode::Batched {
+                max_delay: Duration::from_millis(10),
+                max_entries: 16,
+            },
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::builder(config.clone()).send_delay(10).build();
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let recorder = Recorder::default();
+    router.get_raft_handle(&1)?.set_observer(recorder.clone()).await?;
+
+    tracing::info!(
+        log_index,
+        "--- write concurrently, node-1 receives entries in fewer requests"
+    );
+    {
+        let mut clients = futures::stream::FuturesUnordered::new();
+        for client_id in ["0", "1", "2", "3", "4", "5", "6", "7"] {
+            clients.push(router.client_request_many(0, client_id, 25));
+        }
+        while let Some(res) = clients.next().await {
+            res?;
+        }
+        log_index += 8 * 25;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied all writes").await?;
+
+        // The observer is called in a separate task.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let num_entries = recorder.num_entries.lock().unwrap().clone();
+        assert_eq!(8 * 25, num_entries.iter().sum::<u64>());
+        assert!(
+            (num_entries.len() as u64) < 8 * 25,
+            "node-1 flushes {} times for {} entries",
+            num_entries.len(),
+            8 * 25
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -40,3 +40,50 @@ async fn raft_config() -> Result<()> {
 
     Ok(())
 }
+
+/// Get the config a node is running with via
+/// [`Raft::effective_config`](openraft::Raft::effective_config)
+#[async_entry::test(worker_threads = 4, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn effective_config() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            election_timeout_min: 123,
+            election_timeout_max: 124,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- without runtime changes");
+    {
+        let c = n0.effective_config();
+        assert!(c.enable_tick);
+        assert!(c.enable_heartbeat);
+        assert!(c.enable_elect);
+        assert_eq!(c.election_timeout_min, 123);
+    }
+
+    tracing::info!(log_index, "--- runtime changes are reflected");
+    {
+        n0.runtime_config().tick(false);
+        n0.runtime_config().heartbeat(false);
+        n0.runtime_config().elect(false);
+
+        let c = n0.effective_config();
+        assert!(!c.enable_tick);
+        assert!(!c.enable_heartbeat);
+        assert!(!c.enable_elect);
+        assert_eq!(c.election_timeout_min, 123);
+
+        assert!(n0.config().enable_tick, "startup config is not changed");
+    }
+
+    Ok(())
+}
@@ -9,6 +9,7 @@ mod fixtures;
 
 mod t10_learner_restart;
 mod t10_single_node;
+mod t10_weighted_voters;
 mod t11_add_learner;
 mod t12_concurrent_write_and_add_learner;
 mod t20_change_membership;
@@ -16,8 +17,12 @@ mod t21_change_membership_cases;
 mod t30_commit_joint_config;
 mod t30_elect_with_new_config;
 mod t31_add_remove_follower;
+mod t31_notify_removed_nodes;
 mod t31_remove_leader;
 mod t31_removed_follower;
+mod t31_removed_from_cluster;
+mod t32_complete_joint_after_leader_crash;
+mod t33_membership_state;
 mod t51_remove_unreachable_follower;
 mod t99_issue_471_adding_learner_uses_uninit_leader_id;
 mod t99_issue_584_replication_state_reverted;
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A voter with more than half of the total weight is elected and commits logs alone.
+///
+/// - Initialize a cluster of 3 voters, node 0 has a weight of 3, others have the default weight 1.
+/// - Node 1 and node 2 are unreachable.
+/// - Node 0 becomes leader and commits logs without any other node.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn weighted_voters() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    for id in [0, 1, 2] {
+        router.new_raft_node(id).await;
+    }
+
+    router.set_unreachable(1, true);
+    router.set_unreachable(2, true);
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!("--- initialize with weights, node 0 is elected alone");
+    {
+        n0.initialize_with_weights(btreeset! {0,1,2}, btreemap! {0=>3}).await?;
+        n0.wait(timeout()).state(ServerState::Leader, "node 0 becomes leader").await?;
+
+        let metrics = n0.metrics().borrow().clone();
+        assert_eq!(3, metrics.membership_config.membership().weight(&0));
+        assert_eq!(1, metrics.membership_config.membership().weight(&1));
+    }
+
+    // log 0: membership; log 1: blank log of leader
+    let mut log_index = 1;
+
+    tracing::info!(log_index, "--- node 0 commits logs alone");
+    {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+        n0.wait(timeout()).applied_index(Some(log_index), "committed by node 0").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
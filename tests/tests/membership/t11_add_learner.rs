@@ -7,6 +7,7 @@ use maplit::btreeset;
 use openraft::error::ChangeMembershipError;
 use openraft::error::ClientWriteError;
 use openraft::error::InProgress;
+use openraft::error::LineRateError;
 use openraft::storage::RaftLogReaderExt;
 use openraft::ChangeMembers;
 use openraft::CommittedLeaderId;
@@ -336,6 +337,65 @@ async fn check_learner_after_leader_transferred() -> Result<()> {
     Ok(())
 }
 
+/// `Raft::await_line_rate()` blocks until the replication to a learner catches up.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn await_line_rate() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            replication_lag_threshold: 0,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "0", 10).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- add unreachable node-1, it does not become line-rate");
+    {
+        router.new_raft_node(1).await;
+        router.set_unreachable(1, true);
+
+        n0.add_learner(1, (), false).await?;
+        log_index += 1;
+
+        let err = n0.await_line_rate(1, Duration::from_millis(200)).await.unwrap_err();
+        match err.api_error() {
+            Some(LineRateError::Timeout(t)) => assert_eq!(1, t.target),
+            _ => panic!("expect Timeout, got: {:?}", err),
+        }
+    }
+
+    tracing::info!(log_index, "--- node-1 becomes reachable and catches up");
+    {
+        router.set_unreachable(1, false);
+
+        let matching = n0.await_line_rate(1, timeout().unwrap()).await?;
+        assert_eq!(Some(log_index), matching.map(|x| x.index));
+    }
+
+    tracing::info!(log_index, "--- unknown node and non-leader");
+    {
+        let err = n0.await_line_rate(9, Duration::from_millis(200)).await.unwrap_err();
+        assert!(
+            matches!(err.api_error(), Some(LineRateError::NotInMembers(_))),
+            "got: {:?}",
+            err
+        );
+
+        let n1 = router.get_raft_handle(&1)?;
+        let err = n1.await_line_rate(1, Duration::from_millis(200)).await.unwrap_err();
+        assert_eq!(Some(0), err.forward_to_leader().unwrap().leader_id);
+    }
+
+    Ok(())
+}
+
 fn timeout() -> Option<Duration> {
     Some(Duration::from_millis(3_000))
 }
@@ -1,10 +1,12 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use maplit::btreemap;
 use maplit::btreeset;
 use openraft::error::ChangeMembershipError;
 use openraft::error::ClientWriteError;
 use openraft::storage::RaftLogReaderExt;
+use openraft::ChangeMembers;
 use openraft::Config;
 use openraft::LogIdOptionExt;
 use openraft::ServerState;
@@ -100,6 +102,195 @@ async fn change_with_new_learner_blocking() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// A learner that is not line-rate can not be promoted to a voter, if
+/// `Config::reject_lagging_new_voters` is enabled.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn change_with_lagging_learner() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            replication_lag_threshold: 1,
+            reject_lagging_new_voters: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 10 logs");
+    {
+        router.client_request_many(0, "lagging", 10).await?;
+        log_index += 10;
+    }
+
+    tracing::info!(log_index, "--- add an unreachable learner in non-blocking mode");
+    {
+        router.new_raft_node(1).await;
+        router.set_network_error(1, true);
+
+        let leader = router.get_raft_handle(&0)?;
+        leader.add_learner(1, (), false).await?;
+        log_index += 1;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "add learner").await?;
+    }
+
+    tracing::info!(log_index, "--- promoting the lagging learner is rejected");
+    {
+        let leader = router.get_raft_handle(&0)?;
+        let res = leader.change_membership([0, 1], false).await;
+        let raft_err = res.unwrap_err();
+        tracing::debug!("raft_err: {:?}", raft_err);
+
+        match raft_err.api_error().unwrap() {
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::LearnerNotReady(err)) => {
+                assert_eq!(1, err.node_id);
+                assert_eq!(Some(log_index), err.last_log_index);
+                assert_eq!(Some(log_index), err.committed.index());
+            }
+            _ => {
+                unreachable!("expect LearnerNotReady")
+            }
+        }
+    }
+
+    tracing::info!(log_index, "--- promoting succeeds once the learner catches up");
+    {
+        router.set_network_error(1, false);
+
+        let leader = router.get_raft_handle(&0)?;
+        leader.await_line_rate(1, Duration::from_millis(3_000)).await?;
+        leader.change_membership([0, 1], false).await?;
+        log_index += 2;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 becomes voter").await?;
+    }
+
+    Ok(())
+}
+
+/// By default a lagging learner, or a node that is not yet a learner, is made a voter at once.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn change_with_lagging_learner_by_default() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            replication_lag_threshold: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 10 logs");
+    {
+        router.client_request_many(0, "lagging", 10).await?;
+        log_index += 10;
+    }
+
+    tracing::info!(log_index, "--- add an unreachable learner in non-blocking mode");
+    {
+        router.new_raft_node(1).await;
+        router.set_network_error(1, true);
+
+        let leader = router.get_raft_handle(&0)?;
+        leader.add_learner(1, (), false).await?;
+        log_index += 1;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "add learner").await?;
+    }
+
+    tracing::info!(log_index, "--- promoting the lagging learner is not rejected");
+    {
+        let leader = router.get_raft_handle(&0)?;
+        let handle = tokio::spawn(async move { leader.change_membership([0, 1], false).await });
+
+        // The joint config can not commit until node-1 is reachable.
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(!handle.is_finished(), "the change waits for node-1");
+
+        router.set_network_error(1, false);
+
+        let res = handle.await?;
+        assert!(res.is_ok(), "change succeeds: {:?}", res);
+        log_index += 2;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 becomes voter").await?;
+    }
+
+    tracing::info!(log_index, "--- add a new node as a voter at once");
+    {
+        router.new_raft_node(2).await;
+
+        let leader = router.get_raft_handle(&0)?;
+        leader.change_membership(ChangeMembers::AddVoters(btreemap! {2 => ()}), false).await?;
+        log_index += 2;
+
+        router.wait(&2, timeout()).applied_index(Some(log_index), "node-2 becomes voter").await?;
+        router.wait(&0, timeout()).voter_ids([0, 1, 2], "voters are [0,1,2]").await?;
+    }
+
+    Ok(())
+}
+
+/// `promote_learner()` turns a learner into a voter and returns the log id of the final config;
+/// a node that is not a learner is rejected.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn promote_learner() -> anyhow::Result<()> {
+    let config = Arc::new(Config { ..Default::default() }.validate()?);
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- promote learner 1");
+    {
+        let log_id = leader.promote_learner(1).await?;
+        log_index += 2;
+
+        assert_eq!(Some(log_index), log_id.index);
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 becomes voter").await?;
+
+        let m = leader.metrics().borrow().clone();
+        assert_eq!(
+            vec![btreeset! {0,1}],
+            m.membership_config.membership().get_joint_config().clone()
+        );
+    }
+
+    tracing::info!(log_index, "--- promoting a voter is rejected");
+    {
+        let raft_err = leader.promote_learner(1).await.unwrap_err();
+
+        match raft_err.api_error().unwrap() {
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::NotLearner(err)) => {
+                assert_eq!(1, err.node_id);
+                assert_eq!(Some(log_index), err.committed.index());
+            }
+            _ => {
+                unreachable!("expect NotLearner")
+            }
+        }
+    }
+
+    tracing::info!(log_index, "--- promoting an unknown node is rejected");
+    {
+        let raft_err = leader.promote_learner(9).await.unwrap_err();
+
+        match raft_err.api_error().unwrap() {
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::LearnerNotFound(err)) => {
+                assert_eq!(9, err.node_id);
+            }
+            _ => {
+                unreachable!("expect LearnerNotFound")
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
 async fn change_without_adding_learner() -> anyhow::Result<()> {
     let config = Arc::new(Config { ..Default::default() }.validate()?);
@@ -123,6 +314,7 @@ async fn change_without_adding_learner() -> anyhow::Result<()> {
         match raft_err.api_error().unwrap() {
             ClientWriteError::ChangeMembershipError(ChangeMembershipError::LearnerNotFound(err)) => {
                 assert_eq!(1, err.node_id);
+                assert_eq!(Some(0), err.committed.index(), "the initial membership log");
             }
             _ => {
                 unreachable!("expect LearnerNotFound")
@@ -209,6 +401,127 @@ async fn change_with_turn_removed_voter_to_learner() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// A change rejected because the previous membership change is not yet committed is retried, if
+/// `Config::change_membership_retries` is set, and succeeds once the previous change is committed.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn change_membership_retry_in_progress() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            change_membership_retries: 10,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {1,2}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- block replication to node-1, the joint config can not commit"
+    );
+    {
+        router.set_network_error(1, true);
+
+        let node = router.get_raft_handle(&0)?;
+        tokio::spawn(async move {
+            let res = node.change_membership([0, 1], false).await;
+            tracing::info!("change membership to [0,1]: {:?}", res);
+        });
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+    }
+
+    tracing::info!(log_index, "--- add voter 2 while the previous change is in progress");
+    let handle = {
+        let node = router.get_raft_handle(&0)?;
+        tokio::spawn(async move { node.change_membership(ChangeMembers::AddVoterIds(btreeset! {2}), false).await })
+    };
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(!handle.is_finished(), "the change keeps retrying");
+
+    tracing::info!(log_index, "--- restore replication to node-1");
+    {
+        router.set_network_error(1, false);
+
+        let res = handle.await?;
+        assert!(res.is_ok(), "retried change succeeds: {:?}", res);
+
+        router
+            .wait(&0, timeout())
+            .voter_ids([0, 1, 2], "voters are [0,1,2] after the retried change")
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Retrying a change is idempotent: a change that failed with `InProgress` and is retried after the
+/// same change has committed its joint config ends in the same uniform config.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn change_membership_retry_is_idempotent() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            change_membership_retries: 10,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- block replication to node-1, the joint config can not commit"
+    );
+    let first = {
+        router.set_network_error(1, true);
+
+        let node = router.get_raft_handle(&0)?;
+        let handle = tokio::spawn(async move { node.change_membership([0, 1], false).await });
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        handle
+    };
+
+    tracing::info!(log_index, "--- propose the same change, it is retried");
+    let second = {
+        let node = router.get_raft_handle(&0)?;
+        let handle = tokio::spawn(async move { node.change_membership([0, 1], false).await });
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(!handle.is_finished(), "the change keeps retrying");
+        handle
+    };
+
+    tracing::info!(log_index, "--- restore replication to node-1, the joint config commits");
+    {
+        router.set_network_error(1, false);
+
+        let res = first.await?;
+        assert!(res.is_ok(), "first change succeeds: {:?}", res);
+
+        let res = second.await?;
+        assert!(res.is_ok(), "retried change succeeds: {:?}", res);
+
+        let leader = router.get_raft_handle(&0)?;
+        leader.wait(timeout()).voter_ids([0, 1], "voters are [0,1]").await?;
+
+        let m = leader.metrics().borrow().clone();
+        assert_eq!(
+            vec![btreeset! {0,1}],
+            m.membership_config.membership().get_joint_config().clone(),
+            "the retried change ends in the uniform config"
+        );
+    }
+
+    Ok(())
+}
+
 fn timeout() -> Option<Duration> {
     Some(Duration::from_millis(1000))
 }
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `notify_removed_nodes` enabled, a removed voter receives the membership log that removes
+/// it and becomes a learner. An unreachable removed node does not block the membership change.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn notify_removed_nodes() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            notify_removed_nodes: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- node-1 is unreachable, thus node-2 is required to commit the joint config"
+    );
+    {
+        router.set_unreachable(1, true);
+    }
+
+    tracing::info!(log_index, "--- change membership from 0,1,2 to 0,3");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.change_membership([0, 3], false).await?;
+        log_index += 2;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "leader commits uniform config").await?;
+    }
+
+    tracing::info!(log_index, "--- removed node-2 is notified and is no longer a voter");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        n2.wait(timeout()).state(ServerState::Learner, "node-2 becomes learner").await?;
+
+        let metrics = n2.metrics().borrow().clone();
+        assert_eq!(Some(log_index), metrics.last_log_index);
+        assert!(!metrics.membership_config.membership().voter_ids().any(|id| id == 2));
+    }
+
+    tracing::info!(log_index, "--- unreachable node-1 is not notified");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout()).state(ServerState::Follower, "node-1 is still a follower").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::raft::AppendEntriesRequest;
+use openraft::testing::blank_ent;
+use openraft::testing::log_id;
+use openraft::testing::membership_ent;
+use openraft::Config;
+use openraft::Vote;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::removed_from_cluster()` returns on a node once it receives the membership log that
+/// removes it, while it keeps waiting on a node that is still a member.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn removed_from_cluster() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            notify_removed_nodes: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let n2 = router.get_raft_handle(&2)?;
+
+    let removed_1 = tokio::spawn(async move { n1.removed_from_cluster().await });
+    let removed_2 = tokio::spawn(async move { n2.removed_from_cluster().await });
+
+    tracing::info!(log_index, "--- change membership from 0,1,2 to 0,1");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.change_membership([0, 1], false).await?;
+        log_index += 2;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "leader commits uniform config").await?;
+    }
+
+    tracing::info!(log_index, "--- node-2 is notified of its removal");
+    {
+        tokio::time::timeout(Duration::from_millis(1_000), removed_2).await???;
+    }
+
+    tracing::info!(log_index, "--- node-1 is still a member");
+    {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!removed_1.is_finished());
+    }
+
+    Ok(())
+}
+
+/// An uncommitted membership log that removes a node is not a removal: `removed_from_cluster()`
+/// does not return when the log is truncated by a new leader.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn removal_truncated_is_not_removed() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            notify_removed_nodes: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n2 = router.get_raft_handle(&2)?;
+
+    let removed_2 = {
+        let n2 = n2.clone();
+        tokio::spawn(async move { n2.removed_from_cluster().await })
+    };
+
+    tracing::info!(
+        log_index,
+        "--- node-2 receives an uncommitted membership log that removes it"
+    );
+    {
+        let req = AppendEntriesRequest {
+            vote: Vote::new_committed(1, 0),
+            prev_log_id: Some(log_id(1, 0, log_index)),
+            entries: vec![membership_ent(1, 0, log_index + 1, vec![btreeset! {0,1}])],
+            leader_commit: Some(log_id(1, 0, log_index)),
+        };
+        let resp = n2.append_entries(req).await?;
+        assert!(resp.is_success());
+
+        n2.wait(timeout()).voter_ids([0, 1], "removal is effective on node-2").await?;
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!removed_2.is_finished(), "the removal is not committed");
+    }
+
+    tracing::info!(log_index, "--- a new leader truncates the membership log on node-2");
+    {
+        let req = AppendEntriesRequest {
+            vote: Vote::new_committed(2, 1),
+            prev_log_id: Some(log_id(1, 0, log_index)),
+            entries: vec![blank_ent(2, 1, log_index + 1)],
+            leader_commit: Some(log_id(2, 1, log_index + 1)),
+        };
+        let resp = n2.append_entries(req).await?;
+        assert!(resp.is_success());
+
+        n2.wait(timeout()).voter_ids([0, 1, 2], "removal is truncated on node-2").await?;
+        n2.wait(timeout()).applied_index(Some(log_index + 1), "node-2 applies the new leader log").await?;
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!removed_2.is_finished(), "node-2 is still a member");
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::storage::RaftLogStorageExt;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Entry;
+use openraft::EntryPayload;
+use openraft::Membership;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// The leader crashes after the joint config log is replicated but before the uniform config log
+/// is proposed. The next leader completes the membership change.
+///
+/// - Bring up a cluster of voters {0,1,2} and learner {3}.
+/// - Shutdown all nodes and append the joint config `[{0,1,2}, {1,2,3}]` proposed by node-0, as if
+///   node-0 crashed right after replicating it.
+/// - Restart nodes except node-0, the new leader should commit the joint config and then the
+///   uniform config `{1,2,3}`, with node-0 retained as a learner.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn complete_joint_after_leader_crash() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!(log_index, "--- shutdown all nodes, append the joint config log");
+    {
+        for id in [0, 1, 2, 3] {
+            let (node, mut sto, sm) = router.remove_node(id).unwrap();
+            node.shutdown().await?;
+
+            sto.blocking_append([Entry {
+                log_id: log_id(1, 0, log_index + 1),
+                context: vec![],
+                payload: EntryPayload::Membership(Membership::new(vec![btreeset! {0,1,2}, btreeset! {1,2,3}], None)),
+            }])
+            .await?;
+
+            if id != 0 {
+                router.new_raft_node_with_sto(id, sto, sm).await;
+            }
+        }
+        log_index += 1;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- without node-0, a new leader completes the membership change"
+    );
+    {
+        // The blank log of the new leader and the uniform config log.
+        log_index += 2;
+
+        for id in [1, 2, 3] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "uniform config is committed").await?;
+        }
+
+        let n1 = router.get_raft_handle(&1)?;
+        let m = n1.metrics().borrow().membership_config.clone();
+
+        assert_eq!(Some(log_index), m.log_id().as_ref().map(|x| x.index));
+        assert_eq!(&vec![btreeset! {1,2,3}], m.membership().get_joint_config());
+        assert_eq!(
+            btreeset! {0},
+            m.membership().learner_ids().collect::<BTreeSet<_>>(),
+            "removed voter is retained as learner"
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::membership_state()` shows a membership change in progress, with the joint config and the
+/// target voter set, until the change is committed.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn membership_state() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- no membership change in progress");
+    {
+        let st = n0.membership_state().await?;
+        assert!(!st.is_in_transition());
+        assert_eq!(&btreeset! {0,1,2}, st.target());
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the joint config can not be committed without node-1 and node-2"
+    );
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let n = n0.clone();
+        let _h = tokio::spawn(async move { n.change_membership([0, 1, 2, 3], false).await });
+        log_index += 1;
+
+        n0.wait(timeout())
+            .metrics(|m| m.last_log_index == Some(log_index), "joint config is appended")
+            .await?;
+
+        let st = n0.membership_state().await?;
+        assert!(st.is_in_transition());
+        assert!(st.effective().membership().is_joint());
+        assert_eq!(
+            &vec![btreeset! {0,1,2}, btreeset! {0,1,2,3}],
+            st.effective().membership().get_joint_config()
+        );
+        assert_eq!(&btreeset! {0,1,2,3}, st.target());
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the change completes once node-1 and node-2 are reachable"
+    );
+    {
+        router.set_network_error(1, false);
+        router.set_network_error(2, false);
+        log_index += 1;
+
+        n0.wait(Some(Duration::from_millis(5_000)))
+            .applied_index(Some(log_index), "uniform config is committed")
+            .await?;
+
+        let st = n0.membership_state().await?;
+        assert!(!st.is_in_transition());
+        assert_eq!(
+            &vec![btreeset! {0,1,2,3}],
+            st.effective().membership().get_joint_config()
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
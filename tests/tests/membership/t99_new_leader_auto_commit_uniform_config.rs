@@ -19,8 +19,8 @@ use crate::fixtures::RaftRouter;
 ///
 /// - brings up 1 leader.
 /// - manually append a joint config log.
-/// - shutdown and restart, it should NOT add another final config log to complete the partial
-/// membership changing
+/// - shutdown and restart, once the joint config is committed, it should add another final config
+/// log to complete the partial membership changing, see `t32_complete_joint_after_leader_crash`.
 #[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
 async fn new_leader_auto_commit_uniform_config() -> Result<()> {
     let config = Arc::new(
@@ -41,6 +41,7 @@ async fn new_leader_auto_commit_uniform_config() -> Result<()> {
     {
         sto.blocking_append([Entry {
             log_id: log_id(1, 0, log_index + 1),
+            context: vec![],
             payload: EntryPayload::Membership(Membership::new(
                 vec![btreeset! {0}, btreeset! {0,1,2}],
                 Some(btreeset! {}),
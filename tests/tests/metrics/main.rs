@@ -7,9 +7,20 @@ mod fixtures;
 // The number indicate the preferred running order for these case.
 // The later tests may depend on the earlier ones.
 
+mod t10_commit_quorum;
 mod t10_current_leader;
+mod t10_events;
+mod t10_fully_replicated_index;
+mod t10_leader_commit_seen;
 mod t10_leader_last_ack;
+mod t10_leadership_epoch;
+mod t10_leadership_status;
+mod t10_liveness;
+mod t10_millis_since_last_ack;
+mod t10_pending_client_writes;
 mod t10_purged;
+mod t10_raft_status;
+mod t10_replication_rejections;
 mod t10_server_metrics_and_data_metrics;
 mod t20_metrics_state_machine_consistency;
 mod t30_leader_metrics;
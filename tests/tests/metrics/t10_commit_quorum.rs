@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `RaftMetrics::commit_quorum` contains the voters that accepted the last committed log, and
+/// does not contain a voter that the commit did not depend on.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn commit_quorum() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a follower is not required for commits");
+    {
+        router.set_unreachable(2, true);
+
+        router.client_request_many(0, "foo", 3).await?;
+        log_index += 3;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "committed by a quorum").await?;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(
+            Some(btreeset! {0,1}),
+            m.commit_quorum,
+            "learner and node-2 are not in it"
+        );
+    }
+
+    tracing::info!(log_index, "--- a follower does not report commit_quorum");
+    {
+        router.wait(&1, timeout()).applied_index(Some(log_index), "follower is up to date").await?;
+
+        let n1 = router.get_raft_handle(&1)?;
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(None, m.commit_quorum);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
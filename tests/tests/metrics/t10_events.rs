@@ -0,0 +1,150 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::StreamExt;
+use maplit::btreeset;
+use openraft::metrics::RaftEvent;
+use openraft::Config;
+use openraft::ServerState;
+use tokio::sync::broadcast::error::TryRecvError;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::events()` delivers every server state, term and leader change, without coalescing.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn events() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let mut events = n1.events();
+
+    tracing::info!(log_index, "--- node-1 becomes leader");
+    {
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).current_leader(1, "node-1 becomes leader").await?;
+
+        let mut got = vec![];
+        loop {
+            match events.try_recv() {
+                Ok(ev) => got.push(ev),
+                Err(TryRecvError::Empty) => break,
+                Err(e) => panic!("unexpected error: {:?}", e),
+            }
+        }
+
+        let expected = [
+            RaftEvent::ServerStateChanged {
+                from: ServerState::Follower,
+                to: ServerState::Candidate,
+            },
+            RaftEvent::TermChanged { term: 2 },
+            RaftEvent::ServerStateChanged {
+                from: ServerState::Candidate,
+                to: ServerState::Leader,
+            },
+            RaftEvent::LeaderChanged { leader: Some(1) },
+        ];
+        for ev in expected {
+            assert!(got.contains(&ev), "{} is delivered, got: {:?}", ev, got);
+        }
+    }
+
+    Ok(())
+}
+
+/// A subscriber that falls behind by more than `Config::event_channel_capacity` events is
+/// informed of the number of missed events.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn events_lagged() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            event_channel_capacity: 2,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let mut events = n1.events();
+
+    tracing::info!(log_index, "--- node-1 becomes leader, producing more than 2 events");
+    {
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).current_leader(1, "node-1 becomes leader").await?;
+
+        match events.try_recv() {
+            Err(TryRecvError::Lagged(n)) => assert!(n >= 1),
+            other => panic!("expect Lagged, got: {:?}", other),
+        }
+
+        // After the lag is reported, the latest events are still delivered.
+        assert!(events.try_recv().is_ok());
+    }
+
+    Ok(())
+}
+
+/// The streams built on `Raft::events()`, such as `Raft::state_transitions()`, end when the
+/// receiver falls behind, instead of silently skipping items, and end when `RaftCore` quits.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn event_streams_end_when_lagged() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            event_channel_capacity: 2,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let mut lagged = n1.state_transitions();
+
+    tracing::info!(log_index, "--- node-1 becomes leader, producing more than 2 events");
+    {
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).current_leader(1, "node-1 becomes leader").await?;
+
+        let got = tokio::time::timeout(Duration::from_millis(1_000), lagged.next()).await?;
+        assert_eq!(None, got, "a lagged stream ends");
+    }
+
+    tracing::info!(log_index, "--- a stream ends when raft shuts down");
+    {
+        let mut terms = n1.term_changes();
+        n1.shutdown().await?;
+
+        let got = tokio::time::timeout(Duration::from_millis(1_000), terms.next()).await?;
+        assert_eq!(None, got);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
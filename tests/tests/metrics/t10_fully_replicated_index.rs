@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `RaftMetrics::fully_replicated_index` is the greatest index replicated to every voter, which
+/// does not advance with the committed index when a voter is behind.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn fully_replicated_index() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- all voters have all logs");
+    {
+        n0.wait(timeout())
+            .metrics(
+                |m| m.fully_replicated_index == Some(log_index),
+                "fully replicated to every voter",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- a lagging voter holds back fully_replicated_index");
+    {
+        router.set_unreachable(2, true);
+
+        router.client_request_many(0, "foo", 3).await?;
+        log_index += 3;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "committed by a quorum").await?;
+        router.wait(&3, timeout()).applied_index(Some(log_index), "learner is up to date").await?;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(Some(log_index - 3), m.fully_replicated_index, "learner does not count");
+    }
+
+    tracing::info!(log_index, "--- the lagging voter catches up");
+    {
+        router.set_unreachable(2, false);
+        n0.trigger().heartbeat().await?;
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.fully_replicated_index == Some(log_index),
+                "fully replicated after node-2 catches up",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
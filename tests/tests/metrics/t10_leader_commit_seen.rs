@@ -0,0 +1,59 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A follower reports the committed log id of the leader it has seen, and when it is seen.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn leader_commit_seen() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- leader does not report leader_commit_seen");
+    {
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(None, m.leader_commit_seen);
+        assert_eq!(None, m.millis_since_leader_commit_seen);
+    }
+
+    tracing::info!(log_index, "--- follower sees the leader commit");
+    {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+        n0.trigger().heartbeat().await?;
+
+        n1.wait(timeout())
+            .metrics(
+                |m| m.leader_commit_seen.map(|x| x.index) == Some(log_index),
+                "node 1 sees the leader commit",
+            )
+            .await?;
+
+        let m = n1.data_metrics().borrow().clone();
+        assert_eq!(Some(log_index), m.leader_commit_seen.map(|x| x.index));
+        assert!(m.millis_since_leader_commit_seen.is_some());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
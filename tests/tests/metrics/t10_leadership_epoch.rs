@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `RaftMetrics::leadership_epoch` increases by one every time a node becomes leader, and does not
+/// change when it becomes a follower.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn leadership_epoch() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- node-0 becomes leader once");
+    {
+        assert_eq!(1, n0.metrics().borrow().leadership_epoch);
+        assert_eq!(0, n1.metrics().borrow().leadership_epoch);
+    }
+
+    tracing::info!(log_index, "--- node-1 becomes leader");
+    {
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+        log_index += 1;
+
+        n0.wait(timeout()).applied_index(Some(log_index), "node-0 receives blank log").await?;
+
+        assert_eq!(1, n0.metrics().borrow().leadership_epoch, "unchanged when deposed");
+        assert_eq!(1, n1.metrics().borrow().leadership_epoch);
+    }
+
+    tracing::info!(log_index, "--- node-0 becomes leader again");
+    {
+        n0.trigger().elect().await?;
+        n0.wait(timeout()).state(ServerState::Leader, "node-0 becomes leader").await?;
+
+        let m = n0.wait(timeout()).metrics(|m| m.leadership_epoch == 2, "node-0 epoch increases").await?;
+        assert!(m.current_term >= 3);
+        assert_eq!(1, n1.metrics().borrow().leadership_epoch);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
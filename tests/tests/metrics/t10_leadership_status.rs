@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::metrics::LeadershipStatus;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A node without a known leader reports `ElectingNewLeader` first, and `Leaderless` only after
+/// `Config::leaderless_grace`.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn leadership_status() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            leaderless_grace: 1_000,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- node-1 knows the leader");
+    {
+        n1.wait(timeout())
+            .metrics(
+                |m| m.leadership_status == LeadershipStatus::HasLeader,
+                "node-1 has leader",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- node-1 starts an election that can not succeed");
+    {
+        router.set_unreachable(0, true);
+        n1.trigger().elect().await?;
+
+        let m = n1.wait(timeout()).metrics(|m| m.current_leader.is_none(), "node-1 loses leader").await?;
+        assert_eq!(LeadershipStatus::ElectingNewLeader, m.leadership_status);
+    }
+
+    tracing::info!(log_index, "--- no leader is elected within the grace period");
+    {
+        n1.wait(Some(Duration::from_millis(3_000)))
+            .metrics(
+                |m| m.leadership_status == LeadershipStatus::Leaderless,
+                "node-1 becomes leaderless",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(500))
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::liveness()` reports the server state, whether a leader is known and how long ago the
+/// leader was last heard from.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn liveness() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- an uninitialized node has no leader");
+    {
+        router.new_raft_node(0).await;
+
+        let l = router.get_raft_handle(&0)?.liveness();
+        assert_eq!(ServerState::Learner, l.state);
+        assert!(!l.has_leader);
+        assert_eq!(None, l.last_heartbeat_age);
+
+        router.remove_node(0);
+    }
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- leader and followers are alive");
+    {
+        let l = router.get_raft_handle(&0)?.liveness();
+        assert_eq!(ServerState::Leader, l.state);
+        assert!(l.has_leader);
+        assert!(l.last_heartbeat_age.is_some());
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 is up to date").await?;
+        let l = router.get_raft_handle(&1)?.liveness();
+        assert_eq!(ServerState::Follower, l.state);
+        assert!(l.has_leader);
+        assert!(l.last_heartbeat_age.is_some());
+    }
+
+    tracing::info!(log_index, "--- heartbeat age grows without heartbeat");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let before = n1.liveness().last_heartbeat_age.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let after = n1.liveness().last_heartbeat_age.unwrap();
+        assert!(after >= before + Duration::from_millis(100));
+    }
+
+    tracing::info!(log_index, "--- shutdown node reports Shutdown");
+    {
+        let n2 = router.remove_node(2).unwrap();
+        n2.0.shutdown().await?;
+
+        let l = n2.0.liveness();
+        assert_eq!(ServerState::Shutdown, l.state);
+        assert!(!l.has_leader);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `RaftMetrics::millis_since_last_ack` keeps being refreshed by heartbeats for a live target, and
+/// keeps growing for a target that stops replying.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn millis_since_last_ack() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- every target acked");
+    {
+        let m = n0
+            .wait(timeout())
+            .metrics(
+                |m| {
+                    m.millis_since_last_ack.as_ref().map(|x| x.values().all(|t| t.is_some()) && x.len() == 3)
+                        == Some(true)
+                },
+                "every target acked",
+            )
+            .await?;
+
+        let acks = m.millis_since_last_ack.unwrap();
+        assert!(!acks.contains_key(&0), "the leader itself is not a target");
+    }
+
+    tracing::info!(log_index, "--- node-2 stops replying");
+    {
+        router.set_network_error(2, true);
+
+        let m = n0
+            .wait(timeout())
+            .metrics(
+                |m| {
+                    let acks = m.millis_since_last_ack.as_ref().unwrap();
+                    acks[&2] > Some(300)
+                },
+                "node-2 is silent for more than 300 ms",
+            )
+            .await?;
+
+        let acks = m.millis_since_last_ack.unwrap();
+        assert!(acks[&1] < Some(300), "node-1 is still acked by heartbeat: {:?}", acks);
+        assert!(acks[&3] < Some(300), "learner is still acked by heartbeat: {:?}", acks);
+    }
+
+    tracing::info!(log_index, "--- a follower does not report it");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(None, m.millis_since_last_ack);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// The leader reports the number of client writes waiting to be committed.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn pending_client_writes() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- no pending writes");
+    {
+        n0.wait(timeout()).metrics(|m| m.pending_client_writes == 0, "no pending writes").await?;
+    }
+
+    tracing::info!(log_index, "--- block replication, writes wait for commit");
+    {
+        router.set_unreachable(1, true);
+
+        for i in 0..3 {
+            let n = n0.clone();
+            tokio::spawn(async move {
+                let res = n.client_write(ClientRequest::make_request("cli", i)).await;
+                tracing::info!("res: {:?}", res);
+            });
+        }
+
+        n0.wait(timeout()).metrics(|m| m.pending_client_writes == 3, "3 pending writes").await?;
+    }
+
+    tracing::info!(log_index, "--- restore replication, pending writes are drained");
+    {
+        router.set_unreachable(1, false);
+
+        // Trigger replication again since heartbeat is disabled.
+        n0.trigger().heartbeat().await?;
+
+        n0.wait(timeout()).metrics(|m| m.pending_client_writes == 0, "pending writes drained").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::metrics::RaftStatus;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::status()` returns the state, term, leader and log position from one metrics value.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn raft_status() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write some logs");
+    {
+        router.client_request_many(0, "foo", 3).await?;
+        log_index += 3;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "logs are applied").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- status of the leader");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let term = n0.metrics().borrow().current_term;
+
+        assert_eq!(
+            RaftStatus {
+                state: ServerState::Leader,
+                current_term: term,
+                leader_id: Some(0),
+                commit_index: Some(log_index),
+                last_log_index: Some(log_index),
+            },
+            n0.status()
+        );
+    }
+
+    tracing::info!(log_index, "--- status of a follower");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let status = n1.status();
+
+        assert_eq!(ServerState::Follower, status.state);
+        assert_eq!(Some(0), status.leader_id);
+        assert_eq!(Some(log_index), status.commit_index);
+        assert_eq!(Some(log_index), status.last_log_index);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::metrics::ReplicationRejection;
+use openraft::raft::VoteRequest;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Vote;
+use tokio::time::sleep;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// When a follower rejects AppendEntries because it has seen a higher vote, the leader reports the
+/// reason in `RaftMetrics::replication_rejections`.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn replication_rejections_higher_vote() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- no rejection when replication is healthy");
+    {
+        let rejections = n0.metrics().borrow().replication_rejections.clone();
+        assert!(rejections.is_empty(), "no rejection: {:?}", rejections);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- node-1 grants a higher vote after the leader lease expires"
+    );
+    {
+        sleep(Duration::from_millis(1_000)).await;
+
+        let res = n1.vote(VoteRequest::new(Vote::new(10, 2), Some(log_id(10, 1, 10)))).await?;
+        assert!(res.vote_granted);
+    }
+
+    tracing::info!(log_index, "--- heartbeat is rejected by node-1 with the higher vote");
+    {
+        n0.trigger().heartbeat().await?;
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.replication_rejections.get(&1) == Some(&ReplicationRejection::HigherVote(Vote::new(10, 2))),
+                "node-1 rejects with HigherVote",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -5,6 +5,7 @@
 mod fixtures;
 
 mod t10_append_entries_partial_success;
+mod t20_idle_noop_interval;
 mod t50_append_entries_backoff;
 mod t50_append_entries_backoff_rejoin;
 mod t51_append_entries_too_large;
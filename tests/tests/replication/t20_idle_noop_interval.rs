@@ -0,0 +1,54 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `idle_noop_interval` set, a leader whose log is idle appends a blank entry and commits it
+/// on every node.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn idle_noop_interval() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            idle_noop_interval: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 3).await?;
+    for id in [0, 1, 2] {
+        router.wait(&id, timeout()).applied_index(Some(log_index), "logs applied").await?;
+    }
+
+    tracing::info!(log_index, "--- an idle leader appends a blank entry");
+    {
+        router
+            .wait(&0, timeout())
+            .metrics(|m| m.last_log_index > Some(log_index), "leader appends a blank entry")
+            .await?;
+
+        let last = router.get_raft_handle(&0)?.metrics().borrow().last_log_index;
+
+        for id in [0, 1, 2] {
+            router
+                .wait(&id, timeout())
+                .metrics(|m| m.last_applied.map(|x| x.index) >= last, "blank entry applied")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -5,6 +5,9 @@
 mod fixtures;
 
 mod t10_build_snapshot;
+mod t11_snapshot_meta;
+mod t20_keep_logs_after_snapshot;
+mod t21_last_purged_log_id;
 mod t35_building_snapshot_does_not_block_append;
 mod t35_building_snapshot_does_not_block_apply;
 mod t60_snapshot_policy_never;
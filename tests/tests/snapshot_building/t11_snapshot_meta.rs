@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::CommittedLeaderId;
+use openraft::Config;
+use openraft::LogId;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::snapshot_meta()` returns the metadata of the current snapshot.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn snapshot_meta() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- no snapshot yet");
+    {
+        let meta = n0.snapshot_meta().await?;
+        assert!(meta.is_none());
+    }
+
+    tracing::info!(log_index, "--- build a snapshot");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout())
+            .snapshot(LogId::new(CommittedLeaderId::new(1, 0), log_index), "build snapshot")
+            .await?;
+
+        let meta = n0.snapshot_meta().await?.unwrap();
+        assert_eq!(
+            Some(LogId::new(CommittedLeaderId::new(1, 0), log_index)),
+            meta.last_log_id
+        );
+        assert_eq!(
+            vec![0],
+            meta.last_membership.membership().voter_ids().collect::<Vec<_>>()
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// With `Config::purge_after_snapshot` disabled, building a snapshot does not purge logs, until
+/// it is explicitly requested.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn keep_logs_after_snapshot() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            purge_after_snapshot: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot, logs are kept");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        n0.wait(timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "snapshot built").await?;
+
+        // Give it a chance to purge.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(None, m.purged, "logs are not purged after snapshot");
+    }
+
+    tracing::info!(log_index, "--- purge explicitly");
+    {
+        n0.trigger().purge_log(log_index).await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purged explicitly").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// `Raft::last_purged_log_id()` returns the boundary below which logs are purged.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn last_purged_log_id() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- no log is purged");
+    {
+        assert_eq!(None, n0.last_purged_log_id().await?);
+    }
+
+    tracing::info!(log_index, "--- build a snapshot, logs are purged");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        n0.wait(timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "snapshot built").await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purged").await?;
+
+        assert_eq!(Some(log_id(1, 0, log_index)), n0.last_purged_log_id().await?);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -6,14 +6,18 @@ mod fixtures;
 
 mod t10_api_install_snapshot;
 mod t10_api_install_snapshot_with_lower_vote;
+mod t11_snapshot_install_progress;
 mod t20_startup_snapshot;
 mod t30_purge_in_snapshot_logs;
 mod t31_snapshot_overrides_membership;
 mod t32_snapshot_uses_prev_snap_membership;
 mod t33_snapshot_delete_conflict_logs;
 mod t34_replication_does_not_block_purge;
+mod t35_cancel_snapshot_to_removed_node;
 mod t50_snapshot_line_rate_to_snapshot;
 mod t50_snapshot_when_lacking_log;
 mod t51_after_snapshot_add_learner_and_request_a_log;
+mod t52_snapshot_installed;
+mod t53_preseeded_learner_skips_snapshot;
 mod t60_snapshot_chunk_size;
 mod t90_issue_808_snapshot_to_unreachable_node_should_not_block;
@@ -38,6 +38,7 @@ async fn install_snapshot_lower_vote() -> Result<()> {
         vote: Vote::new_committed(2, 1),
         meta: SnapshotMeta {
             snapshot_id: "ss1".into(),
+            checksum: None,
             last_log_id: Some(log_id(1, 0, 0)),
             last_membership: Default::default(),
         },
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::metrics::SnapshotInstallProgress;
+use openraft::raft::InstallSnapshotRequest;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::SnapshotMeta;
+use openraft::Vote;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// The progress of receiving a snapshot is reported in metrics, as chunks arrive.
+///
+/// What does this test do?
+///
+/// - build a stable single node cluster.
+/// - send install_snapshot chunks and check `RaftMetrics::installing_snapshot`.
+/// - send a chunk that can not be accepted and check the progress is kept.
+/// - begin receiving another snapshot and check the progress is replaced.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn snapshot_install_progress() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n = router.remove_node(0).unwrap();
+    let make_req = |offset: u64| InstallSnapshotRequest {
+        // force it to be a follower
+        vote: Vote::new_committed(2, 1),
+        meta: SnapshotMeta {
+            snapshot_id: "ss1".into(),
+            checksum: None,
+            last_log_id: Some(log_id(1, 0, 5)),
+            last_membership: Default::default(),
+        },
+        offset,
+        data: vec![1, 2, 3],
+        done: false,
+    };
+
+    tracing::info!(log_index, "--- no snapshot is being received");
+    {
+        let m = n.0.metrics().borrow().clone();
+        assert_eq!(None, m.installing_snapshot);
+    }
+
+    tracing::info!(log_index, "--- progress is reported for every chunk");
+    {
+        n.0.install_snapshot(make_req(0)).await?;
+        n.0.wait(timeout())
+            .metrics(
+                |m| {
+                    m.installing_snapshot
+                        == Some(SnapshotInstallProgress {
+                            snapshot_id: "ss1".into(),
+                            last_log_id: Some(log_id(1, 0, 5)),
+                            bytes_received: 3,
+                        })
+                },
+                "received 3 bytes",
+            )
+            .await?;
+
+        n.0.install_snapshot(make_req(3)).await?;
+        n.0.wait(timeout())
+            .metrics(
+                |m| m.installing_snapshot.as_ref().map(|p| p.bytes_received) == Some(6),
+                "received 6 bytes",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- a rejected chunk does not change the progress");
+    {
+        let mut req = make_req(3);
+        req.meta.snapshot_id = "ss2".into();
+        let res = n.0.install_snapshot(req).await;
+        assert!(res.is_err());
+
+        let m = n.0.metrics().borrow().clone();
+        let progress = m.installing_snapshot.unwrap();
+        assert_eq!("ss1", progress.snapshot_id);
+        assert_eq!(6, progress.bytes_received);
+    }
+
+    tracing::info!(log_index, "--- receiving another snapshot replaces the progress");
+    {
+        let mut req = make_req(0);
+        req.meta.snapshot_id = "ss2".into();
+        req.meta.last_log_id = Some(log_id(1, 0, 6));
+        n.0.install_snapshot(req).await?;
+
+        n.0.wait(timeout())
+            .metrics(
+                |m| {
+                    m.installing_snapshot
+                        == Some(SnapshotInstallProgress {
+                            snapshot_id: "ss2".into(),
+                            last_log_id: Some(log_id(1, 0, 6)),
+                            bytes_received: 3,
+                        })
+                },
+                "receiving ss2",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
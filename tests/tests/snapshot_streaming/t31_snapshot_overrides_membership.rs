@@ -93,6 +93,7 @@ async fn snapshot_overrides_membership() -> Result<()> {
                 prev_log_id: None,
                 entries: vec![blank_ent(0, 0, 0), Entry {
                     log_id: LogId::new(CommittedLeaderId::new(1, 0), 1),
+                    context: vec![],
                     payload: EntryPayload::Membership(Membership::new(vec![btreeset! {2,3}], None)),
                 }],
                 leader_commit: Some(LogId::new(CommittedLeaderId::new(0, 0), 0)),
@@ -99,6 +99,7 @@ async fn snapshot_delete_conflicting_logs() -> Result<()> {
                 // conflict membership will be replaced with membership in snapshot
                 Entry {
                     log_id: LogId::new(CommittedLeaderId::new(1, 0), 2),
+                    context: vec![],
                     payload: EntryPayload::Membership(Membership::new(vec![btreeset! {2,3}], None)),
                 },
                 blank_ent(1, 0, 3),
@@ -112,6 +113,7 @@ async fn snapshot_delete_conflicting_logs() -> Result<()> {
                 // another conflict membership, will be removed
                 Entry {
                     log_id: LogId::new(CommittedLeaderId::new(1, 0), 11),
+                    context: vec![],
                     payload: EntryPayload::Membership(Membership::new(vec![btreeset! {4,5}], None)),
                 },
             ],
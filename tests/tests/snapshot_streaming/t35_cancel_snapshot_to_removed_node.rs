@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::testing::log_id;
+use openraft::ChangeMembers;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// When a learner is removed while a snapshot is being streamed to it, the leader stops sending
+/// snapshot chunks at once.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn cancel_snapshot_to_removed_node() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            // Send the snapshot in many small chunks so that it takes a long time.
+            snapshot_max_chunk_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write logs, build snapshot and purge logs");
+    {
+        log_index += router.client_request_many(0, "0", 100).await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purged").await?;
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- add learner 1, it receives a snapshot");
+    {
+        router.network_send_delay(20);
+        router.new_raft_node(1).await;
+        n0.add_learner(1, (), false).await?;
+        log_index += 1;
+
+        let mut started = false;
+        for _ in 0..100 {
+            if install_snapshot_count(&router) > 0 {
+                started = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(started, "snapshot streaming started");
+    }
+
+    tracing::info!(log_index, "--- remove learner 1 while streaming snapshot");
+    {
+        n0.change_membership(ChangeMembers::RemoveNodes(btreeset! {1}), false).await?;
+
+        // Let an outstanding chunk finish.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let count = install_snapshot_count(&router);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let count_later = install_snapshot_count(&router);
+
+        assert_eq!(count, count_later, "no more snapshot chunk is sent to removed node");
+
+        let m1 = router.get_metrics(&1)?;
+        assert_eq!(None, m1.snapshot, "snapshot streaming is not finished");
+    }
+
+    Ok(())
+}
+
+fn install_snapshot_count(router: &RaftRouter) -> u64 {
+    router.get_rpc_count().get(&RPCTypes::InstallSnapshot).copied().unwrap_or_default()
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
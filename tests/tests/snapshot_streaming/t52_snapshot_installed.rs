@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::StreamExt;
+use maplit::btreeset;
+use openraft::CommittedLeaderId;
+use openraft::Config;
+use openraft::LogId;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A snapshot installed on a learner is delivered by `Raft::snapshot_installed()`.
+///
+/// - build a single node cluster and write enough logs to build a snapshot and purge logs.
+/// - add a learner, which can only be caught up with the snapshot.
+/// - assert the learner yields the meta of the installed snapshot.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn snapshot_installed() -> Result<()> {
+    let snapshot_threshold: u64 = 20;
+
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::LogsSinceLast(snapshot_threshold),
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- send just enough logs to trigger snapshot");
+    {
+        router.client_request_many(0, "0", (snapshot_threshold - 1 - log_index) as usize).await?;
+        log_index = snapshot_threshold - 1;
+
+        router
+            .wait_for_snapshot(
+                &btreeset![0],
+                LogId::new(CommittedLeaderId::new(1, 0), log_index),
+                None,
+                "snapshot",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- add learner, it is caught up with the snapshot");
+    {
+        router.new_raft_node(1).await;
+        let n1 = router.get_raft_handle(&1)?;
+        let mut installed = n1.snapshot_installed();
+
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait_for_log(&btreeset![0, 1], Some(log_index), None, "add learner").await?;
+
+        let meta = tokio::time::timeout(Duration::from_millis(1_000), installed.next()).await?;
+        let meta = meta.expect("snapshot meta");
+        assert_eq!(
+            Some(LogId::new(CommittedLeaderId::new(1, 0), snapshot_threshold - 1)),
+            meta.last_log_id
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::storage::RaftStateMachine;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::RaftRouter;
+
+/// A learner whose state machine is pre-seeded with the leader's snapshot receives only the log
+/// tail, without a snapshot being sent to it.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn preseeded_learner_skips_snapshot() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot on the leader and purge logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        n0.wait(timeout()).applied_index(Some(log_index), "logs applied").await?;
+
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "snapshot built").await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "logs purged").await?;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- start node-1 with the leader's snapshot shipped out of band"
+    );
+    {
+        let snapshot = n0.get_snapshot().await?.unwrap();
+
+        let (log_store, mut sm) = router.new_store();
+        sm.install_snapshot(&snapshot.meta, snapshot.snapshot).await?;
+
+        router.new_raft_node_with_sto(1, log_store, sm).await;
+    }
+
+    tracing::info!(log_index, "--- write more logs and add node-1 as learner");
+    {
+        log_index += router.client_request_many(0, "0", 5).await?;
+
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 receives the log tail").await?;
+    }
+
+    tracing::info!(log_index, "--- no snapshot is sent to node-1");
+    {
+        let count = router.get_rpc_count().get(&RPCTypes::InstallSnapshot).copied();
+        assert_eq!(None, count);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::MemStateMachine;
+use crate::fixtures::RaftRouter;
+
+/// Access the state machine through `Raft::with_state_machine()`.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn with_state_machine() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 3).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    n0.wait(timeout()).applied_index(Some(log_index), "logs applied").await?;
+
+    tracing::info!(log_index, "--- read the state machine after ensure_linearizable()");
+    {
+        let read_log_id = n0.ensure_linearizable().await?;
+
+        let last_applied = n0
+            .with_state_machine(|sm: &mut MemStateMachine| {
+                Box::pin(async move { sm.get_state_machine().await.last_applied_log })
+            })
+            .await??;
+
+        assert_eq!(Some(log_index), last_applied.map(|x| x.index));
+        assert!(read_log_id <= last_applied);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
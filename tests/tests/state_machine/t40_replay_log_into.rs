@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::storage::replay_log_into;
+use openraft::storage::ReplayObserver;
+use openraft::Config;
+use openraft::Entry;
+use openraft::LogId;
+use openraft::RaftLogId;
+use openraft_memstore::ClientResponse;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::init_default_ut_tracing;
+use crate::fixtures::MemStateMachine;
+use crate::fixtures::RaftRouter;
+
+/// Records every step of a replay.
+#[derive(Default)]
+struct Recorder {
+    before: Vec<u64>,
+    after: Vec<(u64, ClientResponse)>,
+}
+
+impl ReplayObserver<TypeConfig, MemStateMachine> for Recorder {
+    fn before_apply(&mut self, _sm: &mut MemStateMachine, entry: &Entry<TypeConfig>) {
+        self.before.push(entry.get_log_id().index);
+    }
+
+    fn after_apply(&mut self, _sm: &mut MemStateMachine, log_id: &LogId<u64>, response: &ClientResponse) {
+        self.after.push((log_id.index, response.clone()));
+    }
+}
+
+/// Replay the log of a node into a fresh state machine, without a Raft node.
+#[async_entry::test(worker_threads = 8, init = "init_default_ut_tracing()", tracing_span = "debug")]
+async fn replay_log_into_fresh_state_machine() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    log_index += router.client_request_many(0, "foo", 5).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    n0.wait(timeout()).applied_index(Some(log_index), "logs applied").await?;
+
+    let (mut log_store, sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- replay all logs into a fresh state machine");
+    {
+        let (_, mut sm) = router.new_store();
+        let mut recorder = Recorder::default();
+
+        let last = replay_log_into(&mut log_store, &mut sm, 0, log_index + 1, &mut recorder).await?;
+        assert_eq!(Some(log_index), last.map(|x| x.index));
+
+        let indexes = (0..=log_index).collect::<Vec<_>>();
+        assert_eq!(indexes, recorder.before);
+        assert_eq!(indexes, recorder.after.iter().map(|(i, _)| *i).collect::<Vec<_>>());
+
+        let want = sm0.get_state_machine().await;
+        let got = sm.get_state_machine().await;
+        assert_eq!(want.last_applied_log, got.last_applied_log);
+        assert_eq!(want.client_status, got.client_status);
+    }
+
+    tracing::info!(log_index, "--- replay an empty range");
+    {
+        let (_, mut sm) = router.new_store();
+        let last = replay_log_into(&mut log_store, &mut sm, 3, 3, &mut ()).await?;
+        assert_eq!(None, last);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}